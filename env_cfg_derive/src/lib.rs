@@ -1,80 +1,659 @@
-use heck::ToSnekCase;
+use heck::{ToSnekCase, ToUpperCamelCase};
 use proc_macro::TokenStream;
-use quote::quote;
-use syn::{Data, DeriveInput, Field, Fields, Lit, Meta, parse_macro_input, spanned::Spanned};
+use proc_macro2::Span;
+use quote::{format_ident, quote};
+use syn::{
+    Data, DataEnum, DeriveInput, Field, Fields, Lit, Meta, Token, parse_macro_input,
+    punctuated::Punctuated, spanned::Spanned,
+};
 
-const SUPPORTED_STRUCT_ATTRIBUTES: &[&str] = &[r#"prefix = "<PREFIX>""#, "no_prefix"];
+const SUPPORTED_STRUCT_ATTRIBUTES: &[&str] = &[
+    r#"prefix = "<PREFIX>""#,
+    "no_prefix",
+    r#"expand | expand = "error" | expand = "empty""#,
+    r#"json_env = "<VAR_NAME>""#,
+    "empty_as_none",
+    r#"tag = "<VAR_NAME>" (enums)"#,
+    r#"rename_all = "SCREAMING_SNAKE_CASE" | "ACRONYM_SNAKE_CASE""#,
+    r#"validate = "<VALIDATOR_FN>""#,
+    r#"rename_all_values = "lowercase" | "UPPERCASE""#,
+    "constructor",
+    "keys",
+    "redundant_vars",
+    "strict",
+    "propagate_prefix",
+    "global",
+    "dotted",
+    r#"sub_prefix = "<SEGMENT>""#,
+    r#"profile_var = "<VAR_NAME>""#,
+    r#"group("<FIELD>", "<FIELD>", ...)"#,
+    r#"derive_from = "<VAR_NAME>", derive_with = "<SPLIT_FN>""#,
+];
 const SUPPORTED_FIELD_ATTRIBUTES: &[&str] = &[
     "skip",
     "nested",
-    r#"env = "<VAR_NAME>""#,
+    "independent",
+    "prefix_from_field",
+    r#"defaults(<VAR_NAME> = "<VALUE>", ...)"#,
+    "default_if_absent",
+    r#"enabled_by = "<VAR_NAME>""#,
+    "error_if_set",
+    r#"env = "<VAR_NAME>" | env = ["<VAR_NAME>", ...]"#,
+    r#"rename = "<VAR_NAME>""#,
+    r#"alias(env = "<VAR_NAME>") | alias(env = "<VAR_NAME>", parse_with = "<PARSER_FN>")"#,
+    r#"prefix = "<PREFIX>""#,
     "default = <DEFAULT_VALUE>",
     r#"parse_with = "<PARSER_FN>""#,
+    r#"parse_with_env = "<LOADER_FN>""#,
+    r#"transform = "<TRANSFORM_FN>""#,
+    r#"validate_map = "<VALIDATE_FN>""#,
+    "clamp(<MIN>, <MAX>)",
+    r#"on_clamp = "<WARN_FN>""#,
+    r#"on_parse_error = "fail" | "default" | "none""#,
+    r#"sections = "<PREFIX>""#,
+    "indexed_list",
+    "human_int",
+    "duration_secs_f64",
+    "radix_auto",
+    "flag",
+    "flags",
+    "try_from",
+    "radix = <2..=36>",
+    "unquote",
+    r#"trim_matches = "<CHARS>""#,
+    r#"trim_prefix = "<PREFIX>""#,
+    "empty_as_none",
+    "allow_empty",
+    "required",
+    r#"expand | expand = "error" | expand = "empty""#,
+    r#"default_from = "<FIELD>""#,
+    r#"default_env = "<VAR_NAME>""#,
+    "preserve_case",
 ];
 
-#[derive(Debug, Clone)]
+const SUPPORTED_VARIANT_ATTRIBUTES: &[&str] = &[r#"rename = "<TAG_VALUE>""#];
+
+/// Struct-level configuration parsed from `#[env_cfg(...)]` attributes on the struct
+#[derive(Clone)]
+struct StructAttributes {
+    prefix_config: PrefixConfig,
+    /// Default `${VAR}` expansion mode for all fields
+    expand: Option<ExpandMode>,
+    /// Env var holding the whole struct as JSON, used instead of per-field loading when set
+    json_env: Option<String>,
+    /// Treat variables set to an empty string as unset, unless a field opts out
+    empty_as_none: bool,
+    /// Env var selecting the variant of an enum
+    tag: Option<(String, Span)>,
+    /// How struct and field names are converted to env var names
+    name_case: NameCase,
+    /// Function checking invariants across fields of the loaded struct
+    validate: Option<(syn::ExprPath, Span)>,
+    /// Case all values are converted to before parsing, unless a field opts out
+    value_case: Option<ValueCase>,
+    /// Generate a `new` function taking every field
+    constructor: Option<Span>,
+    /// Generate a `<Name>Key` enum with a variant per field, and `env_name` and `get` taking it
+    keys: Option<Span>,
+    /// Generate `redundant_vars` and `redundant_env_vars`, listing variables set to their default
+    redundant_vars: Option<Span>,
+    /// Ignore the declared defaults of fields, requiring their variables to be set
+    strict: Option<Span>,
+    /// Env var whose value is split into the variables of several fields, and the function
+    /// splitting it
+    derive_from: Option<(String, syn::ExprPath, Span)>,
+    /// Load `nested` fields with this struct's prefix prepended to their variables, unless a
+    /// field is `independent`
+    propagate_prefix: Option<Span>,
+    /// Generate `init` and `global` for a process-wide instance
+    global: Option<Span>,
+    /// Env var naming the active profile, whose `<PROFILE>_<VAR>` variables override `<VAR>`
+    profile_var: Option<(String, Span)>,
+    /// Groups of fields whose variables must be set all together or not at all
+    groups: Vec<(Vec<syn::LitStr>, Span)>,
+}
+
+#[derive(Clone)]
 enum PrefixConfig {
-    /// Use struct name as prefix (default behavior)
+    /// Use struct name as prefix (default behavior), followed by the `sub_prefix` segment if set
     StructName(String),
     /// Use custom prefix
     Custom(String),
+    /// Use the value of a `&str` const as prefix, read when the config is loaded
+    Const(syn::ExprPath),
     /// No prefix
     None,
 }
 
+/// Join a prefix and a field name with a single underscore, so `prefix = "APP_"` and a field
+/// named `_port` still give `APP_PORT`. With `dotted`, join them with a `.` and keep them
+/// lowercase instead. Keep in sync with `__private::prefixed_name` and `__private::dotted_name`
+fn join_prefixed(prefix: &str, field_name: &str, name_case: NameCase) -> String {
+    if let NameCase::Dotted = name_case {
+        return format!(
+            "{}.{}",
+            prefix.trim_end_matches('.'),
+            field_name.trim_start_matches(['.', '_'])
+        )
+        .to_ascii_lowercase();
+    }
+    format!(
+        "{}_{}",
+        prefix.trim_end_matches('_'),
+        field_name.trim_start_matches('_')
+    )
+    .to_ascii_uppercase()
+}
+
 impl PrefixConfig {
-    fn apply_to_field(&self, field_name: &str) -> String {
+    /// Parse the value of a `prefix` attribute, either a string literal or a path to a const
+    fn from_expr(expr: &syn::Expr) -> syn::Result<Self> {
+        match expr {
+            syn::Expr::Lit(syn::ExprLit {
+                lit: Lit::Str(lit_str),
+                ..
+            }) => {
+                let prefix = lit_str.value();
+                if !prefix.is_empty() {
+                    validate_env_name(&prefix, lit_str.span())?;
+                }
+                Ok(PrefixConfig::Custom(prefix))
+            }
+            syn::Expr::Path(path) => Ok(PrefixConfig::Const(path.clone())),
+            other => Err(syn::Error::new(
+                other.span(),
+                "prefix must be a string literal or a path to a `&str` const",
+            )),
+        }
+    }
+
+    /// The env var name of a field, as an expression evaluating to `&str`
+    fn apply_to_field(&self, field_name: &str, name_case: NameCase) -> proc_macro2::TokenStream {
+        match self {
+            PrefixConfig::Const(prefix) if name_case.is_dotted() => {
+                quote! { &::env_cfg::__private::dotted_name(#prefix, #field_name) }
+            }
+            PrefixConfig::Const(prefix) => {
+                quote! { &::env_cfg::__private::prefixed_name(#prefix, #field_name) }
+            }
+            _ => {
+                let env_name = self.static_field_name(field_name, name_case);
+                quote! { #env_name }
+            }
+        }
+    }
+
+    /// The env var name of a field, without the prefix if it is only known at runtime
+    fn static_field_name(&self, field_name: &str, name_case: NameCase) -> String {
+        match self {
+            PrefixConfig::StructName(prefix) | PrefixConfig::Custom(prefix) => {
+                join_prefixed(prefix, field_name, name_case)
+            }
+            PrefixConfig::Const(_) | PrefixConfig::None => name_case.apply_case(field_name),
+        }
+    }
+
+    /// The prefix as an expression evaluating to `&str`, or `None` without a prefix
+    fn to_expr(&self) -> Option<proc_macro2::TokenStream> {
+        match self {
+            PrefixConfig::StructName(prefix) | PrefixConfig::Custom(prefix) => {
+                Some(quote! { #prefix })
+            }
+            PrefixConfig::Const(prefix) => Some(quote! { #prefix }),
+            PrefixConfig::None => None,
+        }
+    }
+
+    /// Replace `{prefix}` placeholders in a custom env var name with the resolved prefix.
+    /// Without a prefix, the placeholder and an underscore or `.` following it are removed.
+    fn apply_to_template(&self, template: &str, name_case: NameCase) -> proc_macro2::TokenStream {
+        match (self, self.static_template(template, name_case)) {
+            (_, Some(env_name)) => quote! { #env_name },
+            (PrefixConfig::Const(prefix), None) if name_case.is_dotted() => {
+                quote! { &::env_cfg::__private::dotted_template(#template, #prefix) }
+            }
+            (PrefixConfig::Const(prefix), None) => {
+                quote! { &::env_cfg::__private::prefixed_template(#template, #prefix) }
+            }
+            (_, None) => unreachable!("only a const prefix is resolved at runtime"),
+        }
+    }
+
+    /// A custom env var name with `{prefix}` replaced, or `None` if the prefix is only known
+    /// at runtime
+    fn static_template(&self, template: &str, name_case: NameCase) -> Option<String> {
         match self {
-            PrefixConfig::StructName(struct_name) => {
-                format!("{}_{}", struct_name, field_name).to_ascii_uppercase()
+            PrefixConfig::StructName(prefix) | PrefixConfig::Custom(prefix) => {
+                Some(template.replace("{prefix}", &name_case.apply_case(prefix)))
             }
-            PrefixConfig::Custom(prefix) => {
-                format!("{}_{}", prefix, field_name).to_ascii_uppercase()
+            PrefixConfig::Const(_) => None,
+            PrefixConfig::None => Some(
+                template
+                    .replace("{prefix}_", "")
+                    .replace("{prefix}.", "")
+                    .replace("{prefix}", ""),
+            ),
+        }
+    }
+
+    /// The full env var name of a field, or `None` if the prefix is only known at runtime
+    fn static_full_name(&self, field_name: &str, name_case: NameCase) -> Option<String> {
+        match self {
+            PrefixConfig::Const(_) => None,
+            _ => Some(self.static_field_name(field_name, name_case)),
+        }
+    }
+}
+
+/// How struct and field names are converted to env var names (before uppercasing)
+#[derive(Debug, Clone, Copy, Default)]
+enum NameCase {
+    /// Split the struct name into words at every case boundary, including inside runs of
+    /// capitals (`HTTPServerConfig` -> `http_server_config`, `OAuthConfig` -> `o_auth_config`).
+    /// Field names are used as written.
+    #[default]
+    ScreamingSnake,
+    /// Split struct and field names only where a lowercase letter or digit is followed by a
+    /// capital, so runs of capitals stay together (`OAuthConfig` -> `OAuth_Config`,
+    /// `HTTPServerConfig` -> `HTTPServer_Config`)
+    AcronymSnake,
+    /// Split the struct name like `ScreamingSnake`, but join the prefix and field name with a
+    /// `.` and keep them lowercase (`AppConfig` and `port` -> `app_config.port`). Set by `dotted`
+    Dotted,
+}
+
+impl NameCase {
+    fn from_value(value: &str) -> Option<Self> {
+        match value {
+            "SCREAMING_SNAKE_CASE" => Some(NameCase::ScreamingSnake),
+            "ACRONYM_SNAKE_CASE" => Some(NameCase::AcronymSnake),
+            _ => None,
+        }
+    }
+
+    fn struct_name(&self, name: &str) -> String {
+        match self {
+            NameCase::ScreamingSnake | NameCase::Dotted => name.to_snek_case(),
+            NameCase::AcronymSnake => split_at_lowercase_boundaries(name),
+        }
+    }
+
+    fn field_name(&self, name: &str) -> String {
+        match self {
+            NameCase::ScreamingSnake | NameCase::Dotted => name.to_string(),
+            NameCase::AcronymSnake => split_at_lowercase_boundaries(name),
+        }
+    }
+
+    fn is_dotted(&self) -> bool {
+        matches!(self, NameCase::Dotted)
+    }
+
+    /// Convert a derived name to the case env var names are written in
+    fn apply_case(&self, name: &str) -> String {
+        if self.is_dotted() {
+            name.to_ascii_lowercase()
+        } else {
+            name.to_ascii_uppercase()
+        }
+    }
+}
+
+/// Insert `_` wherever a lowercase letter or digit is followed by an uppercase letter
+fn split_at_lowercase_boundaries(name: &str) -> String {
+    let mut split = String::with_capacity(name.len());
+    let mut previous: Option<char> = None;
+    for c in name.chars() {
+        if c.is_uppercase() && previous.is_some_and(|p| p.is_lowercase() || p.is_ascii_digit()) {
+            split.push('_');
+        }
+        split.push(c);
+        previous = Some(c);
+    }
+    split
+}
+
+/// Case values are converted to before parsing, set by `rename_all_values`
+#[derive(Debug, Clone, Copy)]
+enum ValueCase {
+    Lowercase,
+    Uppercase,
+}
+
+impl ValueCase {
+    fn from_value(value: &str) -> Option<Self> {
+        match value {
+            "lowercase" => Some(ValueCase::Lowercase),
+            "UPPERCASE" => Some(ValueCase::Uppercase),
+            _ => None,
+        }
+    }
+
+    /// Expression converting the `String` named `value`
+    fn convert(&self) -> proc_macro2::TokenStream {
+        match self {
+            ValueCase::Lowercase => quote! { value.to_lowercase() },
+            ValueCase::Uppercase => quote! { value.to_uppercase() },
+        }
+    }
+
+    /// `value` converted at compile time, like `convert` does at runtime
+    fn apply(&self, value: &str) -> String {
+        match self {
+            ValueCase::Lowercase => value.to_lowercase(),
+            ValueCase::Uppercase => value.to_uppercase(),
+        }
+    }
+}
+
+/// How `${VAR}` references to unset variables are handled when expanding values
+#[derive(Debug, Clone, Copy)]
+enum ExpandMode {
+    /// Fail with `EnvConfigError::Missing` (default)
+    Error,
+    /// Replace the reference with an empty string
+    Empty,
+}
+
+impl ExpandMode {
+    fn from_meta(meta: &Meta) -> syn::Result<Self> {
+        match meta {
+            Meta::Path(_) => Ok(ExpandMode::Error),
+            Meta::NameValue(name_value) => match &name_value.value {
+                syn::Expr::Lit(syn::ExprLit {
+                    lit: Lit::Str(lit_str),
+                    ..
+                }) if lit_str.value() == "error" => Ok(ExpandMode::Error),
+                syn::Expr::Lit(syn::ExprLit {
+                    lit: Lit::Str(lit_str),
+                    ..
+                }) if lit_str.value() == "empty" => Ok(ExpandMode::Empty),
+                other => Err(syn::Error::new(
+                    other.span(),
+                    r#"expand must be one of "error" or "empty""#,
+                )),
+            },
+            Meta::List(list) => Err(syn::Error::new(
+                list.span(),
+                r#"expand must be one of "error" or "empty""#,
+            )),
+        }
+    }
+
+    /// Generate an expression expanding `value`, loaded from the variable `name`
+    fn expand(
+        &self,
+        name: &proc_macro2::TokenStream,
+        value: proc_macro2::TokenStream,
+    ) -> proc_macro2::TokenStream {
+        let missing_as_empty = matches!(self, ExpandMode::Empty);
+        quote! { ::env_cfg::__private::expand_var(source, #name, #value, #missing_as_empty)? }
+    }
+}
+
+/// What happens when the value of a variable that is set fails to parse
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ParseErrorPolicy {
+    /// Fail with `EnvConfigError::Parse` (default)
+    Fail,
+    /// Use the declared default, as if the variable was unset
+    Default,
+    /// Use `None`, for `Option` fields
+    None,
+}
+
+impl ParseErrorPolicy {
+    fn from_expr(expr: &syn::Expr) -> syn::Result<Self> {
+        match expr {
+            syn::Expr::Lit(syn::ExprLit {
+                lit: Lit::Str(lit_str),
+                ..
+            }) if lit_str.value() == "fail" => Ok(ParseErrorPolicy::Fail),
+            syn::Expr::Lit(syn::ExprLit {
+                lit: Lit::Str(lit_str),
+                ..
+            }) if lit_str.value() == "default" => Ok(ParseErrorPolicy::Default),
+            syn::Expr::Lit(syn::ExprLit {
+                lit: Lit::Str(lit_str),
+                ..
+            }) if lit_str.value() == "none" => Ok(ParseErrorPolicy::None),
+            other => Err(syn::Error::new(
+                other.span(),
+                r#"on_parse_error must be one of "fail", "default" or "none""#,
+            )),
+        }
+    }
+}
+
+/// How the (preprocessed) string value of a field is parsed to the field type
+enum ValueParser {
+    /// `FromStr`
+    FromStr,
+    /// `FromStr` after stripping `_` and `,` digit group separators
+    HumanInt,
+    /// `Duration` from a number of seconds that may have a fractional part
+    DurationSecsF64,
+    /// Integer in the given radix, or in the radix chosen by its `0x`/`0o`/`0b` prefix
+    Radix(Option<u32>),
+    /// `bool` that is `true` when set to an empty value
+    Flag,
+    /// Comma-separated flag names, with `FromFlags`
+    Flags,
+    /// Custom parser function with the signature `fn(String) -> T`
+    With(syn::ExprPath),
+    /// `TryFrom<String>`
+    TryFrom,
+    /// `SecretString` or `SecretBytes`, taking ownership of the value instead of copying it
+    Secret,
+}
+
+impl ValueParser {
+    /// Generate an expression parsing a `value: String` loaded from the variable `name`
+    fn parse(&self, name: &proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+        match self {
+            ValueParser::FromStr => quote! { ::env_cfg::parse_value(#name, &value)? },
+            ValueParser::HumanInt => quote! {
+                ::env_cfg::parse_human_int(&value)
+                    .map_err(|e| ::env_cfg::EnvConfigError::Parse(
+                        ::env_cfg::__private::ToString::to_string(#name),
+                        e,
+                    ))?
+            },
+            ValueParser::DurationSecsF64 => quote! {
+                ::env_cfg::parse_duration_secs_f64(&value)
+                    .map_err(|e| ::env_cfg::EnvConfigError::Parse(
+                        ::env_cfg::__private::ToString::to_string(#name),
+                        e,
+                    ))?
+            },
+            ValueParser::Radix(radix) => {
+                let parse = match radix {
+                    Some(radix) => quote! { ::env_cfg::parse_int_radix(&value, #radix) },
+                    None => quote! { ::env_cfg::parse_int_radix_auto(&value) },
+                };
+                quote! {
+                    #parse.map_err(|e| ::env_cfg::EnvConfigError::Parse(
+                        ::env_cfg::__private::ToString::to_string(#name),
+                        e,
+                    ))?
+                }
             }
-            PrefixConfig::None => field_name.to_ascii_uppercase(),
+            ValueParser::Flag => quote! {
+                ::env_cfg::parse_flag(&value)
+                    .map_err(|e| ::env_cfg::EnvConfigError::Parse(
+                        ::env_cfg::__private::ToString::to_string(#name),
+                        e,
+                    ))?
+            },
+            ValueParser::Flags => quote! {
+                ::env_cfg::parse_flags(&value)
+                    .map_err(|e| ::env_cfg::EnvConfigError::Parse(
+                        ::env_cfg::__private::ToString::to_string(#name),
+                        e,
+                    ))?
+            },
+            ValueParser::With(parser) => quote! { #parser(value) },
+            ValueParser::TryFrom => quote! { ::env_cfg::parse_try_from(#name, value)? },
+            ValueParser::Secret => quote! { ::core::convert::From::from(value) },
         }
     }
 }
 
+/// Generated code for loading a single field
+struct FieldCode {
+    /// Expression evaluating to the loaded value, with `source` in scope
+    value: proc_macro2::TokenStream,
+    /// Statements adding the variables this field requires but are unset to `missing`
+    missing: Option<proc_macro2::TokenStream>,
+    /// Expression that is `true` if any variable the field reads is set
+    present: Option<proc_macro2::TokenStream>,
+    /// Names of the variables the field reads as its own value, if known at compile time
+    env_keys: Vec<String>,
+    /// Whether the field declares a `default` used when its variables are unset
+    has_default: bool,
+}
+
+/// Generated bodies of the `EnvConfig` methods, with `source` in scope
+struct LoaderBodies {
+    from_source: proc_macro2::TokenStream,
+    missing_vars: proc_macro2::TokenStream,
+    any_vars_set: proc_macro2::TokenStream,
+    /// Each field reading a variable known at compile time, with the name of that variable
+    field_keys: Vec<(syn::Ident, String)>,
+    /// Body of `redundant_vars`, if any field has a `default` and variables known at compile time
+    redundant_vars: Option<proc_macro2::TokenStream>,
+}
+
 /// Derive macro for EnvConfig trait
 ///
 /// By default, maps struct field names to STRUCT_NAME_FIELD_NAME in UPPER_SNAKE_CASE environment variables.
 ///
+/// Generates `EnvConfig::from_env`, `EnvConfig::from_source` and `EnvConfig::missing_vars`, plus a
+/// `TryFrom<&HashMap<String, String>>` implementation that loads from the map.
+///
+/// With the `cache` feature of `env_cfg`, also generates `cached_from_env`, which loads the
+/// configuration once and returns a `&'static` reference to it afterwards.
+///
+/// Generic structs are supported with the bounds declared on the struct, e.g.
+/// `struct Cache<B: EnvConfig<Error = EnvConfigError>>` for a `nested` field of type `B`. They don't
+/// get `cached_from_env` and can't be `global`.
+///
+/// Every env var name and prefix must match `[A-Za-z_][A-Za-z0-9_]*` (segments of a dotted key
+/// may be joined with `.`) and be at most 255 characters long, or the derive fails. It also fails
+/// if two fields read the same variable (variables of `nested` fields and fallbacks like
+/// `default_env` are not compared).
+///
 /// Supports struct-level attributes:
 /// - `#[env_cfg(no_prefix)]` - disable prefix, use field names directly
-/// - `#[env_cfg(prefix = "PREFIX")]` - use custom prefix instead of struct name
+/// - `#[env_cfg(prefix = "PREFIX")]` - use custom prefix instead of struct name (or `prefix = SOME_CONST`, a path to a `&str` const). Underscores where prefix and field name meet collapse into one
+/// - `#[env_cfg(sub_prefix = "SEGMENT")]` - add a segment between the struct name prefix and the field name (`DATABASE_CONFIG_DB_HOST`)
+/// - `#[env_cfg(expand)]` - expand `${VAR}` references in all values (`expand = "empty"` ignores unset variables)
+/// - `#[env_cfg(empty_as_none)]` - treat variables set to an empty string as unset
+/// - `#[env_cfg(rename_all = "ACRONYM_SNAKE_CASE")]` - keep runs of capitals together when splitting names into words (`OAuthConfig` -> `OAUTH_CONFIG`)
+/// - `#[env_cfg(rename_all_values = "lowercase")]` - convert every value (and string default) to `"lowercase"` or `"UPPERCASE"` before parsing
+/// - `#[env_cfg(propagate_prefix)]` - load `nested` fields with this struct's prefix prepended to their variables (except `independent` ones)
+/// - `#[env_cfg(dotted)]` - derive lowercase dotted keys (`app_config.port`) instead of `SCREAMING_SNAKE_CASE` names
+/// - `#[env_cfg(profile_var = "VAR_NAME")]` - read every variable from `<PROFILE>_<VAR>` before `<VAR>`, where the profile is the value of `VAR_NAME`
+/// - `#[env_cfg(global)]` - also generate `init`, loading a process-wide instance once, and `global`, returning it (panics before `init`)
+/// - `#[env_cfg(constructor)]` - also generate `new`, taking every field in declaration order
+/// - `#[env_cfg(strict)]` - ignore `default` and `default_from`, requiring every variable of those fields to be set
+/// - `#[env_cfg(group("field", "field", ...))]` - require the variables of these fields to be set all together or not at all (repeatable)
+/// - `#[env_cfg(keys)]` - also generate a `<Name>Key` enum with a variant per field read from a variable known at compile time, with `env_name(key)` and `get(&self, key)` (the value as an `Option<String>`)
+/// - `#[env_cfg(redundant_vars)]` - also generate `redundant_vars(source)` and `redundant_env_vars()`, returning the variables set to the default of their field, or the error loading the config (requires `PartialEq` on fields with a `default`)
+/// - `#[env_cfg(derive_from = "VAR_NAME", derive_with = "function_name")]` - split the value of `VAR_NAME` into the variables of several fields (signature: `fn(String) -> Result<impl IntoIterator<Item = (K, V)>, E>`)
+/// - `#[env_cfg(validate = "function_name")]` - check the loaded struct (signature: `fn(&Self) -> Result<(), String>`)
+/// - `#[env_cfg(json_env = "VAR_NAME")]` - load the whole struct from a JSON env var when it is set (requires the `serde` feature and `Deserialize`)
+///
+/// Enums are supported with `#[env_cfg(tag = "VAR_NAME")]`: the value of `VAR_NAME` selects the
+/// variant (its name in snake_case, or `#[env_cfg(rename = "value")]` on the variant), which is
+/// either a unit variant or wraps a single `EnvConfig` type loaded from the same source.
+///
+/// `OsString` and `PathBuf` fields (and `Option`s of them) are loaded with `EnvSource::get_os`, so
+/// they accept values that are not valid Unicode, unless an attribute processes the value as a
+/// string (such as `parse_with`).
+///
+/// With the `zeroize` feature, `SecretString` and `SecretBytes` fields (and `Option`s of them)
+/// take ownership of the loaded value instead of parsing a copy of it.
 ///
 /// Supports field-level attributes:
 /// - `#[env_cfg(skip)]` - skip this field (won't load from env) (must implement Default)
-/// - `#[env_cfg(env = "VAR_NAME")]` - specify custom env var name
-/// - `#[env_cfg(default = "value")]` - specify default value  
-/// - `#[env_cfg(parse_with = "function_name")]` - use custom parser function (signature: `fn(String) -> T`)
-/// - `#[env_cfg(nested)]` - treat field as nested EnvConfig struct (calls T::from_env())
+/// - `#[env_cfg(skip, error_if_set)]` - skip this field, but return an error if its variable is set
+/// - `#[env_cfg(env = "VAR_NAME")]` - specify custom env var name (`{prefix}` is replaced with the struct prefix, `{field}` with the field name), or `env = ["VAR_NAME", ...]` to try several names in order
+/// - `#[env_cfg(alias(env = "OLD_VAR", parse_with = "parse_fn"))]` - when unset, read `OLD_VAR`, parsed with `parse_fn` instead of the field's parser (repeatable, `parse_with` is optional)
+/// - `#[env_cfg(rename = "VAR_NAME")]` - alias for `env`
+/// - `#[env_cfg(prefix = "PREFIX")]` - use a different prefix for this field only (the name is still derived from the field)
+/// - `#[env_cfg(default = "value")]` - specify default value, checked at compile time on primitive fields  
+/// - `#[env_cfg(default = LogLevel::Info)]` - use a value of the field's type as the default instead of parsing a string (any expression, e.g. `DEFAULTS.port` for a field of a static)
+/// - `#[env_cfg(default_from = "other_field")]` - when unset, use a clone of an earlier field's resolved value
+/// - `#[env_cfg(default_env = "OTHER_VAR")]` - when unset, read the fallback variable `OTHER_VAR` before using any `default`
+/// - `#[env_cfg(parse_with = "function_name")]` - use custom parser function (signature: `fn(String) -> T`), which may be a path like `"parsers::parse_point"`, `"Point::parse"` or `"parse_csv::<u16>"`. With `default`, the default is parsed with it too (giving `Some` on an unset `Option<T>` field)
+/// - `#[env_cfg(sections = "PREFIX")]` - load every named section `PREFIX_<NAME>_...` of a config `T` into a `Vec<(String, T)>` field
+/// - `#[env_cfg(on_parse_error = "fail" | "default" | "none")]` - fail on a value that doesn't parse (default), or use the declared default or `None` instead
+/// - `#[env_cfg(parse_with_env = "function_name")]` - load the field with a custom function reading any variables it needs from the source (signature: `fn(&dyn EnvSource) -> Result<T, EnvConfigError>`)
+/// - `#[env_cfg(clamp(MIN, MAX))]` - clamp the parsed value (and the default) into `MIN..=MAX` instead of failing; `on_clamp = "function_name"` is called with the variable name, the original and the clamped value when that changes it (signature: `fn(&str, &T, &T)`)
+/// - `#[env_cfg(transform = "function_name")]` - transform the parsed value, including the default (signature: `fn(T) -> T`); repeat to chain several, applied in order before `clamp` and `validate_map`
+/// - `#[env_cfg(validate_map = "function_name")]` - check and replace the parsed value, including the default (signature: `fn(T) -> Result<T, String>`, where an `Err` becomes a `Parse` error)
+/// - `#[env_cfg(indexed_list)]` - load a `Vec<T>` from `NAME_0`, `NAME_1`, ... up to the first unset index
+/// - `#[env_cfg(nested)]` - treat field as nested EnvConfig struct (calls T::from_source() with the same source), optionally wrapped in `Arc<T>`, `Rc<T>` or `Box<T>`
+/// - `#[env_cfg(nested, defaults(VAR_NAME = "value", ...))]` - values the nested config reads for its variables that are not set
+/// - `#[env_cfg(nested, independent)]` - always load the nested struct with its own prefix configuration
+/// - `#[env_cfg(nested, prefix_from_field)]` - load the nested struct with the parent's prefix and the field name prepended (`APP_PRIMARY_DB_HOST`)
+/// - `#[env_cfg(nested, default_if_absent)]` - use `Default::default()` when none of the nested struct's variables are set
+/// - `#[env_cfg(nested, enabled_by = "VAR_NAME")]` - only load the nested struct when `VAR_NAME` is `true`, otherwise use `Default` (or `None` for `Option<T>`)
+/// - `#[env_cfg(expand)]` - expand `${VAR}` references in the value (and default) before parsing (`expand = "empty"` ignores unset variables)
+/// - `#[env_cfg(trim_matches = "\"")]` - strip a matching pair of the given characters from both ends of the value
+/// - `#[env_cfg(trim_prefix = "Bearer ")]` - strip the given prefix from the value, if present
+/// - `#[env_cfg(unquote)]` - strip a matching pair of `"` or `'` quotes from both ends of the value
+/// - `#[env_cfg(empty_as_none)]` / `#[env_cfg(allow_empty)]` - treat an empty value as unset, or keep it (overrides the struct attribute)
+/// - `#[env_cfg(required)]` - require the variable of an `Option` field to be set (combine with `empty_as_none` for a tri-state)
+/// - `#[env_cfg(preserve_case)]` - keep the case of the value (opts out of `rename_all_values`)
+/// - `#[env_cfg(human_int)]` - allow `_` and `,` digit group separators in integers (e.g. `1_000_000`)
+/// - `#[env_cfg(flag)]` - load a `bool` that is `true` when set to an empty value, parsing other values flexibly (`yes`, `off`, `1`, ...) and `false` (or the `default`) when unset
+/// - `#[env_cfg(flags)]` - parse comma-separated flag names (e.g. `logging,metrics`) into a type implementing `FromFlags`
+/// - `#[env_cfg(try_from)]` - convert the value with `TryFrom<String>` instead of parsing it with `FromStr`
+/// - `#[env_cfg(radix_auto)]` - parse an integer in the radix given by its `0x`/`0o`/`0b` prefix (decimal without one)
+/// - `#[env_cfg(radix = 16)]` - parse an integer in the given radix (2 to 36)
+/// - `#[env_cfg(duration_secs_f64)]` - parse a `Duration` from fractional seconds (e.g. `0.5`)
 ///
 #[proc_macro_derive(EnvConfig, attributes(env_cfg))]
 pub fn derive_env_cfg(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
 
-    // Parse struct-level attributes for prefix configuration
-    let prefix_config = match parse_struct_prefix_config(&input).map_err(|e| e.into_compile_error())
-    {
-        Ok(config) => config,
-        Err(e) => return e.into(),
-    };
+    // Parse struct-level attributes
+    let struct_attributes =
+        match parse_struct_attributes(&input).map_err(|e| e.into_compile_error()) {
+            Ok(attributes) => attributes,
+            Err(e) => return e.into(),
+        };
 
-    expand_env_cfg(input, &prefix_config)
+    expand_env_cfg(input, &struct_attributes)
         .unwrap_or_else(syn::Error::into_compile_error)
         .into()
 }
 
 fn expand_env_cfg(
     input: DeriveInput,
-    prefix_config: &PrefixConfig,
+    struct_attributes: &StructAttributes,
 ) -> syn::Result<proc_macro2::TokenStream> {
     let name = &input.ident;
-    let fields = match &input.data {
+    // Generic configs get the struct's own bounds; e.g. `Cache<B: EnvConfig>` for a `nested`
+    // field of type `B`
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let is_generic = !input.generics.params.is_empty();
+    let LoaderBodies {
+        from_source,
+        missing_vars,
+        any_vars_set,
+        field_keys,
+        redundant_vars,
+    } = match &input.data {
         Data::Struct(data) => match &data.fields {
-            Fields::Named(fields) => &fields.named,
+            Fields::Named(fields) => {
+                if let Some((_, span)) = &struct_attributes.tag {
+                    return Err(syn::Error::new(*span, "'tag' can only be used on enums"));
+                }
+                generate_struct_body(name, &fields.named, struct_attributes)?
+            }
             o => {
                 return Err(syn::Error::new(
                     o.span(),
@@ -82,157 +661,1863 @@ fn expand_env_cfg(
                 ));
             }
         },
-        _ => {
+        Data::Enum(data) => {
+            if let Some((_, span)) = &struct_attributes.validate {
+                return Err(syn::Error::new(
+                    *span,
+                    "'validate' can only be used on structs",
+                ));
+            }
+            if let Some(span) = struct_attributes.constructor {
+                return Err(syn::Error::new(
+                    span,
+                    "'constructor' can only be used on structs",
+                ));
+            }
+            if let Some(span) = struct_attributes.keys {
+                return Err(syn::Error::new(span, "'keys' can only be used on structs"));
+            }
+            if let Some(span) = struct_attributes.redundant_vars {
+                return Err(syn::Error::new(
+                    span,
+                    "'redundant_vars' can only be used on structs",
+                ));
+            }
+            if let Some(span) = struct_attributes.strict {
+                return Err(syn::Error::new(
+                    span,
+                    "'strict' can only be used on structs",
+                ));
+            }
+            if let Some((_, _, span)) = &struct_attributes.derive_from {
+                return Err(syn::Error::new(
+                    *span,
+                    "'derive_from' can only be used on structs",
+                ));
+            }
+            if let Some(span) = struct_attributes.propagate_prefix {
+                return Err(syn::Error::new(
+                    span,
+                    "'propagate_prefix' can only be used on structs",
+                ));
+            }
+            if let Some((_, span)) = &struct_attributes.profile_var {
+                return Err(syn::Error::new(
+                    *span,
+                    "'profile_var' can only be used on structs",
+                ));
+            }
+            if let Some((_, span)) = struct_attributes.groups.first() {
+                return Err(syn::Error::new(
+                    *span,
+                    "'group' can only be used on structs",
+                ));
+            }
+            let Some((tag, _)) = &struct_attributes.tag else {
+                return Err(syn::Error::new(
+                    input.ident.span(),
+                    r#"EnvConfig can only be derived for enums with #[env_cfg(tag = "VAR_NAME")]"#,
+                ));
+            };
+            generate_enum_body(data, tag)?
+        }
+        Data::Union(_) => {
             return Err(syn::Error::new(
                 input.span(),
-                "EnvConfig can only be derived for structs",
+                "EnvConfig can only be derived for structs and enums",
             ));
         }
     };
 
-    let field_assignments: Result<Vec<_>, _> = fields
-        .into_iter()
-        .map(|field| generate_field_assignment(field, &prefix_config))
-        .collect();
-    let field_assignments = field_assignments?;
-
-    let expanded = quote! {
-        impl ::env_cfg::EnvConfig for #name {
-            type Error = ::env_cfg::EnvConfigError;
-
+    // `from_env` and the `HashMap` conversion need the process environment and `std`, which
+    // the `env_cfg/std` feature enables for this crate as well
+    let std_items = cfg!(feature = "std").then(|| {
+        quote! {
             fn from_env() -> Result<Self, Self::Error> {
-                Ok(Self {
-                    #(#field_assignments,)*
-                })
+                Self::from_source(&::env_cfg::SystemEnv)
             }
         }
-    };
-    Ok(expanded)
-}
-
-fn parse_struct_prefix_config(input: &DeriveInput) -> syn::Result<PrefixConfig> {
-    let struct_name = input.ident.to_string();
+    });
+    let std_impls = cfg!(feature = "std").then(|| {
+        quote! {
+            impl #impl_generics ::std::convert::TryFrom<&::std::collections::HashMap<String, String>>
+                for #name #ty_generics #where_clause
+            {
+                type Error = ::env_cfg::EnvConfigError;
 
-    // Convert PascalCase struct name to snake_case for the prefix
-    let snake_case_struct_name = struct_name.to_snek_case();
+                fn try_from(
+                    map: &::std::collections::HashMap<String, String>,
+                ) -> Result<Self, Self::Error> {
+                    <Self as ::env_cfg::EnvConfig>::from_source(map)
+                }
+            }
+        }
+    });
 
-    // Default behavior: use struct name as prefix
-    let mut prefix_config = PrefixConfig::StructName(snake_case_struct_name);
-    let mut existing_struct_attribute = false;
+    // With `env_cfg/cache`, also generate a loader memoizing the first successful `from_env`.
+    // Statics can't be generic, so generic configs don't get one
+    let cached_impl = (cfg!(feature = "cache") && !is_generic).then(|| {
+        quote! {
+            impl #name {
+                /// Load the configuration from the environment on the first successful call and
+                /// return the shared instance on subsequent calls. Failures are not cached.
+                #[allow(dead_code)]
+                pub fn cached_from_env() -> Result<&'static Self, ::env_cfg::EnvConfigError> {
+                    static CACHE: ::std::sync::OnceLock<#name> = ::std::sync::OnceLock::new();
+                    if let Some(config) = CACHE.get() {
+                        return Ok(config);
+                    }
+                    let config = <Self as ::env_cfg::EnvConfig>::from_env()?;
+                    Ok(CACHE.get_or_init(|| config))
+                }
+            }
+        }
+    });
 
-    // Check for struct-level attributes
-    for attr in &input.attrs {
-        if attr.path().is_ident("env_cfg") {
-            if let Meta::List(meta_list) = &attr.meta {
-                let nested_metas = meta_list.parse_args_with(
-                    syn::punctuated::Punctuated::<syn::Meta, syn::Token![,]>::parse_terminated,
-                )?;
+    // With `constructor`, also generate `new` taking every field in declaration order
+    let constructor_impl = match &input.data {
+        Data::Struct(data) if struct_attributes.constructor.is_some() => {
+            let vis = &input.vis;
+            let field_names = data.fields.iter().map(|field| &field.ident);
+            let field_types = data.fields.iter().map(|field| &field.ty);
+            let field_inits = field_names.clone();
+            Some(quote! {
+                impl #impl_generics #name #ty_generics #where_clause {
+                    /// Create the configuration from a value for every field, without reading
+                    /// any variables.
+                    #[allow(dead_code, clippy::too_many_arguments)]
+                    #vis fn new(#(#field_names: #field_types),*) -> Self {
+                        Self { #(#field_inits),* }
+                    }
+                }
+            })
+        }
+        _ => None,
+    };
 
-                for nested in nested_metas {
-                    match nested {
-                        Meta::Path(path) if path.is_ident("no_prefix") => {
-                            if !existing_struct_attribute {
-                                prefix_config = PrefixConfig::None;
-                                existing_struct_attribute = true;
-                            } else {
-                                return Err(syn::Error::new(
-                                    path.span(),
-                                    "Cannot use no_prefix with other attributes",
-                                ));
-                            }
+    // With `keys`, also generate an enum naming the fields, for typed references to their variables
+    let keys_impl = match struct_attributes.keys {
+        Some(span) if field_keys.is_empty() => {
+            return Err(syn::Error::new(
+                span,
+                "'keys' requires at least one field with a variable name known at compile time",
+            ));
+        }
+        Some(_) => {
+            let vis = &input.vis;
+            let key_name = format_ident!("{}Key", name);
+            let key_doc = format!("The fields of [`{name}`] read from a variable");
+            let variants: Vec<_> = field_keys
+                .iter()
+                .map(|(field, _)| format_ident!("{}", field.to_string().to_upper_camel_case()))
+                .collect();
+            let variant_docs = field_keys
+                .iter()
+                .map(|(field, env_name)| format!("`{field}`, read from `{env_name}`"));
+            let env_names = field_keys.iter().map(|(_, env_name)| env_name);
+            // Values are shown with `Display`, `None` for an unset `Option`, and secrets redacted
+            let values = field_keys.iter().map(|(field, _)| {
+                let field_type = match &input.data {
+                    Data::Struct(data) => data
+                        .fields
+                        .iter()
+                        .find(|f| f.ident.as_ref() == Some(field))
+                        .map(|f| &f.ty),
+                    _ => None,
+                }
+                .expect("keys are only generated for fields of the struct");
+                let inner_type = option_inner_type(field_type);
+                let is_secret =
+                    cfg!(feature = "zeroize") && is_secret_type(inner_type.unwrap_or(field_type));
+                match (inner_type.is_some(), is_secret) {
+                    (true, true) => quote! {
+                        self.#field.as_ref().map(|_| ::env_cfg::__private::ToString::to_string("[REDACTED]"))
+                    },
+                    (false, true) => quote! {
+                        {
+                            let _ = &self.#field;
+                            Some(::env_cfg::__private::ToString::to_string("[REDACTED]"))
                         }
-                        Meta::NameValue(name_value) if name_value.path.is_ident("prefix") => {
-                            if let syn::Expr::Lit(syn::ExprLit {
-                                lit: Lit::Str(lit_str),
-                                ..
-                            }) = &name_value.value
-                            {
-                                if !existing_struct_attribute {
-                                    prefix_config = PrefixConfig::Custom(lit_str.value());
-                                    existing_struct_attribute = true;
-                                } else {
-                                    return Err(syn::Error::new(
-                                        name_value.span(),
-                                        "Cannot use prefix with other attributes",
-                                    ));
-                                }
-                            }
+                    },
+                    (true, false) => quote! {
+                        self.#field.as_ref().map(::env_cfg::__private::ToString::to_string)
+                    },
+                    (false, false) => quote! {
+                        Some(::env_cfg::__private::ToString::to_string(&self.#field))
+                    },
+                }
+            });
+            Some(quote! {
+                #[doc = #key_doc]
+                #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+                #[allow(dead_code)]
+                #vis enum #key_name {
+                    #(
+                        #[doc = #variant_docs]
+                        #variants,
+                    )*
+                }
+
+                impl #impl_generics #name #ty_generics #where_clause {
+                    /// The name of the variable the field `key` is read from.
+                    #[allow(dead_code)]
+                    #vis fn env_name(key: #key_name) -> &'static str {
+                        match key {
+                            #(#key_name::#variants => #env_names,)*
                         }
-                        o => {
-                            return Err(syn::Error::new(
-                                o.span(),
-                                format!(
-                                    "Unsupported struct attribute. Supported attributes include: {SUPPORTED_STRUCT_ATTRIBUTES:?}"
-                                ),
-                            ));
+                    }
+
+                    /// The loaded value of the field `key` as a string, or `None` if it is an
+                    /// unset `Option`. Secrets are `[REDACTED]`.
+                    #[allow(dead_code)]
+                    #vis fn get(
+                        &self,
+                        key: #key_name,
+                    ) -> ::core::option::Option<::env_cfg::__private::String> {
+                        match key {
+                            #(#key_name::#variants => #values,)*
                         }
                     }
                 }
-            }
+            })
+        }
+        None => None,
+    };
+
+    // With `redundant_vars`, also generate functions listing the variables that are set to the
+    // default of their field. Each such field is loaded again without its variables, and the
+    // value compared to the loaded one
+    let redundant_vars_impl = match (struct_attributes.redundant_vars, redundant_vars) {
+        (Some(span), None) => {
+            return Err(syn::Error::new(
+                span,
+                "'redundant_vars' requires at least one field with a default and a variable name known at compile time",
+            ));
+        }
+        (Some(_), Some(body)) => {
+            let vis = &input.vis;
+            let env_items = cfg!(feature = "std").then(|| {
+                quote! {
+                    /// Names of the environment variables that are set to the default of their
+                    /// field, see `redundant_vars`.
+                    #[allow(dead_code)]
+                    #vis fn redundant_env_vars() -> Result<
+                        ::env_cfg::__private::Vec<::env_cfg::__private::String>,
+                        ::env_cfg::EnvConfigError,
+                    > {
+                        Self::redundant_vars(&::env_cfg::SystemEnv)
+                    }
+                }
+            });
+            Some(quote! {
+                impl #impl_generics #name #ty_generics #where_clause {
+                    /// Names of the variables set in `source` to a value that loads equal to the
+                    /// declared default of their field, so they can be removed without changing
+                    /// the config. Fails if the config fails to load.
+                    #[allow(dead_code)]
+                    #vis fn redundant_vars(
+                        source: &dyn ::env_cfg::EnvSource,
+                    ) -> Result<
+                        ::env_cfg::__private::Vec<::env_cfg::__private::String>,
+                        ::env_cfg::EnvConfigError,
+                    > {
+                        #body
+                    }
+
+                    #env_items
+                }
+            })
         }
+        (None, _) => None,
+    };
+
+    // With `global`, also generate `init` and `global` for a process-wide instance
+    if let (Some(span), true) = (struct_attributes.global, is_generic) {
+        return Err(syn::Error::new(
+            span,
+            "'global' cannot be used on generic structs",
+        ));
     }
+    let global_impl = struct_attributes.global.map(|_| {
+        let vis = &input.vis;
+        let struct_name = name.to_string();
+        let uninitialized = format!("{struct_name}::global() called before {struct_name}::init()");
+        quote! {
+            impl #name {
+                /// Load the configuration from the environment into the process-wide instance
+                /// returned by `global`. Fails if loading fails or if it was already initialized,
+                /// so the instance never changes once set.
+                #[allow(dead_code)]
+                #vis fn init() -> Result<(), ::env_cfg::EnvConfigError> {
+                    let config = <Self as ::env_cfg::EnvConfig>::from_env()?;
+                    Self::__env_cfg_global().set(config).map_err(|_| {
+                        ::env_cfg::EnvConfigError::Invalid(
+                            ::env_cfg::__private::ToString::to_string(#struct_name),
+                            ::env_cfg::__private::ToString::to_string("already initialized"),
+                        )
+                    })
+                }
 
-    Ok(prefix_config)
-}
+                /// The process-wide instance loaded by `init`.
+                ///
+                /// # Panics
+                ///
+                /// If `init` has not successfully returned yet.
+                #[allow(dead_code)]
+                #vis fn global() -> &'static Self {
+                    Self::__env_cfg_global().get().expect(#uninitialized)
+                }
 
-fn is_option_type(ty: &syn::Type) -> bool {
-    if let syn::Type::Path(type_path) = ty {
-        if type_path.qself.is_none() {
-            if let Some(segment) = type_path.path.segments.last() {
-                return segment.ident == "Option";
+                fn __env_cfg_global() -> &'static ::std::sync::OnceLock<Self> {
+                    static GLOBAL: ::std::sync::OnceLock<#name> = ::std::sync::OnceLock::new();
+                    &GLOBAL
+                }
             }
         }
-    }
-    false
-}
+    });
 
-fn generate_field_assignment(
-    field: &Field,
-    prefix_config: &PrefixConfig,
-) -> syn::Result<proc_macro2::TokenStream> {
-    let field_name = field.ident.as_ref().unwrap();
-    let field_name_str = field_name.to_string();
-    let field_type = &field.ty;
+    let expanded = quote! {
+        impl #impl_generics ::env_cfg::EnvConfig for #name #ty_generics #where_clause {
+            type Error = ::env_cfg::EnvConfigError;
 
-    // Parse attributes
-    let mut env_name = prefix_config.apply_to_field(&field_name_str);
-    let mut default_expr: Option<syn::Expr> = None;
-    let mut skip = false;
-    let mut parse_with: Option<syn::Expr> = None;
-    let mut is_nested = false;
+            #std_items
 
-    for attr in &field.attrs {
-        if attr.path().is_ident("env_cfg") {
-            if let Meta::List(meta_list) = &attr.meta {
-                let nested_result = meta_list.parse_args_with(
-                    syn::punctuated::Punctuated::<syn::Meta, syn::Token![,]>::parse_terminated,
-                );
+            fn from_source(source: &dyn ::env_cfg::EnvSource) -> Result<Self, Self::Error> {
+                #from_source
+            }
 
-                if let Ok(nested_metas) = nested_result {
+            fn missing_vars(
+                source: &dyn ::env_cfg::EnvSource,
+            ) -> ::env_cfg::__private::Vec<::env_cfg::__private::String> {
+                #missing_vars
+            }
+
+            fn any_vars_set(source: &dyn ::env_cfg::EnvSource) -> bool {
+                #any_vars_set
+            }
+        }
+
+        #std_impls
+
+        #cached_impl
+
+        #constructor_impl
+
+        #keys_impl
+
+        #redundant_vars_impl
+
+        #global_impl
+    };
+    Ok(expanded)
+}
+
+/// Generate the body of `from_source` for a struct with named fields
+fn generate_struct_body(
+    name: &syn::Ident,
+    fields: &Punctuated<Field, Token![,]>,
+    struct_attributes: &StructAttributes,
+) -> syn::Result<LoaderBodies> {
+    // Fields are resolved in declaration order into local bindings, so later fields can
+    // refer to the values of earlier ones (e.g. `default_from`)
+    let mut field_names = Vec::new();
+    let mut field_bindings = Vec::new();
+    let mut field_values = Vec::new();
+    let mut missing_checks = Vec::new();
+    let mut present_checks = Vec::new();
+    // The field reading each variable, so two fields reading the same one can be rejected
+    let mut env_key_fields = std::collections::BTreeMap::new();
+    let mut field_keys = Vec::new();
+    // For `redundant_vars`, each field with a default is loaded again without its variables
+    let mut redundant_checks = Vec::new();
+    // Whether each field's variables are set, and the name to report it by, for `group`
+    let mut field_presence = std::collections::BTreeMap::new();
+    for field in fields {
+        let field_name = field.ident.as_ref().unwrap();
+        let FieldCode {
+            value,
+            missing,
+            present,
+            env_keys,
+            has_default,
+        } = generate_field_value(field, struct_attributes, &field_names)?;
+        if let Some(env_key) = env_keys.first() {
+            field_keys.push((field_name.clone(), env_key.clone()));
+        }
+        if has_default && !env_keys.is_empty() {
+            let field_type = &field.ty;
+            redundant_checks.push(quote! {
+                let names: &[&str] = &[#(#env_keys),*];
+                let set = names.iter().find(|name| !matches!(source.get(name), Ok(None)));
+                if let Some(name) = set {
+                    let unset = ::env_cfg::__private::Unset { source, names };
+                    let defaulted: #field_type = {
+                        let source: &dyn ::env_cfg::EnvSource = &unset;
+                        #value
+                    };
+                    if config.#field_name == defaulted {
+                        redundant.push(::env_cfg::__private::ToString::to_string(*name));
+                    }
+                }
+            });
+        }
+        if let Some(present) = &present {
+            let label = env_keys
+                .first()
+                .cloned()
+                .unwrap_or_else(|| field_name.to_string());
+            field_presence.insert(field_name.to_string(), (present.clone(), label));
+        }
+        for env_key in env_keys {
+            match env_key_fields.insert(env_key.clone(), field_name) {
+                Some(other) if other != field_name => {
+                    return Err(syn::Error::new(
+                        field_name.span(),
+                        format!(
+                            "Fields '{other}' and '{field_name}' both read the environment variable `{env_key}`"
+                        ),
+                    ));
+                }
+                _ => {}
+            }
+        }
+        field_values.push(value);
+        missing_checks.extend(missing);
+        present_checks.extend(present);
+        field_bindings.push(field_binding(field_name));
+        field_names.push(field_name.clone());
+    }
+
+    // Groups are checked before loading any field, so a partial group isn't reported as the
+    // first of its variables that is missing
+    let mut check_groups = Vec::new();
+    for (members, _) in &struct_attributes.groups {
+        let mut presence = Vec::new();
+        for member in members {
+            let Some((present, label)) = field_presence.get(&member.value()) else {
+                return Err(syn::Error::new(
+                    member.span(),
+                    format!(
+                        "group member '{}' must be a field of this struct that reads a variable",
+                        member.value()
+                    ),
+                ));
+            };
+            presence.push(quote! { (#label, #present) });
+        }
+        let struct_name = name.to_string();
+        check_groups.push(quote! {
+            ::env_cfg::__private::check_group(#struct_name, &[#(#presence),*])?;
+        });
+    }
+
+    // Cross-field invariants are checked once the struct is constructed
+    let validate = struct_attributes.validate.as_ref().map(|(validate, _)| {
+        let struct_name = name.to_string();
+        quote! {
+            #validate(&config)
+                .map_err(|e| ::env_cfg::EnvConfigError::Invalid(
+                    ::env_cfg::__private::ToString::to_string(#struct_name),
+                    e,
+                ))?;
+        }
+    });
+
+    // If the struct can be delivered as a single JSON blob, prefer it over per-field loading
+    let load_json = struct_attributes.json_env.as_ref().map(|json_env| {
+        quote! {
+            if let Some(value) = source.get(#json_env)? {
+                let config = ::env_cfg::parse_json(#json_env, &value)?;
+                #validate
+                return Ok(config);
+            }
+        }
+    });
+
+    // A set JSON blob provides every field
+    let json_present = struct_attributes.json_env.as_ref().map(|json_env| {
+        quote! {
+            if let Ok(Some(_)) = source.get(#json_env) {
+                return missing;
+            }
+        }
+    });
+
+    // Variables split from a single variable are read after the ones that are set, so each field
+    // can still be set on its own
+    let (load_derived, missing_derived) = match &struct_attributes.derive_from {
+        Some((var, split_fn, _)) => {
+            let layer_source = quote! {
+                let layered;
+                let source: &dyn ::env_cfg::EnvSource = match &derived {
+                    Some(derived) => {
+                        layered = ::env_cfg::__private::Layered { source, fallback: derived };
+                        &layered
+                    }
+                    None => source,
+                };
+            };
+            (
+                quote! {
+                    let derived = match source.get(#var)? {
+                        Some(value) => Some(::env_cfg::__private::derived_vars(#var, #split_fn(value))?),
+                        None => None,
+                    };
+                    #layer_source
+                },
+                quote! {
+                    // A value that fails to split provides no variables
+                    let derived = match source.get(#var) {
+                        Ok(Some(value)) => ::env_cfg::__private::derived_vars(#var, #split_fn(value)).ok(),
+                        _ => None,
+                    };
+                    #layer_source
+                },
+            )
+        }
+        None => (quote! {}, quote! {}),
+    };
+
+    // The JSON blob and the variable split into fields count as variables of the struct
+    present_checks.extend(
+        struct_attributes
+            .json_env
+            .iter()
+            .chain(
+                struct_attributes
+                    .derive_from
+                    .as_ref()
+                    .map(|(var, _, _)| var),
+            )
+            .map(|var| quote! { !matches!(source.get(#var), Ok(None)) }),
+    );
+
+    // With a profile, every variable (including those of nested configs) is read from a source
+    // preferring the variable of the active profile
+    let select_profile = |profile: proc_macro2::TokenStream| {
+        struct_attributes.profile_var.as_ref().map(|_| {
+            let dotted = struct_attributes.name_case.is_dotted();
+            quote! {
+                let profiled = ::env_cfg::__private::Profiled { source, profile: #profile, dotted: #dotted };
+                let source: &dyn ::env_cfg::EnvSource = &profiled;
+            }
+        })
+    };
+    let profile_var = struct_attributes.profile_var.as_ref().map(|(var, _)| var);
+    let load_profile = select_profile(quote! { source.get(#profile_var)? });
+    let read_profile = select_profile(quote! { source.get(#profile_var).ok().flatten() });
+
+    Ok(LoaderBodies {
+        from_source: quote! {
+            #load_profile
+            #load_json
+            #load_derived
+            #(#check_groups)*
+            #(let #field_bindings = #field_values;)*
+            let config = Self {
+                #(#field_names: #field_bindings,)*
+            };
+            #validate
+            Ok(config)
+        },
+        missing_vars: quote! {
+            #[allow(unused_mut)]
+            let mut missing = ::env_cfg::__private::Vec::new();
+            #read_profile
+            #json_present
+            #missing_derived
+            #(#missing_checks)*
+            missing
+        },
+        any_vars_set: quote! {
+            #read_profile
+            false #(|| #present_checks)*
+        },
+        field_keys,
+        redundant_vars: (!redundant_checks.is_empty()).then(|| {
+            // A set JSON blob provides every field, so no variable can be redundant
+            let json_set = struct_attributes.json_env.as_ref().map(|json_env| {
+                quote! {
+                    if source.get(#json_env)?.is_some() {
+                        return Ok(redundant);
+                    }
+                }
+            });
+            quote! {
+                let config = <Self as ::env_cfg::EnvConfig>::from_source(source)?;
+                let mut redundant = ::env_cfg::__private::Vec::new();
+                #json_set
+                #load_profile
+                #load_derived
+                #(#redundant_checks)*
+                Ok(redundant)
+            }
+        }),
+    })
+}
+
+/// Generate the body of `from_source` for an enum whose variant is selected by the value of the
+/// variable `tag`. Each variant is either a unit variant or wraps a single `EnvConfig` type.
+fn generate_enum_body(data: &DataEnum, tag: &str) -> syn::Result<LoaderBodies> {
+    let mut tag_values = Vec::new();
+    let mut arms = Vec::new();
+    let mut missing_arms = Vec::new();
+    for variant in &data.variants {
+        let variant_name = &variant.ident;
+        let mut tag_value = variant_name.to_string().to_snek_case();
+        for attr in &variant.attrs {
+            if attr.path().is_ident("env_cfg") {
+                let nested_metas =
+                    attr.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)?;
+                for nested in nested_metas {
+                    match nested {
+                        Meta::NameValue(name_value) if name_value.path.is_ident("rename") => {
+                            if let syn::Expr::Lit(syn::ExprLit {
+                                lit: Lit::Str(lit_str),
+                                ..
+                            }) = &name_value.value
+                            {
+                                tag_value = lit_str.value();
+                            } else {
+                                return Err(syn::Error::new(
+                                    name_value.value.span(),
+                                    "rename must be a string literal containing the tag value",
+                                ));
+                            }
+                        }
+                        o => {
+                            return Err(syn::Error::new(
+                                o.span(),
+                                format!(
+                                    "Unsupported variant attribute. Supported attributes: {SUPPORTED_VARIANT_ATTRIBUTES:?}"
+                                ),
+                            ));
+                        }
+                    }
+                }
+            }
+        }
+
+        let load_variant = match &variant.fields {
+            Fields::Unit => quote! { Self::#variant_name },
+            Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+                let variant_type = &fields.unnamed[0].ty;
+                let variant_type_label = type_label(variant_type);
+                missing_arms.push(quote! {
+                    #tag_value => <#variant_type as ::env_cfg::EnvConfig>::missing_vars(source),
+                });
+                quote! {
+                    Self::#variant_name(
+                        <#variant_type as ::env_cfg::EnvConfig>::from_source(source)
+                            .map_err(|e| ::env_cfg::EnvConfigError::context(
+                                ::core::convert::Into::into(e),
+                                ::env_cfg::__private::nested_context::<#variant_type>(#variant_type_label),
+                            ))?
+                    )
+                }
+            }
+            o => {
+                return Err(syn::Error::new(
+                    o.span(),
+                    "Enum variants must be unit variants or wrap a single EnvConfig type",
+                ));
+            }
+        };
+        arms.push(quote! { #tag_value => #load_variant, });
+        tag_values.push(tag_value);
+    }
+
+    let expected = tag_values.join(", ");
+    Ok(LoaderBodies {
+        from_source: quote! {
+            let tag = ::env_cfg::require(#tag, source.get(#tag)?)?;
+            Ok(match tag.as_str() {
+                #(#arms)*
+                other => {
+                    return Err(::env_cfg::EnvConfigError::Parse(
+                        ::env_cfg::__private::ToString::to_string(#tag),
+                        ::env_cfg::__private::format!(
+                            "Unknown variant `{}`, expected one of: {}",
+                            other,
+                            #expected
+                        ),
+                    ));
+                }
+            })
+        },
+        // Only the variables of the selected variant are required. An invalid tag is a parse
+        // error rather than a missing variable
+        missing_vars: quote! {
+            match source.get(#tag) {
+                Ok(None) => ::env_cfg::__private::vec![
+                    ::env_cfg::__private::ToString::to_string(#tag),
+                ],
+                Ok(Some(tag)) => match tag.as_str() {
+                    #(#missing_arms)*
+                    _ => ::env_cfg::__private::Vec::new(),
+                },
+                Err(_) => ::env_cfg::__private::Vec::new(),
+            }
+        },
+        // Variables of the variants are only read once the tag selects one
+        any_vars_set: quote! { !matches!(source.get(#tag), Ok(None)) },
+        field_keys: Vec::new(),
+        redundant_vars: None,
+    })
+}
+
+fn parse_struct_attributes(input: &DeriveInput) -> syn::Result<StructAttributes> {
+    let struct_name = input.ident.to_string();
+
+    // Default behavior: use struct name as prefix, converted once `rename_all` is known
+    let mut prefix_config = PrefixConfig::StructName(struct_name.clone());
+    let mut name_case = NameCase::default();
+    let mut rename_all = None;
+    let mut dotted = None;
+    let mut sub_prefix: Option<(String, Span)> = None;
+    let mut profile_var = None;
+    let mut existing_struct_attribute = false;
+    let mut expand = None;
+    let mut json_env = None;
+    let mut empty_as_none = false;
+    let mut tag = None;
+    let mut validate = None;
+    let mut value_case = None;
+    let mut constructor = None;
+    let mut keys = None;
+    let mut redundant_vars = None;
+    let mut strict = None;
+    let mut derive_from = None;
+    let mut derive_with = None;
+    let mut propagate_prefix = None;
+    let mut global = None;
+    let mut groups = Vec::new();
+
+    // Check for struct-level attributes
+    for attr in &input.attrs {
+        if attr.path().is_ident("env_cfg") {
+            if let Meta::List(meta_list) = &attr.meta {
+                let nested_metas = meta_list.parse_args_with(
+                    syn::punctuated::Punctuated::<syn::Meta, syn::Token![,]>::parse_terminated,
+                )?;
+
+                for nested in nested_metas {
+                    match nested {
+                        Meta::Path(path) if path.is_ident("no_prefix") => {
+                            if !existing_struct_attribute {
+                                prefix_config = PrefixConfig::None;
+                                existing_struct_attribute = true;
+                            } else {
+                                return Err(syn::Error::new(
+                                    path.span(),
+                                    "Cannot use no_prefix with other attributes",
+                                ));
+                            }
+                        }
+                        Meta::NameValue(name_value) if name_value.path.is_ident("prefix") => {
+                            if !existing_struct_attribute {
+                                prefix_config = PrefixConfig::from_expr(&name_value.value)?;
+                                existing_struct_attribute = true;
+                            } else {
+                                return Err(syn::Error::new(
+                                    name_value.span(),
+                                    "Cannot use prefix with other attributes",
+                                ));
+                            }
+                        }
+                        Meta::NameValue(name_value) if name_value.path.is_ident("sub_prefix") => {
+                            let syn::Expr::Lit(syn::ExprLit {
+                                lit: Lit::Str(lit_str),
+                                ..
+                            }) = &name_value.value
+                            else {
+                                return Err(syn::Error::new(
+                                    name_value.value.span(),
+                                    "sub_prefix must be a string literal containing the segment",
+                                ));
+                            };
+                            validate_env_name(&lit_str.value(), lit_str.span())?;
+                            sub_prefix = Some((lit_str.value(), name_value.span()));
+                        }
+                        ref meta if meta.path().is_ident("expand") => {
+                            expand = Some(ExpandMode::from_meta(meta)?);
+                        }
+                        Meta::List(list) if list.path.is_ident("group") => {
+                            let members = list
+                                .parse_args_with(
+                                    Punctuated::<syn::LitStr, Token![,]>::parse_terminated,
+                                )
+                                .map_err(|_| {
+                                    syn::Error::new(
+                                        list.span(),
+                                        r#"group takes field names as string literals, e.g. `group("db_host", "db_port")`"#,
+                                    )
+                                })?;
+                            if members.len() < 2 {
+                                return Err(syn::Error::new(
+                                    list.span(),
+                                    "A group needs at least two fields",
+                                ));
+                            }
+                            groups.push((members.into_iter().collect(), list.span()));
+                        }
+                        Meta::Path(path) if path.is_ident("empty_as_none") => {
+                            empty_as_none = true;
+                        }
+                        Meta::Path(path) if path.is_ident("constructor") => {
+                            constructor = Some(path.span());
+                        }
+                        Meta::Path(path) if path.is_ident("keys") => {
+                            keys = Some(path.span());
+                        }
+                        Meta::Path(path) if path.is_ident("redundant_vars") => {
+                            redundant_vars = Some(path.span());
+                        }
+                        Meta::Path(path) if path.is_ident("strict") => {
+                            strict = Some(path.span());
+                        }
+                        Meta::Path(path) if path.is_ident("propagate_prefix") => {
+                            propagate_prefix = Some(path.span());
+                        }
+                        Meta::Path(path) if path.is_ident("global") => {
+                            if !cfg!(feature = "std") {
+                                return Err(syn::Error::new(
+                                    path.span(),
+                                    "'global' requires the `std` feature of env_cfg",
+                                ));
+                            }
+                            global = Some(path.span());
+                        }
+                        Meta::Path(path) if path.is_ident("dotted") => {
+                            dotted = Some(path.span());
+                        }
+                        Meta::NameValue(name_value) if name_value.path.is_ident("derive_from") => {
+                            if let syn::Expr::Lit(syn::ExprLit {
+                                lit: Lit::Str(lit_str),
+                                ..
+                            }) = &name_value.value
+                            {
+                                validate_env_name(&lit_str.value(), lit_str.span())?;
+                                derive_from = Some((lit_str.value(), name_value.span()));
+                            } else {
+                                return Err(syn::Error::new(
+                                    name_value.value.span(),
+                                    "derive_from must be a string literal containing the variable name",
+                                ));
+                            }
+                        }
+                        Meta::NameValue(name_value) if name_value.path.is_ident("derive_with") => {
+                            derive_with = Some((
+                                parse_function_path(&name_value.value, "derive_with")?,
+                                name_value.span(),
+                            ));
+                        }
+                        Meta::NameValue(name_value) if name_value.path.is_ident("json_env") => {
+                            if let syn::Expr::Lit(syn::ExprLit {
+                                lit: Lit::Str(lit_str),
+                                ..
+                            }) = &name_value.value
+                            {
+                                validate_env_name(&lit_str.value(), lit_str.span())?;
+                                json_env = Some(lit_str.value());
+                            } else {
+                                return Err(syn::Error::new(
+                                    name_value.value.span(),
+                                    "json_env must be a string literal containing the variable name",
+                                ));
+                            }
+                        }
+                        Meta::NameValue(name_value) if name_value.path.is_ident("profile_var") => {
+                            let syn::Expr::Lit(syn::ExprLit {
+                                lit: Lit::Str(lit_str),
+                                ..
+                            }) = &name_value.value
+                            else {
+                                return Err(syn::Error::new(
+                                    name_value.value.span(),
+                                    "profile_var must be a string literal containing the variable name",
+                                ));
+                            };
+                            validate_env_name(&lit_str.value(), lit_str.span())?;
+                            profile_var = Some((lit_str.value(), name_value.span()));
+                        }
+                        Meta::NameValue(name_value) if name_value.path.is_ident("rename_all") => {
+                            rename_all = Some(name_value.span());
+                            name_case = match &name_value.value {
+                                syn::Expr::Lit(syn::ExprLit {
+                                    lit: Lit::Str(lit_str),
+                                    ..
+                                }) => NameCase::from_value(&lit_str.value()),
+                                _ => None,
+                            }
+                            .ok_or_else(|| {
+                                syn::Error::new(
+                                    name_value.value.span(),
+                                    r#"rename_all must be one of "SCREAMING_SNAKE_CASE" or "ACRONYM_SNAKE_CASE""#,
+                                )
+                            })?;
+                        }
+                        Meta::NameValue(name_value)
+                            if name_value.path.is_ident("rename_all_values") =>
+                        {
+                            let case = match &name_value.value {
+                                syn::Expr::Lit(syn::ExprLit {
+                                    lit: Lit::Str(lit_str),
+                                    ..
+                                }) => ValueCase::from_value(&lit_str.value()),
+                                _ => None,
+                            };
+                            value_case = Some(case.ok_or_else(|| {
+                                syn::Error::new(
+                                    name_value.value.span(),
+                                    r#"rename_all_values must be one of "lowercase" or "UPPERCASE""#,
+                                )
+                            })?);
+                        }
+                        Meta::NameValue(name_value) if name_value.path.is_ident("validate") => {
+                            validate = Some((
+                                parse_function_path(&name_value.value, "validate")?,
+                                name_value.span(),
+                            ));
+                        }
+                        Meta::NameValue(name_value) if name_value.path.is_ident("tag") => {
+                            if let syn::Expr::Lit(syn::ExprLit {
+                                lit: Lit::Str(lit_str),
+                                ..
+                            }) = &name_value.value
+                            {
+                                validate_env_name(&lit_str.value(), lit_str.span())?;
+                                tag = Some((lit_str.value(), name_value.span()));
+                            } else {
+                                return Err(syn::Error::new(
+                                    name_value.value.span(),
+                                    "tag must be a string literal containing the variable name",
+                                ));
+                            }
+                        }
+                        o => {
+                            return Err(syn::Error::new(
+                                o.span(),
+                                format!(
+                                    "Unsupported struct attribute. Supported attributes include: {SUPPORTED_STRUCT_ATTRIBUTES:?}"
+                                ),
+                            ));
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    // The variable and the function splitting it only make sense together
+    let derive_from = match (derive_from, derive_with) {
+        (Some((var, span)), Some((split_fn, _))) => Some((var, split_fn, span)),
+        (Some((_, span)), None) => {
+            return Err(syn::Error::new(
+                span,
+                "'derive_from' requires 'derive_with'",
+            ));
+        }
+        (None, Some((_, span))) => {
+            return Err(syn::Error::new(
+                span,
+                "'derive_with' requires 'derive_from'",
+            ));
+        }
+        (None, None) => None,
+    };
+
+    if let (Some(span), PrefixConfig::None) = (propagate_prefix, &prefix_config) {
+        return Err(syn::Error::new(
+            span,
+            "'propagate_prefix' has no prefix to propagate with 'no_prefix'",
+        ));
+    }
+
+    if let Some(span) = dotted {
+        if rename_all.is_some() {
+            return Err(syn::Error::new(
+                span,
+                "Cannot use both 'dotted' and 'rename_all' attributes",
+            ));
+        }
+        name_case = NameCase::Dotted;
+    }
+
+    // Convert PascalCase struct name to snake_case for the prefix, and add the sub-namespace
+    if let PrefixConfig::StructName(_) = prefix_config {
+        let mut prefix = name_case.struct_name(&struct_name);
+        if let Some((segment, _)) = &sub_prefix {
+            let separator = if name_case.is_dotted() { "." } else { "_" };
+            prefix = format!("{prefix}{separator}{}", segment.trim_matches(['_', '.']));
+        }
+        prefix_config = PrefixConfig::StructName(prefix);
+    } else if let Some((_, span)) = sub_prefix {
+        return Err(syn::Error::new(
+            span,
+            "'sub_prefix' extends the struct name prefix, and cannot be used with 'prefix' or 'no_prefix'",
+        ));
+    }
+
+    Ok(StructAttributes {
+        prefix_config,
+        expand,
+        json_env,
+        empty_as_none,
+        tag,
+        name_case,
+        validate,
+        value_case,
+        constructor,
+        keys,
+        redundant_vars,
+        strict,
+        derive_from,
+        propagate_prefix,
+        global,
+        profile_var,
+        groups,
+    })
+}
+
+/// Parse the string literal value of a function attribute (e.g. `parse_with`) as a path.
+/// The path's tokens carry the span of the literal, so errors about the function, like a typo
+/// in its name, underline the string in the attribute rather than the derive
+fn parse_function_path(expr: &syn::Expr, attribute: &str) -> syn::Result<syn::ExprPath> {
+    if let syn::Expr::Lit(syn::ExprLit {
+        lit: Lit::Str(lit_str),
+        ..
+    }) = expr
+    {
+        lit_str.parse::<syn::ExprPath>().map_err(|_| {
+            syn::Error::new(
+                lit_str.span(),
+                format!(
+                    "{attribute} must be a path to a function, e.g. \"parse_point\", \"parsers::parse_point\" or \"parse_csv::<u16>\" (with `::` before type arguments)"
+                ),
+            )
+        })
+    } else {
+        Err(syn::Error::new(
+            expr.span(),
+            format!("{attribute} must be a string literal containing the function name"),
+        ))
+    }
+}
+
+fn is_option_type(ty: &syn::Type) -> bool {
+    if let syn::Type::Path(type_path) = ty {
+        if type_path.qself.is_none() {
+            if let Some(segment) = type_path.path.segments.last() {
+                return segment.ident == "Option";
+            }
+        }
+    }
+    false
+}
+
+/// The type as written in the source, e.g. `config::DbCfg`, for error messages
+fn type_label(ty: &syn::Type) -> String {
+    // Token streams print with spaces between all tokens (`config :: DbCfg < T >`)
+    quote! { #ty }
+        .to_string()
+        .replace(" :: ", "::")
+        .replace(":: ", "::")
+        .replace(" <", "<")
+        .replace("< ", "<")
+        .replace(" >", ">")
+        .replace(" ,", ",")
+}
+
+/// Parse the value of `env`, either a string literal or an array of them tried in order.
+/// `{field}` is replaced by the field's own name before the names are checked
+fn env_name_list(expr: &syn::Expr, attribute: &str, field_name: &str) -> syn::Result<Vec<String>> {
+    let error = || {
+        syn::Error::new(
+            expr.span(),
+            format!("{attribute} must be a string literal or a non-empty array of string literals"),
+        )
+    };
+    let as_string = |expr: &syn::Expr| match expr {
+        syn::Expr::Lit(syn::ExprLit {
+            lit: Lit::Str(lit_str),
+            ..
+        }) => {
+            let name = lit_str.value().replace("{field}", field_name);
+            validate_env_name(&name, lit_str.span())?;
+            Ok(name)
+        }
+        _ => Err(error()),
+    };
+    match expr {
+        syn::Expr::Array(array) if !array.elems.is_empty() => {
+            array.elems.iter().map(as_string).collect()
+        }
+        other => Ok(vec![as_string(other)?]),
+    }
+}
+
+/// The longest env var name that can be relied on across platforms. Keep in sync with
+/// `env_cfg::MAX_ENV_NAME_LEN`
+const MAX_ENV_NAME_LEN: usize = 255;
+
+/// Check that an env var name (or a name template) only uses characters that can be set from
+/// a shell, `[A-Za-z_][A-Za-z0-9_]*`, or dotted keys of such names (`app.database.host`) read
+/// from other sources, and is at most `MAX_ENV_NAME_LEN` long. Other names are never set in
+/// practice, so the variable would always be missing
+fn validate_env_name(name: &str, span: Span) -> syn::Result<()> {
+    // `{prefix}` is replaced by a prefix that is checked on its own
+    let valid_chars = name
+        .replace("{prefix}", "")
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '.');
+    let valid_start = name.starts_with("{prefix}")
+        || name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_');
+    if !(valid_chars && valid_start) {
+        return Err(syn::Error::new(
+            span,
+            format!(
+                "Invalid environment variable name `{name}`: names must start with an ASCII letter or `_` and contain only ASCII letters, digits, `_` and `.`"
+            ),
+        ));
+    }
+    let len = name.replace("{prefix}", "").len();
+    if len > MAX_ENV_NAME_LEN {
+        return Err(syn::Error::new(
+            span,
+            format!(
+                "Environment variable name `{name}` is {len} characters long, but names longer than {MAX_ENV_NAME_LEN} characters can't be set on every platform"
+            ),
+        ));
+    }
+    Ok(())
+}
+
+/// The `T` of an `Option<T>` field type
+fn option_inner_type(ty: &syn::Type) -> Option<&syn::Type> {
+    wrapped_type(ty, &["Option"])
+}
+
+/// The `T` of a `Vec<(String, T)>` field type
+fn section_type(ty: &syn::Type) -> Option<&syn::Type> {
+    let syn::Type::Tuple(tuple) = wrapped_type(ty, &["Vec"])? else {
+        return None;
+    };
+    match (tuple.elems.len(), tuple.elems.last()) {
+        (2, Some(section_type)) => Some(section_type),
+        _ => None,
+    }
+}
+
+/// The `T` of an `Arc<T>`, `Rc<T>` or `Box<T>` field type, which can be created with `From<T>`
+fn pointer_inner_type(ty: &syn::Type) -> Option<&syn::Type> {
+    wrapped_type(ty, &["Arc", "Rc", "Box"])
+}
+
+/// The `T` of a field type `W<T>`, where `W` is one of `wrappers`
+fn wrapped_type<'a>(ty: &'a syn::Type, wrappers: &[&str]) -> Option<&'a syn::Type> {
+    let syn::Type::Path(type_path) = ty else {
+        return None;
+    };
+    let segment = type_path.path.segments.last()?;
+    if type_path.qself.is_some() || !wrappers.iter().any(|wrapper| segment.ident == wrapper) {
+        return None;
+    }
+    let syn::PathArguments::AngleBracketed(arguments) = &segment.arguments else {
+        return None;
+    };
+    match arguments.args.first()? {
+        syn::GenericArgument::Type(ty) => Some(ty),
+        _ => None,
+    }
+}
+
+/// Whether the type is `SecretString` or `SecretBytes`, built from the loaded value without copying
+fn is_secret_type(ty: &syn::Type) -> bool {
+    if let syn::Type::Path(type_path) = ty {
+        if let Some(segment) = type_path.path.segments.last() {
+            return segment.ident == "SecretString" || segment.ident == "SecretBytes";
+        }
+    }
+    false
+}
+
+/// Whether the type is `OsString` or `PathBuf`, loaded without requiring valid Unicode
+fn is_os_string_type(ty: &syn::Type) -> bool {
+    if let syn::Type::Path(type_path) = ty {
+        if type_path.qself.is_none() {
+            if let Some(segment) = type_path.path.segments.last() {
+                return segment.ident == "OsString" || segment.ident == "PathBuf";
+            }
+        }
+    }
+    false
+}
+
+/// The value of an integer literal, possibly negated, e.g. a bound of `clamp(-1, 1)`
+fn int_literal(expr: &syn::Expr) -> Option<i128> {
+    match expr {
+        syn::Expr::Lit(syn::ExprLit {
+            lit: Lit::Int(int), ..
+        }) => int.base10_parse().ok(),
+        syn::Expr::Unary(syn::ExprUnary {
+            op: syn::UnOp::Neg(_),
+            expr,
+            ..
+        }) => int_literal(expr).map(|value| -value),
+        _ => None,
+    }
+}
+
+/// Whether a `default` is a string to parse, rather than a value of the field's type
+fn is_string_literal(expr: &syn::Expr) -> bool {
+    matches!(
+        expr,
+        syn::Expr::Lit(syn::ExprLit {
+            lit: Lit::Str(_),
+            ..
+        })
+    )
+}
+
+/// Check that the string default of a primitive field parses, so a typo in it is a compile error
+/// rather than an error whenever the variable happens to be unset. Other types are only known
+/// to parse at runtime
+fn validate_primitive_default(
+    ty: &syn::Type,
+    default: &syn::LitStr,
+    value_case: Option<ValueCase>,
+) -> syn::Result<()> {
+    fn check<T>(value: &str) -> Result<(), String>
+    where
+        T: std::str::FromStr,
+        T::Err: std::fmt::Display,
+    {
+        value.parse::<T>().map(|_| ()).map_err(|e| e.to_string())
+    }
+
+    let syn::Type::Path(type_path) = ty else {
+        return Ok(());
+    };
+    let Some(ident) = type_path
+        .path
+        .get_ident()
+        .filter(|_| type_path.qself.is_none())
+    else {
+        return Ok(());
+    };
+    let value = match value_case {
+        Some(case) => case.apply(&default.value()),
+        None => default.value(),
+    };
+    let result = match ident.to_string().as_str() {
+        "bool" => check::<bool>(&value),
+        "char" => check::<char>(&value),
+        "u8" => check::<u8>(&value),
+        "u16" => check::<u16>(&value),
+        "u32" => check::<u32>(&value),
+        "u64" => check::<u64>(&value),
+        "u128" => check::<u128>(&value),
+        "usize" => check::<usize>(&value),
+        "i8" => check::<i8>(&value),
+        "i16" => check::<i16>(&value),
+        "i32" => check::<i32>(&value),
+        "i64" => check::<i64>(&value),
+        "i128" => check::<i128>(&value),
+        "isize" => check::<isize>(&value),
+        "f32" => check::<f32>(&value),
+        "f64" => check::<f64>(&value),
+        _ => return Ok(()),
+    };
+    result.map_err(|e| {
+        syn::Error::new(
+            default.span(),
+            format!(
+                "default \"{}\" is not a valid `{ident}`: {e}",
+                default.value()
+            ),
+        )
+    })
+}
+
+/// The `T` of a `Result<T, EnvConfigError>` field type
+fn result_ok_type(ty: &syn::Type) -> Option<&syn::Type> {
+    let syn::Type::Path(type_path) = ty else {
+        return None;
+    };
+    let segment = type_path.path.segments.last()?;
+    if type_path.qself.is_some() || segment.ident != "Result" {
+        return None;
+    }
+    let syn::PathArguments::AngleBracketed(arguments) = &segment.arguments else {
+        return None;
+    };
+    let mut types = arguments.args.iter().filter_map(|argument| match argument {
+        syn::GenericArgument::Type(ty) => Some(ty),
+        _ => None,
+    });
+    match (types.next(), types.next(), types.next()) {
+        (Some(ok_type), Some(syn::Type::Path(error)), None)
+            if error
+                .path
+                .segments
+                .last()
+                .is_some_and(|segment| segment.ident == "EnvConfigError") =>
+        {
+            Some(ok_type)
+        }
+        _ => None,
+    }
+}
+
+/// Name of the local binding holding the resolved value of a field in `from_source`
+fn field_binding(field_name: &syn::Ident) -> syn::Ident {
+    format_ident!("__{}", field_name)
+}
+
+/// Generate the expression loading the value of `field`. `earlier_fields` are the fields
+/// declared before it, whose resolved values are already in scope.
+fn generate_field_value(
+    field: &Field,
+    struct_attributes: &StructAttributes,
+    earlier_fields: &[syn::Ident],
+) -> syn::Result<FieldCode> {
+    // A `Result<T, EnvConfigError>` field captures the error of loading `T` instead of failing
+    let Some(ok_type) = result_ok_type(&field.ty) else {
+        return generate_field_loader(field, &field.ty, struct_attributes, earlier_fields);
+    };
+    let FieldCode {
+        value,
+        present,
+        env_keys,
+        has_default,
+        ..
+    } = generate_field_loader(field, ok_type, struct_attributes, earlier_fields)?;
+    Ok(FieldCode {
+        value: quote! {
+            (|| -> ::core::result::Result<#ok_type, ::env_cfg::EnvConfigError> {
+                Ok(#value)
+            })()
+        },
+        // Loading never fails, so the variables are not required
+        missing: None,
+        present,
+        env_keys,
+        has_default,
+    })
+}
+
+/// Generate the expression loading `field` as `field_type`, propagating errors with `?`
+fn generate_field_loader(
+    field: &Field,
+    field_type: &syn::Type,
+    struct_attributes: &StructAttributes,
+    earlier_fields: &[syn::Ident],
+) -> syn::Result<FieldCode> {
+    let field_name = field.ident.as_ref().unwrap();
+    let field_name_str = struct_attributes
+        .name_case
+        .field_name(&field_name.to_string());
+
+    // Parse attributes
+    let mut env_name = struct_attributes
+        .prefix_config
+        .apply_to_field(&field_name_str, struct_attributes.name_case);
+    // The names in `env_name` and `env_aliases`, if they are known at compile time
+    let mut static_names: Option<Vec<String>> = struct_attributes
+        .prefix_config
+        .static_full_name(&field_name_str, struct_attributes.name_case)
+        .map(|name| vec![name]);
+    // Attribute values are kept alongside the span of the attribute that set them,
+    // so validation errors can point at the offending attribute rather than the field
+    // `env` or its alias `rename`
+    let mut custom_env: Option<(&'static str, Span)> = None;
+    // Further names from `env = [...]`, tried in order when the first one is unset
+    let mut env_aliases: Vec<proc_macro2::TokenStream> = Vec::new();
+    // Names from `alias(...)`, tried after those in `env`, each with an optional parser of its own
+    let mut aliases: Vec<(proc_macro2::TokenStream, Option<syn::ExprPath>)> = Vec::new();
+    let mut alias_static_names: Option<Vec<String>> = Some(Vec::new());
+    let mut field_prefix: Option<(PrefixConfig, Span)> = None;
+    let mut default_expr: Option<(syn::Expr, Span)> = None;
+    let mut default_from: Option<(syn::Ident, Span)> = None;
+    let mut default_env: Option<(proc_macro2::TokenStream, Span)> = None;
+    let mut skip: Option<Span> = None;
+    let mut parse_with: Option<(syn::Expr, Span)> = None;
+    // `transform` functions, applied in the order they are declared
+    let mut transforms: Vec<syn::Expr> = Vec::new();
+    let mut validate_map: Option<(syn::Expr, Span)> = None;
+    let mut clamp: Option<(syn::Expr, syn::Expr, Span)> = None;
+    let mut on_clamp: Option<(syn::Expr, Span)> = None;
+    let mut on_parse_error: Option<(ParseErrorPolicy, Span)> = None;
+    let mut parse_with_env: Option<(syn::Expr, Span)> = None;
+    let mut sections: Option<(proc_macro2::TokenStream, Span)> = None;
+    let mut indexed_list: Option<Span> = None;
+    let mut is_nested: Option<Span> = None;
+    let mut independent: Option<Span> = None;
+    // Load the nested config with this struct's prefix and the field name prepended
+    let mut prefix_from_field: Option<Span> = None;
+    // Values for variables of a `nested` config that are not set
+    let mut nested_defaults: Option<(Vec<(String, syn::LitStr)>, Span)> = None;
+    let mut default_if_absent: Option<Span> = None;
+    let mut error_if_set: Option<Span> = None;
+    let mut enabled_by: Option<(proc_macro2::TokenStream, Span)> = None;
+    let mut expand: Option<ExpandMode> = None;
+    let mut human_int: Option<Span> = None;
+    let mut duration_secs_f64: Option<Span> = None;
+    let mut flag: Option<Span> = None;
+    let mut flags: Option<Span> = None;
+    let mut try_from: Option<Span> = None;
+    // `Some(None)` for `radix_auto`
+    let mut radix: Option<Option<u32>> = None;
+    let mut trim_matches: Option<String> = None;
+    let mut trim_prefix: Option<String> = None;
+    let mut preserve_case: Option<Span> = None;
+    let mut required: Option<Span> = None;
+    // `Some(true)` for `empty_as_none`, `Some(false)` for `allow_empty`
+    let mut empty_as_none: Option<(bool, Span)> = None;
+    // Attributes that change how the value is loaded, for validating combinations with
+    // `skip` and `nested`
+    let mut value_attributes: Vec<(&'static str, Span)> = Vec::new();
+
+    for attr in &field.attrs {
+        if attr.path().is_ident("env_cfg") {
+            if let Meta::List(meta_list) = &attr.meta {
+                let nested_result = meta_list.parse_args_with(
+                    syn::punctuated::Punctuated::<syn::Meta, syn::Token![,]>::parse_terminated,
+                );
+
+                if let Ok(nested_metas) = nested_result {
                     for nested in nested_metas {
                         match nested {
                             Meta::Path(path) if path.is_ident("skip") => {
-                                skip = true;
+                                skip = Some(path.span());
+                            }
+                            Meta::Path(path) if path.is_ident("nested") => {
+                                is_nested = Some(path.span());
+                            }
+                            Meta::Path(path) if path.is_ident("independent") => {
+                                independent = Some(path.span());
+                            }
+                            Meta::Path(path) if path.is_ident("prefix_from_field") => {
+                                prefix_from_field = Some(path.span());
+                            }
+                            Meta::List(list) if list.path.is_ident("defaults") => {
+                                let mut defaults = Vec::new();
+                                for default in list.parse_args_with(
+                                    Punctuated::<syn::MetaNameValue, Token![,]>::parse_terminated,
+                                )? {
+                                    let (
+                                        Some(name),
+                                        syn::Expr::Lit(syn::ExprLit {
+                                            lit: Lit::Str(value),
+                                            ..
+                                        }),
+                                    ) = (default.path.get_ident(), &default.value)
+                                    else {
+                                        return Err(syn::Error::new(
+                                            default.span(),
+                                            r#"defaults takes variable names with string values, e.g. `defaults(DB_PORT = "5432")`"#,
+                                        ));
+                                    };
+                                    let name =
+                                        struct_attributes.name_case.apply_case(&name.to_string());
+                                    validate_env_name(&name, default.path.span())?;
+                                    defaults.push((name, value.clone()));
+                                }
+                                nested_defaults = Some((defaults, list.span()));
+                            }
+                            Meta::Path(path) if path.is_ident("default_if_absent") => {
+                                default_if_absent = Some(path.span());
+                            }
+                            Meta::Path(path) if path.is_ident("error_if_set") => {
+                                error_if_set = Some(path.span());
+                            }
+                            Meta::NameValue(name_value)
+                                if name_value.path.is_ident("enabled_by") =>
+                            {
+                                let syn::Expr::Lit(syn::ExprLit {
+                                    lit: Lit::Str(lit_str),
+                                    ..
+                                }) = &name_value.value
+                                else {
+                                    return Err(syn::Error::new(
+                                        name_value.value.span(),
+                                        "enabled_by must be a string literal containing the variable name",
+                                    ));
+                                };
+                                validate_env_name(&lit_str.value(), lit_str.span())?;
+                                enabled_by = Some((
+                                    struct_attributes.prefix_config.apply_to_template(
+                                        &lit_str.value(),
+                                        struct_attributes.name_case,
+                                    ),
+                                    name_value.span(),
+                                ));
+                            }
+                            Meta::NameValue(name_value)
+                                if name_value.path.is_ident("env")
+                                    || name_value.path.is_ident("rename") =>
+                            {
+                                let attribute = if name_value.path.is_ident("env") {
+                                    "env"
+                                } else {
+                                    "rename"
+                                };
+                                if let Some((existing, _)) = custom_env {
+                                    if existing != attribute {
+                                        return Err(syn::Error::new(
+                                            name_value.span(),
+                                            "Cannot use both 'env' and 'rename' attributes on the same field",
+                                        ));
+                                    }
+                                }
+                                let names = env_name_list(
+                                    &name_value.value,
+                                    attribute,
+                                    &struct_attributes.name_case.apply_case(&field_name_str),
+                                )?;
+                                static_names = names
+                                    .iter()
+                                    .map(|name| {
+                                        struct_attributes
+                                            .prefix_config
+                                            .static_template(name, struct_attributes.name_case)
+                                    })
+                                    .collect();
+                                let mut names = names.into_iter().map(|name| {
+                                    struct_attributes
+                                        .prefix_config
+                                        .apply_to_template(&name, struct_attributes.name_case)
+                                });
+                                env_name = names.next().unwrap();
+                                env_aliases = names.collect();
+                                custom_env = Some((attribute, name_value.span()));
+                            }
+                            Meta::NameValue(name_value) if name_value.path.is_ident("prefix") => {
+                                field_prefix = Some((
+                                    PrefixConfig::from_expr(&name_value.value)?,
+                                    name_value.span(),
+                                ));
+                                value_attributes.push(("prefix", name_value.span()));
+                            }
+                            Meta::NameValue(name_value) if name_value.path.is_ident("default") => {
+                                default_expr = Some((name_value.value.clone(), name_value.span()));
+                                value_attributes.push(("default", name_value.span()));
+                            }
+                            Meta::NameValue(name_value)
+                                if name_value.path.is_ident("default_from") =>
+                            {
+                                let referenced = if let syn::Expr::Lit(syn::ExprLit {
+                                    lit: Lit::Str(lit_str),
+                                    ..
+                                }) = &name_value.value
+                                {
+                                    lit_str.parse::<syn::Ident>().map_err(|_| {
+                                        syn::Error::new(
+                                            lit_str.span(),
+                                            "default_from must be the name of another field",
+                                        )
+                                    })?
+                                } else {
+                                    return Err(syn::Error::new(
+                                        name_value.value.span(),
+                                        "default_from must be a string literal containing the field name",
+                                    ));
+                                };
+                                default_from = Some((referenced, name_value.span()));
+                                value_attributes.push(("default_from", name_value.span()));
+                            }
+                            Meta::NameValue(name_value)
+                                if name_value.path.is_ident("default_env") =>
+                            {
+                                let syn::Expr::Lit(syn::ExprLit {
+                                    lit: Lit::Str(lit_str),
+                                    ..
+                                }) = &name_value.value
+                                else {
+                                    return Err(syn::Error::new(
+                                        name_value.value.span(),
+                                        "default_env must be a string literal containing the variable name",
+                                    ));
+                                };
+                                validate_env_name(&lit_str.value(), lit_str.span())?;
+                                default_env = Some((
+                                    struct_attributes.prefix_config.apply_to_template(
+                                        &lit_str.value(),
+                                        struct_attributes.name_case,
+                                    ),
+                                    name_value.span(),
+                                ));
+                                value_attributes.push(("default_env", name_value.span()));
+                            }
+                            Meta::NameValue(name_value)
+                                if name_value.path.is_ident("parse_with_env") =>
+                            {
+                                parse_with_env =
+                                    Some((name_value.value.clone(), name_value.span()));
+                                value_attributes.push(("parse_with_env", name_value.span()));
+                            }
+                            Meta::NameValue(name_value) if name_value.path.is_ident("sections") => {
+                                let syn::Expr::Lit(syn::ExprLit {
+                                    lit: Lit::Str(lit_str),
+                                    ..
+                                }) = &name_value.value
+                                else {
+                                    return Err(syn::Error::new(
+                                        name_value.value.span(),
+                                        "sections must be a string literal containing the prefix of the sections",
+                                    ));
+                                };
+                                validate_env_name(&lit_str.value(), lit_str.span())?;
+                                sections = Some((
+                                    struct_attributes.prefix_config.apply_to_template(
+                                        &lit_str.value(),
+                                        struct_attributes.name_case,
+                                    ),
+                                    name_value.span(),
+                                ));
+                                value_attributes.push(("sections", name_value.span()));
+                            }
+                            Meta::NameValue(name_value)
+                                if name_value.path.is_ident("parse_with") =>
+                            {
+                                parse_with = Some((name_value.value.clone(), name_value.span()));
+                                value_attributes.push(("parse_with", name_value.span()));
+                            }
+                            Meta::NameValue(name_value)
+                                if name_value.path.is_ident("transform") =>
+                            {
+                                transforms.push(name_value.value.clone());
+                                value_attributes.push(("transform", name_value.span()));
+                            }
+                            Meta::NameValue(name_value)
+                                if name_value.path.is_ident("validate_map") =>
+                            {
+                                validate_map = Some((name_value.value.clone(), name_value.span()));
+                                value_attributes.push(("validate_map", name_value.span()));
+                            }
+                            Meta::List(list) if list.path.is_ident("clamp") => {
+                                let bounds = list.parse_args_with(
+                                    Punctuated::<syn::Expr, Token![,]>::parse_terminated,
+                                )?;
+                                let mut bounds = bounds.into_iter();
+                                let (Some(min), Some(max), None) =
+                                    (bounds.next(), bounds.next(), bounds.next())
+                                else {
+                                    return Err(syn::Error::new(
+                                        list.span(),
+                                        "clamp takes a minimum and a maximum, e.g. `clamp(1, 256)`",
+                                    ));
+                                };
+                                if let (Some(min_value), Some(max_value)) =
+                                    (int_literal(&min), int_literal(&max))
+                                {
+                                    if min_value > max_value {
+                                        return Err(syn::Error::new(
+                                            list.span(),
+                                            "The minimum of clamp must not exceed the maximum",
+                                        ));
+                                    }
+                                }
+                                clamp = Some((min, max, list.span()));
+                                value_attributes.push(("clamp", list.span()));
+                            }
+                            Meta::List(list) if list.path.is_ident("alias") => {
+                                let mut alias_name = None;
+                                let mut alias_parser = None;
+                                for meta in list.parse_args_with(
+                                    Punctuated::<syn::Meta, Token![,]>::parse_terminated,
+                                )? {
+                                    match meta {
+                                        Meta::NameValue(name_value)
+                                            if name_value.path.is_ident("env") =>
+                                        {
+                                            let syn::Expr::Lit(syn::ExprLit {
+                                                lit: Lit::Str(lit_str),
+                                                ..
+                                            }) = &name_value.value
+                                            else {
+                                                return Err(syn::Error::new(
+                                                    name_value.value.span(),
+                                                    "The env of an alias must be a string literal containing the variable name",
+                                                ));
+                                            };
+                                            validate_env_name(&lit_str.value(), lit_str.span())?;
+                                            alias_name = Some(lit_str.value());
+                                        }
+                                        Meta::NameValue(name_value)
+                                            if name_value.path.is_ident("parse_with") =>
+                                        {
+                                            alias_parser = Some(parse_function_path(
+                                                &name_value.value,
+                                                "parse_with",
+                                            )?);
+                                        }
+                                        other => {
+                                            return Err(syn::Error::new(
+                                                other.span(),
+                                                r#"alias takes `env = "<VAR_NAME>"` and an optional `parse_with = "<PARSER_FN>"`"#,
+                                            ));
+                                        }
+                                    }
+                                }
+                                let Some(alias_name) = alias_name else {
+                                    return Err(syn::Error::new(
+                                        list.span(),
+                                        r#"alias requires the variable name, e.g. `alias(env = "OLD_POINT")`"#,
+                                    ));
+                                };
+                                let prefix_config = &struct_attributes.prefix_config;
+                                alias_static_names =
+                                    alias_static_names
+                                        .zip(prefix_config.static_template(
+                                            &alias_name,
+                                            struct_attributes.name_case,
+                                        ))
+                                        .map(|(mut names, name)| {
+                                            names.push(name);
+                                            names
+                                        });
+                                aliases.push((
+                                    prefix_config.apply_to_template(
+                                        &alias_name,
+                                        struct_attributes.name_case,
+                                    ),
+                                    alias_parser,
+                                ));
+                                value_attributes.push(("alias", list.span()));
+                            }
+                            Meta::NameValue(name_value) if name_value.path.is_ident("on_clamp") => {
+                                on_clamp = Some((name_value.value.clone(), name_value.span()));
+                                value_attributes.push(("on_clamp", name_value.span()));
+                            }
+                            Meta::NameValue(name_value)
+                                if name_value.path.is_ident("on_parse_error") =>
+                            {
+                                on_parse_error = Some((
+                                    ParseErrorPolicy::from_expr(&name_value.value)?,
+                                    name_value.span(),
+                                ));
+                                value_attributes.push(("on_parse_error", name_value.span()));
+                            }
+                            ref meta if meta.path().is_ident("expand") => {
+                                expand = Some(ExpandMode::from_meta(meta)?);
+                                value_attributes.push(("expand", meta.span()));
+                            }
+                            Meta::Path(path) if path.is_ident("indexed_list") => {
+                                indexed_list = Some(path.span());
+                                value_attributes.push(("indexed_list", path.span()));
+                            }
+                            Meta::Path(path) if path.is_ident("human_int") => {
+                                human_int = Some(path.span());
+                                value_attributes.push(("human_int", path.span()));
+                            }
+                            Meta::Path(path) if path.is_ident("duration_secs_f64") => {
+                                duration_secs_f64 = Some(path.span());
+                                value_attributes.push(("duration_secs_f64", path.span()));
+                            }
+                            Meta::Path(path) if path.is_ident("flag") => {
+                                flag = Some(path.span());
+                                value_attributes.push(("flag", path.span()));
+                            }
+                            Meta::Path(path) if path.is_ident("flags") => {
+                                flags = Some(path.span());
+                                value_attributes.push(("flags", path.span()));
+                            }
+                            Meta::Path(path) if path.is_ident("try_from") => {
+                                try_from = Some(path.span());
+                                value_attributes.push(("try_from", path.span()));
+                            }
+                            Meta::Path(path) if path.is_ident("radix_auto") => {
+                                radix = Some(None);
+                                value_attributes.push(("radix_auto", path.span()));
+                            }
+                            Meta::NameValue(name_value) if name_value.path.is_ident("radix") => {
+                                let value = match &name_value.value {
+                                    syn::Expr::Lit(syn::ExprLit {
+                                        lit: Lit::Int(lit_int),
+                                        ..
+                                    }) => lit_int.base10_parse::<u32>().ok(),
+                                    _ => None,
+                                }
+                                .filter(|radix| (2..=36).contains(radix))
+                                .ok_or_else(|| {
+                                    syn::Error::new(
+                                        name_value.value.span(),
+                                        "radix must be an integer between 2 and 36",
+                                    )
+                                })?;
+                                radix = Some(Some(value));
+                                value_attributes.push(("radix", name_value.span()));
                             }
-                            Meta::Path(path) if path.is_ident("nested") => {
-                                is_nested = true;
+                            Meta::Path(path)
+                                if path.is_ident("empty_as_none")
+                                    || path.is_ident("allow_empty") =>
+                            {
+                                let value = path.is_ident("empty_as_none");
+                                if let Some((existing, _)) = empty_as_none {
+                                    if existing != value {
+                                        return Err(syn::Error::new(
+                                            path.span(),
+                                            "Cannot use both 'empty_as_none' and 'allow_empty' on the same field",
+                                        ));
+                                    }
+                                }
+                                empty_as_none = Some((value, path.span()));
+                                value_attributes.push((
+                                    if value {
+                                        "empty_as_none"
+                                    } else {
+                                        "allow_empty"
+                                    },
+                                    path.span(),
+                                ));
+                            }
+                            Meta::Path(path) if path.is_ident("required") => {
+                                required = Some(path.span());
+                                value_attributes.push(("required", path.span()));
+                            }
+                            Meta::Path(path) if path.is_ident("preserve_case") => {
+                                preserve_case = Some(path.span());
+                                value_attributes.push(("preserve_case", path.span()));
                             }
-                            Meta::NameValue(name_value) if name_value.path.is_ident("env") => {
+                            Meta::Path(path) if path.is_ident("unquote") => {
+                                trim_matches = Some("\"'".to_string());
+                                value_attributes.push(("unquote", path.span()));
+                            }
+                            Meta::NameValue(name_value)
+                                if name_value.path.is_ident("trim_matches") =>
+                            {
                                 if let syn::Expr::Lit(syn::ExprLit {
                                     lit: Lit::Str(lit_str),
                                     ..
                                 }) = &name_value.value
                                 {
-                                    env_name = lit_str.value();
+                                    trim_matches = Some(lit_str.value());
+                                } else {
+                                    return Err(syn::Error::new(
+                                        name_value.value.span(),
+                                        "trim_matches must be a string literal containing the characters to strip",
+                                    ));
                                 }
-                            }
-                            Meta::NameValue(name_value) if name_value.path.is_ident("default") => {
-                                default_expr = Some(name_value.value.clone());
+                                value_attributes.push(("trim_matches", name_value.span()));
                             }
                             Meta::NameValue(name_value)
-                                if name_value.path.is_ident("parse_with") =>
+                                if name_value.path.is_ident("trim_prefix") =>
                             {
-                                parse_with = Some(name_value.value.clone());
+                                if let syn::Expr::Lit(syn::ExprLit {
+                                    lit: Lit::Str(lit_str),
+                                    ..
+                                }) = &name_value.value
+                                {
+                                    trim_prefix = Some(lit_str.value());
+                                } else {
+                                    return Err(syn::Error::new(
+                                        name_value.value.span(),
+                                        "trim_prefix must be a string literal containing the prefix to strip",
+                                    ));
+                                }
+                                value_attributes.push(("trim_prefix", name_value.span()));
                             }
                             other => {
                                 return Err(syn::Error::new(
@@ -249,88 +2534,972 @@ fn generate_field_assignment(
         }
     }
 
+    // A field-level prefix replaces the struct prefix for this field only
+    if let Some((prefix_config, span)) = &field_prefix {
+        if let Some((attribute, _)) = custom_env {
+            return Err(syn::Error::new(
+                *span,
+                format!("Cannot use both '{attribute}' and 'prefix' attributes on the same field"),
+            ));
+        }
+        env_name = prefix_config.apply_to_field(&field_name_str, struct_attributes.name_case);
+        static_names = prefix_config
+            .static_full_name(&field_name_str, struct_attributes.name_case)
+            .map(|name| vec![name]);
+    }
+
+    // The name derived from the field (and struct) name is only checked if the field reads it
+    let reads_own_name = if skip.is_some() {
+        error_if_set.is_some()
+    } else {
+        is_nested.is_none() && parse_with_env.is_none()
+    };
+    // Fields of the same struct reading the same variable are rejected in `generate_struct_body`
+    let own_keys = match static_names.zip(alias_static_names) {
+        Some((mut names, alias_names)) if skip.is_none() && reads_own_name => {
+            names.extend(alias_names);
+            names
+        }
+        _ => Vec::new(),
+    };
+    if custom_env.is_none() && reads_own_name {
+        let prefix_config = field_prefix
+            .as_ref()
+            .map_or(&struct_attributes.prefix_config, |(config, _)| config);
+        validate_env_name(
+            &prefix_config.static_field_name(&field_name_str, struct_attributes.name_case),
+            field_name.span(),
+        )?;
+    }
+    // Names assembled from a prefix and a template are only complete here
+    for name in &own_keys {
+        validate_env_name(name, field_name.span())?;
+    }
+
     // Validate attribute combinations
-    if skip && (default_expr.is_some() || parse_with.is_some() || is_nested) {
+    if skip.is_some() {
+        let other_span = is_nested
+            .or(independent)
+            .or(default_if_absent)
+            .or(value_attributes.first().map(|(_, span)| *span));
+        if let Some(span) = other_span {
+            return Err(syn::Error::new(
+                span,
+                "Cannot use 'skip' with other attributes",
+            ));
+        }
+    }
+
+    if let (None, Some((_, span))) = (is_nested, &nested_defaults) {
+        return Err(syn::Error::new(*span, "'defaults' requires 'nested'"));
+    }
+    if let (None, Some(span)) = (is_nested, independent) {
+        return Err(syn::Error::new(span, "'independent' requires 'nested'"));
+    }
+    if let (None, Some(span)) = (is_nested, prefix_from_field) {
+        return Err(syn::Error::new(
+            span,
+            "'prefix_from_field' requires 'nested'",
+        ));
+    }
+    if let (Some(span), Some(_)) = (prefix_from_field, independent) {
         return Err(syn::Error::new(
-            field.span(),
-            "Cannot use 'skip' with other attributes",
+            span,
+            "Cannot use 'prefix_from_field' with 'independent'",
         ));
     }
 
-    if is_nested && (default_expr.is_some() || parse_with.is_some()) {
+    if let (None, Some(span)) = (is_nested, default_if_absent) {
+        return Err(syn::Error::new(
+            span,
+            "'default_if_absent' requires 'nested'",
+        ));
+    }
+
+    if let (None, Some((_, span))) = (is_nested, &enabled_by) {
+        return Err(syn::Error::new(*span, "'enabled_by' requires 'nested'"));
+    }
+
+    if let (None, Some(span)) = (skip, error_if_set) {
+        return Err(syn::Error::new(span, "'error_if_set' requires 'skip'"));
+    }
+
+    if let (None, Some(span)) = (struct_attributes.value_case, preserve_case) {
+        return Err(syn::Error::new(
+            span,
+            "'preserve_case' requires the struct attribute 'rename_all_values'",
+        ));
+    }
+
+    if is_nested.is_some() {
+        if let Some((name, span)) = value_attributes.first() {
+            let message = match *name {
+                "default" | "parse_with" => {
+                    "Cannot use 'nested' with 'default' or 'parse_with' attributes".to_string()
+                }
+                other => format!("Cannot use 'nested' with '{other}'"),
+            };
+            return Err(syn::Error::new(*span, message));
+        }
+    }
+
+    if sections.is_some() {
+        // Each section is loaded as a whole, so nothing else applies to it
+        if let Some((name, span)) = value_attributes
+            .iter()
+            .find(|(name, _)| *name != "sections")
+        {
+            return Err(syn::Error::new(
+                *span,
+                format!("Cannot use 'sections' with '{name}'"),
+            ));
+        }
+    }
+
+    if parse_with_env.is_some() {
+        // The function loads the whole value itself, so nothing else applies to it
+        if let Some((name, span)) = value_attributes
+            .iter()
+            .find(|(name, _)| *name != "parse_with_env")
+        {
+            return Err(syn::Error::new(
+                *span,
+                format!("Cannot use 'parse_with_env' with '{name}'"),
+            ));
+        }
+    }
+
+    if indexed_list.is_some() {
+        // Each entry is parsed with `FromStr`, so only the name of the variables can be changed
+        if let Some((name, span)) = value_attributes
+            .iter()
+            .find(|(name, _)| !matches!(*name, "indexed_list" | "prefix"))
+        {
+            return Err(syn::Error::new(
+                *span,
+                format!("Cannot use 'indexed_list' with '{name}'"),
+            ));
+        }
+        if let (false, Some((attribute, span))) = (env_aliases.is_empty(), custom_env) {
+            return Err(syn::Error::new(
+                span,
+                format!("Cannot use 'indexed_list' with a list of names in '{attribute}'"),
+            ));
+        }
+    }
+
+    // Only one attribute can decide how the value is parsed
+    let mut parser_attributes = value_attributes.iter().filter(|(name, _)| {
+        matches!(
+            *name,
+            "parse_with"
+                | "human_int"
+                | "duration_secs_f64"
+                | "radix_auto"
+                | "radix"
+                | "flag"
+                | "flags"
+                | "try_from"
+        )
+    });
+    if let (Some((first, _)), Some((second, span))) =
+        (parser_attributes.next(), parser_attributes.next())
+    {
         return Err(syn::Error::new(
-            field.span(),
-            "Cannot use 'nested' with 'default' or 'parse_with' attributes",
+            *span,
+            format!("Cannot use both '{first}' and '{second}' attributes on the same field"),
         ));
     }
 
-    if parse_with.is_some() && default_expr.is_some() {
+    if let (Some(span), Some((true, _))) = (flag, empty_as_none) {
         return Err(syn::Error::new(
-            field.span(),
-            "Cannot use both 'parse_with' and 'default' attributes on the same field",
+            span,
+            "Cannot use both 'flag' and 'empty_as_none' attributes on the same field, an empty flag is `true`",
         ));
     }
 
+    if let Some(span) = required {
+        if !is_option_type(field_type) {
+            return Err(syn::Error::new(
+                span,
+                "'required' can only be used on Option fields",
+            ));
+        }
+        if let Some((_, default_span)) = &default_expr {
+            return Err(syn::Error::new(
+                *default_span,
+                "Cannot use both 'required' and 'default' attributes on the same field",
+            ));
+        }
+    }
+
+    if let Some((referenced, span)) = &default_from {
+        if default_expr.is_some() {
+            return Err(syn::Error::new(
+                *span,
+                "Cannot use both 'default' and 'default_from' attributes on the same field",
+            ));
+        }
+        if required.is_some() {
+            return Err(syn::Error::new(
+                *span,
+                "Cannot use both 'required' and 'default_from' attributes on the same field",
+            ));
+        }
+        if !earlier_fields.contains(referenced) {
+            return Err(syn::Error::new(
+                *span,
+                format!("default_from must name a field declared before '{field_name}'"),
+            ));
+        }
+    }
+
+    match on_parse_error {
+        Some((ParseErrorPolicy::Default, span))
+            if default_expr.is_none() && default_from.is_none() =>
+        {
+            return Err(syn::Error::new(
+                span,
+                r#"on_parse_error = "default" requires 'default' or 'default_from'"#,
+            ));
+        }
+        Some((ParseErrorPolicy::None, span)) if !is_option_type(field_type) => {
+            return Err(syn::Error::new(
+                span,
+                r#"on_parse_error = "none" can only be used on Option fields"#,
+            ));
+        }
+        _ => {}
+    }
+
+    // In strict mode declared defaults are ignored, so the field's variable must be set even if
+    // the field is optional
+    if struct_attributes.strict.is_some() {
+        let default_span = match (default_expr.take(), default_from.take()) {
+            (Some((_, span)), _) | (_, Some((_, span))) => Some(span),
+            (None, None) => None,
+        };
+        required = required.or(default_span);
+    }
+
+    // Secrets are read with `get_secret`, so sources recording their lookups leave out the value
+    let is_secret = cfg!(feature = "zeroize")
+        && is_secret_type(option_inner_type(field_type).unwrap_or(field_type));
+    let get = if is_secret {
+        quote! { get_secret }
+    } else {
+        quote! { get }
+    };
+
+    // Whether the field's own variables are set, ignoring fallbacks that other fields may share
+    let names_present = {
+        let names = std::iter::once(&env_name)
+            .chain(&env_aliases)
+            .chain(aliases.iter().map(|(name, _)| name));
+        quote! { #(!matches!(source.#get(#names), Ok(None)))||* }
+    };
+
     // Handle skipped fields
-    if skip {
-        return Ok(quote! {
-            #field_name: Default::default()
+    if skip.is_some() {
+        // Setting the variable of a skipped field has no effect, which can be rejected so it
+        // doesn't go unnoticed
+        let check_unset = error_if_set.map(|_| {
+            let message = format!("set, but field `{field_name}` is skipped and ignores it");
+            let names = std::iter::once(&env_name).chain(&env_aliases);
+            quote! {
+                #(
+                    if !matches!(source.#get(#names), Ok(None)) {
+                        return Err(::env_cfg::EnvConfigError::Invalid(
+                            ::env_cfg::__private::ToString::to_string(#names),
+                            ::env_cfg::__private::ToString::to_string(#message),
+                        ));
+                    }
+                )*
+            }
+        });
+        return Ok(FieldCode {
+            value: quote! {
+                {
+                    #check_unset
+                    Default::default()
+                }
+            },
+            missing: None,
+            present: None,
+            env_keys: Vec::new(),
+            has_default: false,
         });
     }
 
     // Handle nested EnvConfig structs
-    if is_nested {
-        return Ok(quote! {
-            #field_name: #field_type::from_env()
-                .map_err(|e| ::env_cfg::EnvConfigError::Parse(
-                    format!("nested {}", stringify!(#field_type)),
-                    e.to_string()
-                ))?
+    if is_nested.is_some() {
+        // A toggled `Option<T>` field is `None` when disabled, other fields use `Default`
+        let toggled_option = enabled_by.as_ref().and(option_inner_type(field_type));
+        let nested_type = toggled_option.unwrap_or(field_type);
+        // A config behind `Arc`, `Rc` or `Box` is loaded, then moved into the pointer
+        let pointer_inner = pointer_inner_type(nested_type);
+        let nested_type = pointer_inner.unwrap_or(nested_type);
+        let nested_type_label = type_label(nested_type);
+        let propagated_prefix = if prefix_from_field.is_some() {
+            // The field's own variable name is the prefix, e.g. `APP_DB_CONFIG` for `db_config`
+            Some(env_name.clone())
+        } else {
+            struct_attributes
+                .propagate_prefix
+                .filter(|_| independent.is_none())
+                .and_then(|_| struct_attributes.prefix_config.to_expr())
+        };
+        let dotted = struct_attributes.name_case.is_dotted();
+        let (mut value, mut missing, present) = if let Some(prefix) = propagated_prefix {
+            // Read the nested config's variables with this struct's prefix prepended, and name
+            // the prefix and the prefixed variables in its errors
+            (
+                quote! {
+                    {
+                        let source = ::env_cfg::__private::Prefixed { source, prefix: #prefix, dotted: #dotted };
+                        <#nested_type as ::env_cfg::EnvConfig>::from_source(&source)
+                            .map_err(|e| source.error(e))
+                            .map_err(|e| ::env_cfg::EnvConfigError::context(
+                                ::core::convert::Into::into(e),
+                                source.nested_context::<#nested_type>(#nested_type_label),
+                            ))?
+                    }
+                },
+                quote! {
+                    let prefixed = ::env_cfg::__private::Prefixed { source, prefix: #prefix, dotted: #dotted };
+                    missing.extend(
+                        <#nested_type as ::env_cfg::EnvConfig>::missing_vars(&prefixed)
+                            .iter()
+                            .map(|name| prefixed.label(name)),
+                    );
+                },
+                quote! {
+                    <#nested_type as ::env_cfg::EnvConfig>::any_vars_set(
+                        &::env_cfg::__private::Prefixed { source, prefix: #prefix, dotted: #dotted },
+                    )
+                },
+            )
+        } else {
+            (
+                quote! {
+                    <#nested_type as ::env_cfg::EnvConfig>::from_source(source)
+                        .map_err(|e| ::env_cfg::EnvConfigError::context(
+                            ::core::convert::Into::into(e),
+                            ::env_cfg::__private::nested_context::<#nested_type>(#nested_type_label),
+                        ))?
+                },
+                quote! {
+                    missing.extend(<#nested_type as ::env_cfg::EnvConfig>::missing_vars(source));
+                },
+                quote! { <#nested_type as ::env_cfg::EnvConfig>::any_vars_set(source) },
+            )
+        };
+        if let Some((defaults, _)) = &nested_defaults {
+            // The nested config reads the defaults for variables that are not set. Whether any of
+            // its variables are set is still checked without them
+            let (names, values): (Vec<_>, Vec<_>) = defaults.iter().cloned().unzip();
+            let layer_defaults = quote! {
+                let defaults = ::env_cfg::__private::defaults(&[#((#names, #values)),*]);
+                let layered = ::env_cfg::__private::Layered { source, fallback: &defaults };
+                let source: &dyn ::env_cfg::EnvSource = &layered;
+            };
+            value = quote! {
+                {
+                    #layer_defaults
+                    #value
+                }
+            };
+            missing = quote! {
+                {
+                    #layer_defaults
+                    #missing
+                }
+            };
+        }
+        if pointer_inner.is_some() {
+            value = quote! { ::core::convert::From::from(#value) };
+        }
+        if default_if_absent.is_some() {
+            // Without any of its variables the nested config is `Default`, but once one is set,
+            // the others are required as usual
+            value = quote! {
+                if #present { #value } else { ::core::default::Default::default() }
+            };
+            missing = quote! {
+                if #present {
+                    #missing
+                }
+            };
+        }
+        let present = match &enabled_by {
+            Some((flag, _)) => quote! { (!matches!(source.get(#flag), Ok(None)) || #present) },
+            None => present,
+        };
+        if let Some((flag, _)) = &enabled_by {
+            // The nested config is only loaded, and its variables only required, when the flag
+            // is set to a true value like `true`, `1` or `yes`, see `parse_flag`
+            let disabled = if toggled_option.is_some() {
+                value = quote! { Some(#value) };
+                quote! { None }
+            } else {
+                quote! { Default::default() }
+            };
+            value = quote! {
+                {
+                    let enabled = match source.get(#flag)? {
+                        Some(value) => ::env_cfg::parse_flag(&value).map_err(|e| {
+                            ::env_cfg::EnvConfigError::Parse(
+                                ::env_cfg::__private::ToString::to_string(#flag),
+                                e,
+                            )
+                        })?,
+                        None => false,
+                    };
+                    if enabled { #value } else { #disabled }
+                }
+            };
+            missing = quote! {
+                if matches!(
+                    source.get(#flag),
+                    Ok(Some(value)) if matches!(::env_cfg::parse_flag(&value), Ok(true))
+                ) {
+                    #missing
+                }
+            };
+        }
+        return Ok(FieldCode {
+            value,
+            missing: Some(missing),
+            present: Some(present),
+            env_keys: Vec::new(),
+            has_default: false,
         });
     }
 
-    // Handle fields with custom parser
-    if let Some(parser_fn) = parse_with {
-        let parser_ident = if let syn::Expr::Lit(syn::ExprLit {
-            lit: Lit::Str(lit_str),
-            ..
-        }) = &parser_fn
-        {
-            let fn_name = lit_str.value();
-            syn::Ident::new(&fn_name, lit_str.span())
+    // Handle fields loaded by a custom function with access to the source
+    if let Some((loader_fn, _)) = parse_with_env {
+        let loader_path = parse_function_path(&loader_fn, "parse_with_env")?;
+        // The variables read by the function are unknown, so none are reported as missing
+        return Ok(FieldCode {
+            value: quote! { #loader_path(source)? },
+            missing: None,
+            present: None,
+            env_keys: Vec::new(),
+            has_default: false,
+        });
+    }
+
+    // Handle named sections `PREFIX_<NAME>_...` of a nested config, collected into a `Vec`
+    if let Some((prefix, span)) = &sections {
+        let section_type = section_type(field_type).ok_or_else(|| {
+            syn::Error::new(
+                *span,
+                "'sections' requires a field of type `Vec<(String, T)>`",
+            )
+        })?;
+        let section_type_label = type_label(section_type);
+        return Ok(FieldCode {
+            value: quote! {
+                ::env_cfg::load_sections::<#section_type>(source, #prefix)
+                    .map_err(|e| ::env_cfg::EnvConfigError::context(
+                        ::core::convert::Into::into(e),
+                        ::env_cfg::__private::nested_context::<#section_type>(#section_type_label),
+                    ))?
+            },
+            // Only the variables of sections that are present are required
+            missing: Some(quote! {
+                missing.extend(
+                    ::env_cfg::__private::missing_section_vars::<#section_type>(source, #prefix),
+                );
+            }),
+            present: Some(quote! {
+                !::env_cfg::section_names::<#section_type>(source, #prefix).is_empty()
+            }),
+            env_keys: Vec::new(),
+            has_default: false,
+        });
+    }
+
+    // Handle lists loaded from `NAME_0`, `NAME_1`, ...
+    if indexed_list.is_some() {
+        let load_list = quote! { ::env_cfg::indexed_list_from(source, #env_name)? };
+        let value = if is_option_type(field_type) {
+            quote! {
+                {
+                    let list = #load_list;
+                    if list.is_empty() { None } else { Some(list) }
+                }
+            }
         } else {
-            return Err(syn::Error::new(
-                parser_fn.span(),
-                "parse_with must be a string literal containing the function name",
-            ));
+            load_list
         };
+        // An empty list is valid, so no variable is required
+        return Ok(FieldCode {
+            value,
+            missing: None,
+            present: Some(quote! {
+                !matches!(
+                    source.get(&::env_cfg::__private::format!("{}_0", #env_name)),
+                    Ok(None)
+                )
+            }),
+            env_keys: Vec::new(),
+            has_default: false,
+        });
+    }
 
-        return if is_option_type(field_type) {
-            Ok(quote! {
-                #field_name: ::env_cfg::env_var_optional_with_parser(#env_name, #parser_ident)?
-            })
-        } else {
-            Ok(quote! {
-                #field_name: ::env_cfg::env_var_with_parser(#env_name, #parser_ident)?
-            })
+    // Handle `OsString` and `PathBuf` fields, which keep values that are not valid Unicode. Any
+    // attribute that processes the value as a string keeps them on the `FromStr` path below
+    let loads_os_string = value_attributes.iter().all(|(name, _)| {
+        matches!(
+            *name,
+            "default" | "prefix" | "required" | "empty_as_none" | "allow_empty" | "preserve_case"
+        )
+    }) && struct_attributes.expand.is_none()
+        && env_aliases.is_empty();
+    if loads_os_string && is_os_string_type(option_inner_type(field_type).unwrap_or(field_type)) {
+        let mut steps = Vec::new();
+        if required.is_some() {
+            steps.push(quote! {
+                if value.is_none() {
+                    return Err(::env_cfg::EnvConfigError::Missing(
+                        ::env_cfg::__private::ToString::to_string(#env_name),
+                    ));
+                }
+            });
+        }
+        if empty_as_none
+            .map(|(value, _)| value)
+            .unwrap_or(struct_attributes.empty_as_none)
+        {
+            steps.push(quote! {
+                let value = value.filter(|value| !value.is_empty());
+            });
+        }
+        let value = match (&default_expr, is_option_type(field_type)) {
+            (Some((default, _)), is_option) => {
+                let loaded = if is_string_literal(default) {
+                    quote! {
+                        ::core::convert::From::from(
+                            value.unwrap_or_else(|| ::std::ffi::OsString::from(#default)),
+                        )
+                    }
+                } else {
+                    quote! {
+                        match value {
+                            Some(value) => ::core::convert::From::from(value),
+                            None => #default,
+                        }
+                    }
+                };
+                if is_option {
+                    quote! { Some(#loaded) }
+                } else {
+                    loaded
+                }
+            }
+            (None, true) => quote! { value.map(::core::convert::From::from) },
+            (None, false) => quote! {
+                ::core::convert::From::from(value.ok_or_else(|| {
+                    ::env_cfg::EnvConfigError::Missing(
+                        ::env_cfg::__private::ToString::to_string(#env_name),
+                    )
+                })?)
+            },
         };
+        let is_required =
+            required.is_some() || !(is_option_type(field_type) || default_expr.is_some());
+        return Ok(FieldCode {
+            value: quote! {
+                {
+                    let value = source.get_os(#env_name)?;
+                    #(#steps)*
+                    #value
+                }
+            },
+            missing: is_required.then(|| {
+                quote! {
+                    if !matches!(source.get_os(#env_name), Ok(Some(_))) {
+                        missing.push(::env_cfg::__private::ToString::to_string(#env_name));
+                    }
+                }
+            }),
+            present: Some(quote! { !matches!(source.get_os(#env_name), Ok(None)) }),
+            env_keys: own_keys,
+            has_default: default_expr.is_some(),
+        });
     }
 
-    // Handle default
-    if let Some(default) = default_expr {
-        return Ok(quote! {
-            #field_name: ::env_cfg::env_var_or_parse(#env_name, #default)?
+    // Variables read in order when the field's own variable is unset. Errors name all of them,
+    // since the value may have come from any
+    let fallbacks: Vec<_> = env_aliases
+        .iter()
+        .chain(aliases.iter().map(|(name, _)| name))
+        .chain(default_env.as_ref().map(|(fallback, _)| fallback))
+        .collect();
+    let name_label = if fallbacks.is_empty() {
+        quote! { #env_name }
+    } else {
+        let label_format = format!("{{}} (or {})", vec!["{}"; fallbacks.len()].join(" or "));
+        quote! { &::env_cfg::__private::format!(#label_format, #env_name, #(#fallbacks),*) }
+    };
+
+    // Errors reading a secret must not show its value
+    let redact = is_secret.then(|| quote! { .map_err(::env_cfg::__private::redact_not_unicode) });
+
+    // Load the raw value, then run it through any preprocessing steps before parsing
+    let mut steps = Vec::new();
+    // Aliases with a parser of their own record which of them the value came from
+    let alias_parsers: Vec<_> = aliases
+        .iter()
+        .enumerate()
+        .filter_map(|(index, (name, parser))| Some((index, name, parser.as_ref()?)))
+        .collect();
+    if !alias_parsers.is_empty() {
+        steps.push(quote! {
+            let alias_index: ::core::option::Option<usize> = None;
+        });
+    }
+    // The index of each fallback among `aliases`, for those with a parser
+    let alias_indices = env_aliases
+        .iter()
+        .map(|_| None)
+        .chain(
+            aliases
+                .iter()
+                .enumerate()
+                .map(|(index, (_, parser))| parser.as_ref().map(|_| index)),
+        )
+        .chain(default_env.iter().map(|_| None));
+    for (fallback, alias_index) in fallbacks.iter().zip(alias_indices) {
+        steps.push(match alias_index {
+            Some(index) => quote! {
+                let (value, alias_index) = match value {
+                    Some(value) => (Some(value), alias_index),
+                    None => match source.#get(#fallback)#redact? {
+                        Some(value) => (Some(value), Some(#index)),
+                        None => (None, None),
+                    },
+                };
+            },
+            None => quote! {
+                let value = match value {
+                    Some(value) => Some(value),
+                    None => source.#get(#fallback)#redact?,
+                };
+            },
+        });
+    }
+    if required.is_some() {
+        // Presence is checked on the raw value, so e.g. an empty value can still become `None`
+        steps.push(quote! {
+            if value.is_none() {
+                return Err(::env_cfg::EnvConfigError::Missing(
+                    ::env_cfg::__private::ToString::to_string(#name_label),
+                ));
+            }
+        });
+    }
+    if let Some(chars) = trim_matches {
+        steps.push(quote! {
+            let value = value.map(|value| {
+                ::env_cfg::__private::ToString::to_string(::env_cfg::trim_matching_pair(&value, #chars))
+            });
+        });
+    }
+    if let Some(prefix) = trim_prefix {
+        steps.push(quote! {
+            let value = value.map(|value| match value.strip_prefix(#prefix) {
+                Some(rest) => ::env_cfg::__private::ToString::to_string(rest),
+                None => value,
+            });
+        });
+    }
+    let expand_mode = expand.or(struct_attributes.expand);
+    // Values, and string defaults, are converted to this case before parsing
+    let value_case = struct_attributes
+        .value_case
+        .filter(|_| preserve_case.is_none());
+    if let Some(mode) = expand_mode {
+        let expand = mode.expand(&name_label, quote! { &value });
+        steps.push(quote! {
+            let value = match value {
+                Some(value) => Some(#expand),
+                None => None,
+            };
+        });
+    }
+    if let Some(case) = value_case {
+        let convert = case.convert();
+        steps.push(quote! {
+            let value = value.map(|value| #convert);
+        });
+    }
+    // An empty flag is `true` rather than unset
+    let empty_as_none = flag.is_none()
+        && empty_as_none
+            .map(|(value, _)| value)
+            .unwrap_or(struct_attributes.empty_as_none);
+    if empty_as_none {
+        steps.push(quote! {
+            let value = value.filter(|value| !value.is_empty());
         });
     }
 
-    // Standard field - type determines behavior (T vs Option<T>)
-    if is_option_type(field_type) {
-        Ok(quote! {
-            #field_name: ::env_cfg::env_var_optional(#env_name)?
-        })
+    // An unset flag is `false`, unless it has a default
+    if let Some(span) = flag {
+        if default_expr.is_none() && default_from.is_none() && !is_option_type(field_type) {
+            default_expr = Some((syn::parse_quote_spanned!(span=> false), span));
+        }
+    }
+
+    let value_parser = if let Some((parser_fn, _)) = parse_with {
+        // Handle fields with custom parser. The function is emitted as a (possibly relative) path
+        // spanned at the attribute, so it resolves in the scope the struct is defined in.
+        ValueParser::With(parse_function_path(&parser_fn, "parse_with")?)
+    } else if human_int.is_some() {
+        ValueParser::HumanInt
+    } else if duration_secs_f64.is_some() {
+        ValueParser::DurationSecsF64
+    } else if let Some(radix) = radix {
+        ValueParser::Radix(radix)
+    } else if flag.is_some() {
+        ValueParser::Flag
+    } else if flags.is_some() {
+        ValueParser::Flags
+    } else if try_from.is_some() {
+        ValueParser::TryFrom
+    } else if cfg!(feature = "zeroize")
+        && is_secret_type(option_inner_type(field_type).unwrap_or(field_type))
+    {
+        ValueParser::Secret
     } else {
-        Ok(quote! {
-            #field_name: ::env_cfg::env_var(#env_name)?
-        })
+        ValueParser::FromStr
+    };
+    // Expanded defaults may reference other variables, so they're only known at runtime
+    if let (
+        ValueParser::FromStr,
+        None,
+        Some((
+            syn::Expr::Lit(syn::ExprLit {
+                lit: Lit::Str(default),
+                ..
+            }),
+            _,
+        )),
+    ) = (&value_parser, &expand_mode, &default_expr)
+    {
+        validate_primitive_default(
+            option_inner_type(field_type).unwrap_or(field_type),
+            default,
+            value_case,
+        )?;
+    }
+    if let (Some((_, span)), None) = (&on_clamp, &clamp) {
+        return Err(syn::Error::new(*span, "'on_clamp' requires 'clamp'"));
+    }
+    let on_clamp = on_clamp
+        .map(|(warn_fn, _)| parse_function_path(&warn_fn, "on_clamp"))
+        .transpose()?;
+    // `validate_map` checks and possibly replaces every parsed value, including the default
+    let validate_map = validate_map
+        .map(|(validate_fn, _)| parse_function_path(&validate_fn, "validate_map"))
+        .transpose()?;
+    let transforms = transforms
+        .iter()
+        .map(|transform_fn| parse_function_path(transform_fn, "transform"))
+        .collect::<syn::Result<Vec<_>>>()?;
+    let validate_parsed = |parsed: proc_macro2::TokenStream, name: &proc_macro2::TokenStream| {
+        // Transforms run first, each on the result of the previous one
+        let parsed = transforms.iter().fold(
+            parsed,
+            |parsed, transform_fn| quote! { #transform_fn(#parsed) },
+        );
+        // Out-of-range values are then clamped, reporting the original to `on_clamp`
+        let parsed = match (&clamp, &on_clamp) {
+            (Some((min, max, _)), Some(on_clamp)) => quote! {
+                match ::env_cfg::__private::clamp(#parsed, #min, #max) {
+                    (value, Some(original)) => {
+                        #on_clamp(#name, &original, &value);
+                        value
+                    }
+                    (value, None) => value,
+                }
+            },
+            (Some((min, max, _)), None) => {
+                quote! { ::env_cfg::__private::clamp(#parsed, #min, #max).0 }
+            }
+            (None, _) => parsed,
+        };
+        match &validate_map {
+            Some(validate_fn) => quote! {
+                #validate_fn(#parsed).map_err(|e| ::env_cfg::EnvConfigError::Parse(
+                    ::env_cfg::__private::ToString::to_string(#name),
+                    e,
+                ))?
+            },
+            None => parsed,
+        }
+    };
+    let default_label = quote! { &::env_cfg::__private::format!("default for {}", #env_name) };
+    let mut parse_value = validate_parsed(value_parser.parse(&name_label), &name_label);
+    if !alias_parsers.is_empty() {
+        let alias_arms = alias_parsers.iter().map(|(index, name, parser)| {
+            let parse_alias = ValueParser::With((*parser).clone()).parse(name);
+            let parse_alias = validate_parsed(parse_alias, name);
+            quote! { Some(#index) => #parse_alias, }
+        });
+        parse_value = quote! {
+            match alias_index {
+                #(#alias_arms)*
+                _ => #parse_value,
+            }
+        };
     }
+    // A lenient field treats a value that fails to parse as unset, so it gets the default or `None`
+    if let Some((ParseErrorPolicy::Default | ParseErrorPolicy::None, _)) = on_parse_error {
+        steps.push(quote! {
+            let value = match value {
+                Some(value) => {
+                    let parse = move || -> ::core::result::Result<_, ::env_cfg::EnvConfigError> {
+                        let parsed = #parse_value;
+                        Ok(parsed)
+                    };
+                    parse().ok()
+                }
+                None => None,
+            };
+        });
+        parse_value = quote! { value };
+    }
+
+    let parse = if let Some((default, _)) = default_expr
+        .as_ref()
+        .filter(|(default, _)| !is_string_literal(default))
+    {
+        // A typed default (e.g. `LogLevel::Info` or `8080`) is used as-is
+        let parse_value = if is_option_type(field_type) {
+            quote! { Some(#parse_value) }
+        } else {
+            parse_value
+        };
+        let default = validate_parsed(quote! { #default }, &default_label);
+        let default = if is_option_type(field_type) {
+            quote! { Some(#default) }
+        } else {
+            default
+        };
+        quote! {
+            match value {
+                Some(value) => #parse_value,
+                None => #default,
+            }
+        }
+    } else if let Some((default, _)) = &default_expr {
+        // Handle default
+        let parse_default = validate_parsed(value_parser.parse(&default_label), &default_label);
+        // Defaults are expanded like values, so they can reference other variables
+        let load_default = match expand_mode {
+            Some(mode) => {
+                let expand = mode.expand(&default_label, quote! { #default });
+                quote! { let value = #expand; }
+            }
+            None => quote! { let value = ::env_cfg::__private::String::from(#default); },
+        };
+        // Defaults are converted to the value case like values, e.g. `"INFO"` to `"info"`
+        let load_default = match value_case {
+            Some(case) => {
+                let convert = case.convert();
+                quote! {
+                    #load_default
+                    let value = #convert;
+                }
+            }
+            None => load_default,
+        };
+        let (parse_value, parse_default) = if is_option_type(field_type) {
+            (
+                quote! { Some(#parse_value) },
+                quote! { Some(#parse_default) },
+            )
+        } else {
+            (parse_value, parse_default)
+        };
+        quote! {
+            match value {
+                Some(value) => #parse_value,
+                None => {
+                    #load_default
+                    #parse_default
+                }
+            }
+        }
+    } else if let Some((referenced, _)) = &default_from {
+        // Fall back to the resolved value of an earlier field
+        let referenced = field_binding(referenced);
+        let parse_value = if is_option_type(field_type) {
+            quote! { Some(#parse_value) }
+        } else {
+            parse_value
+        };
+        quote! {
+            match value {
+                Some(value) => #parse_value,
+                None => ::core::clone::Clone::clone(&#referenced),
+            }
+        }
+    } else if is_option_type(field_type) {
+        // Standard field - type determines behavior (T vs Option<T>)
+        quote! {
+            match value {
+                Some(value) => Some(#parse_value),
+                None => None,
+            }
+        }
+    } else {
+        quote! {
+            {
+                let value = ::env_cfg::require(#name_label, value)?;
+                #parse_value
+            }
+        }
+    };
+
+    // The variable is required if loading fails without it
+    let is_required = required.is_some()
+        || !(is_option_type(field_type) || default_expr.is_some() || default_from.is_some());
+    let missing = is_required.then(|| {
+        // `required` checks the raw value, so an empty value counts as set even if it becomes `None`
+        let is_set_check = |name: &proc_macro2::TokenStream| {
+            if empty_as_none && required.is_none() {
+                quote! { matches!(source.#get(#name), Ok(Some(value)) if !value.is_empty()) }
+            } else {
+                quote! { !matches!(source.#get(#name), Ok(None)) }
+            }
+        };
+        let mut is_set = is_set_check(&env_name);
+        for fallback in &fallbacks {
+            let fallback_is_set = is_set_check(fallback);
+            is_set = quote! { (#is_set || #fallback_is_set) };
+        }
+        quote! {
+            if !#is_set {
+                missing.push(::env_cfg::__private::ToString::to_string(#name_label));
+            }
+        }
+    });
+
+    Ok(FieldCode {
+        value: quote! {
+            {
+                let value = source.#get(#env_name)#redact?;
+                #(#steps)*
+                #parse
+            }
+        },
+        missing,
+        present: Some(names_present),
+        env_keys: own_keys,
+        has_default: default_expr.is_some(),
+    })
 }
@@ -1,3 +1,4 @@
+#![cfg_attr(not(feature = "std"), no_std)]
 //!
 //! ```rust
 //! use env_cfg::EnvConfig;
@@ -96,11 +97,33 @@
 //! }
 //! ```
 
-use std::str::FromStr;
+extern crate alloc;
+
+use alloc::boxed::Box;
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::str::FromStr;
+use core::time::Duration;
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+#[cfg(feature = "std")]
+use std::ffi::OsString;
 
 // Re-export the derive macro
 pub use env_cfg_derive::EnvConfig;
 
+mod report;
+#[cfg(feature = "zeroize")]
+mod secret;
+#[cfg(feature = "test-util")]
+pub mod test_util;
+
+pub use report::{LoadReport, Lookup, LookupOutcome};
+#[cfg(feature = "zeroize")]
+pub use secret::{SecretBytes, SecretString};
+
 /// Trait for loading configuration from environment variables.
 ///
 /// This trait provides an interface for loading configuration from environment variables.
@@ -137,27 +160,322 @@ pub use env_cfg_derive::EnvConfig;
 ///
 /// # Derive Macro Attributes
 ///
+/// Variable names, prefixes and the names derived from struct and field names must match
+/// `[A-Za-z_][A-Za-z0-9_]*`, or be dotted keys of such names, and be at most [`MAX_ENV_NAME_LEN`]
+/// characters long, which is checked at compile time.
+/// Two fields of a struct reading the same variable are rejected at compile time as well.
+///
 /// **Struct-level attributes:**
 /// - **`#[env_cfg(no_prefix)]`**: Don't use struct name as prefix for field names
-/// - **`#[env_cfg(prefix = "PREFIX")]`**: Use custom prefix instead of struct name
+/// - **`#[env_cfg(prefix = "PREFIX")]`**: Use custom prefix instead of struct name (or `prefix = SOME_CONST` to read it from a `&str` const). `prefix = "APP_"` behaves like `prefix = "APP"`
+/// - **`#[env_cfg(sub_prefix = "SEGMENT")]`**: Add a segment after the struct name prefix, e.g. `DATABASE_CONFIG_DB_HOST`
+/// - **`#[env_cfg(rename_all = "ACRONYM_SNAKE_CASE")]`**: Keep runs of capitals together when converting names (`OAuthConfig` -> `OAUTH_CONFIG`), instead of splitting them like the default `SCREAMING_SNAKE_CASE` (`O_AUTH_CONFIG`)
+/// - **`#[env_cfg(expand)]`**: Expand `${VAR}` references in all field values
+/// - **`#[env_cfg(empty_as_none)]`**: Treat variables set to an empty string as unset
+/// - **`#[env_cfg(rename_all_values = "lowercase")]`**: Convert every value, and string default, to `"lowercase"` or `"UPPERCASE"` before parsing (fields opt out with `preserve_case`)
+/// - **`#[env_cfg(propagate_prefix)]`**: Load every `nested` field with this struct's prefix prepended to its variables, unless the field is `independent`
+/// - **`#[env_cfg(dotted)]`**: Derive lowercase dotted keys (`app_config.port`) instead of `SCREAMING_SNAKE_CASE` names, for sources with dotted namespacing
+/// - **`#[env_cfg(profile_var = "VAR_NAME")]`**: Read every variable from `<PROFILE>_<VAR>` before `<VAR>`, where the profile is the value of `VAR_NAME`
+/// - **`#[env_cfg(global)]`**: Also generate `init`, loading a process-wide instance once, and `global`, returning it (panics before a successful `init`)
+/// - **`#[env_cfg(constructor)]`**: Also generate `new` taking a value for every field in declaration order
+/// - **`#[env_cfg(strict)]`**: Ignore declared defaults, so their variables must be set (e.g. `#[cfg_attr(not(debug_assertions), env_cfg(strict))]` for production builds)
+/// - **`#[env_cfg(group("field", "field", ...))]`**: Require the variables of these fields to be set all together or not at all, otherwise return `EnvConfigError::Invalid`. Repeatable
+/// - **`#[env_cfg(keys)]`**: Also generate an enum `<Name>Key` with a variant per field, `env_name(key)`, returning the field's variable name, and `get(&self, key)`, returning its value as a string (`None` for an unset `Option`; fields must implement `Display`)
+/// - **`#[env_cfg(redundant_vars)]`**: Also generate `redundant_vars(source)` and `redundant_env_vars()`, returning the variables set to the default of their field, or the error loading the config (fields with a `default` must implement `PartialEq`)
+/// - **`#[env_cfg(derive_from = "VAR_NAME", derive_with = "function_name")]`**: Split the value of `VAR_NAME` into the variables of several fields (takes `String`, returns `Result` of name/value pairs). Variables that are set take precedence
+/// - **`#[env_cfg(validate = "function_name")]`**: Check invariants across fields once the struct is loaded (takes `&Self`, returns `Result<(), String>`). An `Err` is returned as `EnvConfigError::Invalid`
+/// - **`#[env_cfg(json_env = "VAR_NAME")]`**: If set, deserialize the whole struct from the JSON value of `VAR_NAME` (requires the `serde` feature)
+/// - **`#[env_cfg(tag = "VAR_NAME")]`** (enums): Select the variant by the value of `VAR_NAME` (the variant name in snake_case, or `#[env_cfg(rename = "value")]` on the variant). Variants are unit variants or wrap a single `EnvConfig` type
 ///
 /// **Field-level attributes:**
 /// - **No attribute**: Field name is prefixed with struct name and converted to UPPER_SNAKE_CASE
-/// - **`#[env_cfg(env = "VAR_NAME")]`**: Use custom environment variable name
-/// - **`#[env_cfg(default = "value")]`**: Provide default value if env var not set
+/// - **`Result<T, EnvConfigError>` fields**: Load `T` as usual, but store a load error in the field instead of failing the whole config
+/// - **`OsString` / `PathBuf` fields**: Loaded with [`EnvSource::get_os`], so values that are not valid Unicode work. Attributes that process the value as a string (like `parse_with` or `expand`) use `FromStr` instead
+/// - **`SecretString` / `SecretBytes` fields** (`zeroize` feature): Take ownership of the loaded value, which is zeroized when the config is dropped and never printed by `Debug`
+/// - **`#[env_cfg(env = "VAR_NAME")]`**: Use custom environment variable name (`{prefix}` is replaced with the struct prefix, `{field}` with the field name). `env = ["VAR_NAME", "OTHER_NAME"]` tries each name in order
+/// - **`#[env_cfg(alias(env = "OLD_VAR", parse_with = "function_name"))]`**: When unset, read `OLD_VAR` instead, parsed with its own (optional) function, e.g. for a variable whose format changed
+/// - **`#[env_cfg(rename = "VAR_NAME")]`**: Same as `env`, reading as "use this exact env var name". Both are supported
+/// - **`#[env_cfg(prefix = "PREFIX")]`**: Use a different prefix for this field only, e.g. `METRICS_FIELD_NAME` (the name is still derived from the field)
+/// - **`#[env_cfg(default = "value")]`**: Provide default value if env var not set (on primitive fields, a default that doesn't parse is a compile error)
+/// - **`#[env_cfg(default = LogLevel::Info)]`**: Any other expression (a path, a number, a call, a field of a static like `DEFAULTS.port`) is used as the default value directly instead of being parsed
+/// - **`#[env_cfg(default_from = "other_field")]`**: If env var not set, use a clone of an earlier field's resolved value (the field must be `Clone` and of the same type)
+/// - **`#[env_cfg(default_env = "OTHER_VAR")]`**: If the field's variable is not set, read `OTHER_VAR` instead (then `default`, if any). Errors name both variables
 /// - **`#[env_cfg(skip)]`**: Skip this field (must implement `Default`)
-/// - **`#[env_cfg(parse_with = "function_name")]`**: Use custom parser function (takes `String`, returns `T`)
-/// - **`#[env_cfg(nested)]`**: Treat field as nested EnvConfig struct (calls `T::from_env()`)
+/// - **`#[env_cfg(skip, error_if_set)]`**: Skip this field, but fail with `EnvConfigError::Invalid` if its variable is set
+/// - **`#[env_cfg(parse_with = "function_name")]`**: Use custom parser function (takes `String`, returns `T`), which may be a relative path like `"parsers::parse_point"` or an associated function like `"Point::parse"`, or a generic function with type arguments like `"parse_csv::<u16>"`. Combined with `default`, the default string is parsed with the same function
+/// - **`#[env_cfg(sections = "UPSTREAM")]`**: Load the named sections `UPSTREAM_<NAME>_...` of the config `T` into a `Vec<(String, T)>` field, see [`load_sections`]
+/// - **`#[env_cfg(on_parse_error = "default")]`**: When a set value fails to parse, `"fail"` (the default) returns the error, `"default"` uses the declared default and `"none"` makes an `Option` field `None`
+/// - **`#[env_cfg(parse_with_env = "function_name")]`**: Load the field with a custom function that reads any variables it needs (takes `&dyn EnvSource`, returns `Result<T, EnvConfigError>`). Useful for one field assembled from several related variables
+/// - **`#[env_cfg(clamp(MIN, MAX))]`**: Clamp the parsed value into `MIN..=MAX` instead of failing, optionally calling `on_clamp = "function_name"` with the variable name, the original and the clamped value
+/// - **`#[env_cfg(transform = "function_name")]`**: Transform the parsed value (and the default) with a function taking and returning `T`. Repeated attributes run in the order they are declared, before `clamp` and `validate_map`
+/// - **`#[env_cfg(validate_map = "function_name")]`**: Check and normalize the parsed value (and the default) with a function taking `T` and returning `Result<T, String>`, where an `Err` is a `Parse` error
+/// - **`#[env_cfg(indexed_list)]`**: Load a `Vec<T>` (or `Option<Vec<T>>`, `None` when empty) from numbered variables `NAME_0`, `NAME_1`, ... up to the first unset index, parsing each entry. A gap followed by a set entry (`NAME_0` and `NAME_2` without `NAME_1`) is an error
+/// - **`#[env_cfg(nested)]`**: Treat field as nested EnvConfig struct (calls `T::from_source()` with the parent's source). The config may be wrapped in `Arc<T>`, `Rc<T>` or `Box<T>`, or be generic, like `Cache<RedisBackend>`
+/// - **`#[env_cfg(nested, defaults(DB_PORT = "5432"))]`**: Values for variables of the nested config that are not set
+/// - **`#[env_cfg(nested, independent)]`**: Always load the nested struct with its own prefix configuration
+/// - **`#[env_cfg(nested, prefix_from_field)]`**: Load the nested struct with the parent's prefix and the field name prepended (`APP_PRIMARY_DB_HOST` for `primary_db`)
+/// - **`#[env_cfg(nested, default_if_absent)]`**: Use `Default::default()` if none of the nested struct's variables are set, see [`EnvConfig::any_vars_set`]
+/// - **`#[env_cfg(nested, enabled_by = "VAR_NAME")]`**: Only load the nested struct when `VAR_NAME` is true as parsed by [`parse_flag`] (e.g. `true`, `1` or `yes`), otherwise use `Default::default()` (or `None` for `Option<T>`)
+/// - **`#[env_cfg(expand)]`**: Expand `${VAR}` references in the value (and default) before parsing (`expand = "empty"` ignores unset variables)
+/// - **`#[env_cfg(trim_matches = "\"")]`**: Strip a matching pair of the given characters from both ends of the value
+/// - **`#[env_cfg(trim_prefix = "Bearer ")]`**: Strip the given prefix from the start of the value if it is there (e.g. `Bearer xyz` becomes `xyz`), otherwise leave the value as is
+/// - **`#[env_cfg(unquote)]`**: Strip a matching pair of `"` or `'` quotes from both ends of the value
+/// - **`#[env_cfg(empty_as_none)]`** / **`#[env_cfg(allow_empty)]`**: Treat an empty value as unset, or keep it (overrides the struct attribute)
+/// - **`#[env_cfg(required)]`**: Require the variable of an `Option<T>` field to be set (with `empty_as_none`, empty is `None`)
+/// - **`#[env_cfg(preserve_case)]`**: Keep the case of the value when the struct sets `rename_all_values`
+/// - **`#[env_cfg(human_int)]`**: Allow `_` and `,` digit group separators in integer values (e.g. `1_000_000`)
+/// - **`#[env_cfg(flag)]`**: Load a `bool` that is `false` (or the `default`) when unset, `true` when set to an empty value, and parsed with [`parse_flag`] otherwise
+/// - **`#[env_cfg(flags)]`**: Parse comma-separated flag names into a type implementing [`FromFlags`], see [`parse_flags`]
+/// - **`#[env_cfg(try_from)]`**: Convert the value with `TryFrom<String>` instead of `FromStr`, see [`parse_try_from`]
+/// - **`#[env_cfg(radix_auto)]`**: Parse an integer in the radix chosen by its `0x`, `0o` or `0b` prefix, see [`parse_int_radix_auto`]
+/// - **`#[env_cfg(radix = 16)]`**: Parse an integer in the given radix, see [`parse_int_radix`]
+/// - **`#[env_cfg(duration_secs_f64)]`**: Parse a `Duration` from fractional seconds (e.g. `0.5`), see [`parse_duration_secs_f64`]
 pub trait EnvConfig: Sized {
     /// Error type returned by `from_env()`.
     type Error;
 
     /// Load configuration from environment variables.
+    #[cfg(feature = "std")]
     fn from_env() -> Result<Self, Self::Error>;
+
+    /// Load configuration from the given source of variables instead of the process environment.
+    ///
+    /// The default implementation ignores `source` and calls [`EnvConfig::from_env`].
+    /// The derive macro generates an implementation that reads every variable from `source`.
+    #[cfg(feature = "std")]
+    fn from_source(source: &dyn EnvSource) -> Result<Self, Self::Error> {
+        let _ = source;
+        Self::from_env()
+    }
+
+    /// Load configuration from the given source of variables.
+    #[cfg(not(feature = "std"))]
+    fn from_source(source: &dyn EnvSource) -> Result<Self, Self::Error>;
+
+    /// Load configuration resolving each variable with `resolver`, which returns `None` for
+    /// variables that are not set.
+    ///
+    /// ```rust
+    /// use std::collections::HashMap;
+    /// use env_cfg::EnvConfig;
+    ///
+    /// #[derive(Debug, EnvConfig)]
+    /// #[env_cfg(prefix = "DB")]
+    /// struct DatabaseConfig {
+    ///     host: String, // -> DB_HOST
+    /// }
+    ///
+    /// let vars = HashMap::from([("DB_HOST", "localhost")]);
+    /// let config = DatabaseConfig::from_env_with(|name| vars.get(name).map(|v| v.to_string()));
+    /// assert_eq!(config.unwrap().host, "localhost");
+    /// ```
+    fn from_env_with<F>(resolver: F) -> Result<Self, Self::Error>
+    where
+        F: Fn(&str) -> Option<String>,
+    {
+        Self::from_source(&FnSource(resolver))
+    }
+
+    /// Load configuration from `source`, recording every variable looked up along the way in a
+    /// [`LoadReport`], e.g. to attach the configuration a process actually saw to an incident.
+    /// The report is returned whether loading succeeds or not.
+    ///
+    /// ```rust
+    /// use std::collections::BTreeMap;
+    /// use env_cfg::{EnvConfig, LookupOutcome};
+    ///
+    /// #[derive(Debug, EnvConfig)]
+    /// #[env_cfg(prefix = "DB")]
+    /// struct DatabaseConfig {
+    ///     host: String, // -> DB_HOST
+    ///     #[env_cfg(env = ["DB_PORT", "PGPORT"], default = 5432)]
+    ///     port: u16,
+    /// }
+    ///
+    /// let vars = BTreeMap::from([("DB_HOST".to_string(), "localhost".to_string())]);
+    /// let (config, report) = DatabaseConfig::from_source_with_report(&vars);
+    /// assert_eq!(config.unwrap().port, 5432);
+    /// assert_eq!(report.to_string(), "DB_HOST=localhost\nDB_PORT (unset)\nPGPORT (unset)\n");
+    /// ```
+    fn from_source_with_report(source: &dyn EnvSource) -> (Result<Self, Self::Error>, LoadReport) {
+        let recording = report::Recording {
+            source,
+            report: core::cell::RefCell::new(LoadReport::default()),
+        };
+        let result = Self::from_source(&recording);
+        (result, recording.report.into_inner())
+    }
+
+    /// Load configuration from the environment, recording every variable looked up, see
+    /// [`EnvConfig::from_source_with_report`].
+    #[cfg(feature = "std")]
+    fn from_env_with_report() -> (Result<Self, Self::Error>, LoadReport) {
+        Self::from_source_with_report(&SystemEnv)
+    }
+
+    /// Load configuration from the environment together with the variables it read, for
+    /// snapshot tests. As long as every variable read holds valid Unicode and none holds a
+    /// secret, loading the config again from the returned map (`from_source(&map)`) gives the
+    /// same config, so the map can be committed as a golden file and replayed without touching
+    /// the environment.
+    ///
+    /// Only variables that are set are captured, with the same values as
+    /// [`LoadReport::values`]. So the map does not replay exactly:
+    /// - The values of `SecretString` and `SecretBytes` fields are left out, so replaying fails
+    ///   with [`EnvConfigError::Missing`] for a required secret until it is added to the map.
+    /// - `OsString` and `PathBuf` values that are not valid Unicode are converted lossily, with
+    ///   invalid sequences replaced by `U+FFFD`, so the replayed value differs.
+    ///
+    /// ```rust
+    /// use env_cfg::EnvConfig;
+    ///
+    /// #[derive(Debug, PartialEq, EnvConfig)]
+    /// #[env_cfg(prefix = "DB")]
+    /// struct DatabaseConfig {
+    ///     #[env_cfg(default = "localhost")]
+    ///     host: String, // -> DB_HOST
+    /// }
+    ///
+    /// let (config, vars) = DatabaseConfig::capture().unwrap();
+    /// assert_eq!(DatabaseConfig::from_source(&vars).unwrap(), config);
+    /// ```
+    #[cfg(feature = "std")]
+    fn capture() -> Result<(Self, HashMap<String, String>), Self::Error> {
+        let (result, report) = Self::from_env_with_report();
+        let vars = report
+            .values()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect();
+        Ok((result?, vars))
+    }
+
+    /// Names of the required variables that are not set in `source`, including those of nested
+    /// configs. Nothing is parsed, so a `Vec` without missing variables does not guarantee that
+    /// loading succeeds.
+    ///
+    /// The default implementation returns an empty `Vec`. The derive macro generates an
+    /// implementation reporting every field without a default that is not optional or skipped.
+    fn missing_vars(source: &dyn EnvSource) -> Vec<String> {
+        let _ = source;
+        Vec::new()
+    }
+
+    /// Whether any variable this config reads is set in `source`, including those of nested
+    /// configs. Used by `#[env_cfg(nested, default_if_absent)]` fields.
+    ///
+    /// The default implementation returns `true`. The derive macro generates an implementation
+    /// checking the variables of every field that is not skipped (fallbacks like `default_env`
+    /// and the variables read by `parse_with_env` functions are not checked).
+    fn any_vars_set(source: &dyn EnvSource) -> bool {
+        let _ = source;
+        true
+    }
+
+    /// Names of the required environment variables that are currently unset, e.g. for a
+    /// `--check-config` preflight. See [`EnvConfig::missing_vars`].
+    #[cfg(feature = "std")]
+    fn missing_env_vars() -> Vec<String> {
+        Self::missing_vars(&SystemEnv)
+    }
+}
+
+/// A source of environment-like variables to load configuration from.
+///
+/// Implemented for the process environment (`SystemEnv`), `HashMap<String, String>` and
+/// `BTreeMap<String, String>`. Without the `std` feature, only `BTreeMap` and your own
+/// implementations are available.
+pub trait EnvSource {
+    /// Look up the value of the variable `name`.
+    /// Returns `None` if the variable is not set.
+    fn get(&self, name: &str) -> Result<Option<String>, EnvConfigError>;
+
+    /// Look up the value of the variable `name` for a field holding a secret, like a
+    /// `SecretString`. Sources that record or log their lookups override it to leave out the
+    /// value.
+    ///
+    /// The default implementation calls [`EnvSource::get`].
+    fn get_secret(&self, name: &str) -> Result<Option<String>, EnvConfigError> {
+        self.get(name)
+    }
+
+    /// Look up the value of the variable `name` without requiring it to be valid Unicode.
+    /// Used for `OsString` and `PathBuf` fields.
+    ///
+    /// The default implementation converts the value returned by [`EnvSource::get`].
+    #[cfg(feature = "std")]
+    fn get_os(&self, name: &str) -> Result<Option<OsString>, EnvConfigError> {
+        Ok(self.get(name)?.map(OsString::from))
+    }
+
+    /// Names of all variables that are set, used to discover sections (see [`load_sections`]).
+    ///
+    /// The default implementation returns an empty `Vec`, for sources that can't list their
+    /// variables.
+    fn names(&self) -> Vec<String> {
+        Vec::new()
+    }
+}
+
+/// The process environment, read with [`std::env::var`].
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemEnv;
+
+#[cfg(feature = "std")]
+impl EnvSource for SystemEnv {
+    fn get(&self, name: &str) -> Result<Option<String>, EnvConfigError> {
+        match std::env::var(name) {
+            Ok(value) => Ok(Some(value)),
+            Err(std::env::VarError::NotPresent) => Ok(None),
+            Err(std::env::VarError::NotUnicode(value)) => Err(not_unicode(name, &value)),
+        }
+    }
+
+    fn get_os(&self, name: &str) -> Result<Option<OsString>, EnvConfigError> {
+        Ok(std::env::var_os(name))
+    }
+
+    fn names(&self) -> Vec<String> {
+        // Variables with a name that is not valid Unicode can't be looked up with `get`
+        std::env::vars_os()
+            .filter_map(|(name, _)| name.into_string().ok())
+            .collect()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<S: std::hash::BuildHasher> EnvSource for HashMap<String, String, S> {
+    fn get(&self, name: &str) -> Result<Option<String>, EnvConfigError> {
+        Ok(HashMap::get(self, name).cloned())
+    }
+
+    fn names(&self) -> Vec<String> {
+        self.keys().cloned().collect()
+    }
+}
+
+impl EnvSource for BTreeMap<String, String> {
+    fn get(&self, name: &str) -> Result<Option<String>, EnvConfigError> {
+        Ok(BTreeMap::get(self, name).cloned())
+    }
+
+    fn names(&self) -> Vec<String> {
+        self.keys().cloned().collect()
+    }
+}
+
+/// A source resolving each variable with a closure, see [`EnvConfig::from_env_with`].
+#[derive(Debug, Clone, Copy)]
+pub struct FnSource<F>(pub F);
+
+impl<F: Fn(&str) -> Option<String>> EnvSource for FnSource<F> {
+    fn get(&self, name: &str) -> Result<Option<String>, EnvConfigError> {
+        Ok((self.0)(name))
+    }
 }
 
 /// Error type for environment configuration loading.
-#[derive(Debug, thiserror::Error)]
+#[derive(Debug, PartialEq, Eq, thiserror::Error)]
 pub enum EnvConfigError {
     /// Environment variable is missing.
     #[error("Missing environment variable: `{0}`")]
@@ -165,15 +483,124 @@ pub enum EnvConfigError {
     /// Failed to parse environment variable value.
     #[error("Failed to parse environment variable: '{0}': {1}")]
     Parse(String, String),
+    /// The loaded configuration failed validation, or a variable that must not be set is set.
+    #[error("Invalid configuration '{0}': {1}")]
+    Invalid(String, String),
+    /// An error with added context, see [`EnvConfigError::context`].
+    #[error("{context}: {source}")]
+    Nested {
+        /// Description of what was being loaded when the error occurred.
+        context: String,
+        /// The underlying error.
+        source: Box<EnvConfigError>,
+    },
+}
+
+impl EnvConfigError {
+    /// Wrap this error with a description of what was being loaded.
+    ///
+    /// ```rust
+    /// use env_cfg::EnvConfigError;
+    ///
+    /// let error = EnvConfigError::Missing("DB_HOST".to_string())
+    ///     .context("while loading database settings");
+    /// assert_eq!(
+    ///     error.to_string(),
+    ///     "while loading database settings: Missing environment variable: `DB_HOST`"
+    /// );
+    /// ```
+    pub fn context(self, context: impl Into<String>) -> Self {
+        EnvConfigError::Nested {
+            context: context.into(),
+            source: Box::new(self),
+        }
+    }
+
+    /// The error, with any context added by [`EnvConfigError::context`] removed.
+    pub fn root_cause(&self) -> &EnvConfigError {
+        match self {
+            EnvConfigError::Nested { source, .. } => source.root_cause(),
+            other => other,
+        }
+    }
+
+    /// Whether a variable is missing, looking through any added context.
+    pub fn is_missing(&self) -> bool {
+        matches!(self.root_cause(), EnvConfigError::Missing(_))
+    }
+
+    /// Whether a value failed to parse, looking through any added context.
+    pub fn is_parse(&self) -> bool {
+        matches!(self.root_cause(), EnvConfigError::Parse(..))
+    }
+
+    /// Whether the loaded configuration is invalid, looking through any added context.
+    pub fn is_invalid(&self) -> bool {
+        matches!(self.root_cause(), EnvConfigError::Invalid(..))
+    }
+
+    /// The name of the variable that is missing or failed to parse, looking through any added
+    /// context. `None` for [`EnvConfigError::Invalid`], which usually names a struct.
+    ///
+    /// ```rust
+    /// use env_cfg::EnvConfigError;
+    ///
+    /// let error = EnvConfigError::Missing("DB_HOST".to_string()).context("database");
+    /// assert!(error.is_missing());
+    /// assert_eq!(error.var_name(), Some("DB_HOST"));
+    /// ```
+    pub fn var_name(&self) -> Option<&str> {
+        match self.root_cause() {
+            EnvConfigError::Missing(name) | EnvConfigError::Parse(name, _) => Some(name),
+            _ => None,
+        }
+    }
+}
+
+/// Extension trait adding [`EnvConfigError::context`] to results.
+pub trait ResultExt<T> {
+    /// Wrap the error of this result with a description of what was being loaded.
+    fn context(self, context: impl Into<String>) -> Result<T, EnvConfigError>;
+}
+
+impl<T> ResultExt<T> for Result<T, EnvConfigError> {
+    fn context(self, context: impl Into<String>) -> Result<T, EnvConfigError> {
+        self.map_err(|e| e.context(context))
+    }
+}
+
+/// The longest variable name that can be relied on across platforms. Derived configs reject longer
+/// names at compile time, and report variables of nested configs whose propagated prefix makes
+/// their name longer as [`EnvConfigError::Invalid`] rather than missing. [`EnvConfig::missing_vars`]
+/// still lists such a variable like any other that is unset, since it only checks presence.
+pub const MAX_ENV_NAME_LEN: usize = 255;
+
+// Helper functions for implementing the trait. These read the process environment and require
+// the `std` feature.
+//
+/// Start of the message of a [`EnvConfigError::Parse`] for a value that is not valid Unicode
+const INVALID_UNICODE: &str = "Invalid Unicode";
+
+/// Error for a variable whose value is not valid Unicode, showing the value with invalid
+/// sequences replaced so operators can tell what was set
+#[cfg(feature = "std")]
+fn not_unicode(name: &str, value: &std::ffi::OsStr) -> EnvConfigError {
+    EnvConfigError::Parse(
+        name.to_string(),
+        format!("{INVALID_UNICODE}: \"{}\"", value.to_string_lossy()),
+    )
 }
 
-// Helper functions for implementing the trait
 /// Load a required environment variable and parse it to the target type.
 /// Fails if the variable is not set or cannot be parsed.
+///
+/// A value that is not valid Unicode is reported as [`EnvConfigError::Missing`], unlike by the
+/// other helpers, which report it as [`EnvConfigError::Parse`].
+#[cfg(feature = "std")]
 pub fn env_var<T>(name: &str) -> Result<T, EnvConfigError>
 where
     T: FromStr,
-    T::Err: std::fmt::Display,
+    T::Err: core::fmt::Display,
 {
     let value = std::env::var(name).map_err(|_| EnvConfigError::Missing(name.to_string()))?;
     value
@@ -183,10 +610,11 @@ where
 
 /// Load an optional environment variable and parse it to the target type.
 /// Returns `None` if the variable is not set.
+#[cfg(feature = "std")]
 pub fn env_var_optional<T>(name: &str) -> Result<Option<T>, EnvConfigError>
 where
     T: FromStr,
-    T::Err: std::fmt::Display,
+    T::Err: core::fmt::Display,
 {
     match std::env::var(name) {
         Ok(value) => value
@@ -194,18 +622,29 @@ where
             .map(Some)
             .map_err(|e| EnvConfigError::Parse(name.to_string(), e.to_string())),
         Err(std::env::VarError::NotPresent) => Ok(None),
-        Err(std::env::VarError::NotUnicode(_)) => Err(EnvConfigError::Parse(
-            name.to_string(),
-            "Invalid Unicode".to_string(),
-        )),
+        Err(std::env::VarError::NotUnicode(value)) => Err(not_unicode(name, &value)),
     }
 }
 
 /// Load an environment variable with a default value if not present.
+///
+/// Equivalent to `env_var_optional(name)?.unwrap_or(default)`.
+///
+/// The defaulting helpers form a matrix of the result type and the kind of default, a value or a
+/// string parsed like the variable would be:
+///
+/// |             | value default           | string default                 |
+/// |-------------|-------------------------|--------------------------------|
+/// | `T`         | [`env_var_or`]          | [`env_var_or_parse`]           |
+/// | `Option<T>` | [`env_var_optional_or`] | [`env_var_optional_or_parse`]  |
+///
+/// A value that is not valid Unicode is reported as [`EnvConfigError::Parse`], like by every
+/// helper except [`env_var`] and [`env_var_with_parser`].
+#[cfg(feature = "std")]
 pub fn env_var_or<T>(name: &str, default: T) -> Result<T, EnvConfigError>
 where
     T: FromStr,
-    T::Err: std::fmt::Display,
+    T::Err: core::fmt::Display,
 {
     match env_var_optional(name)? {
         Some(value) => Ok(value),
@@ -214,10 +653,11 @@ where
 }
 
 /// Load an environment variable with a string default that gets parsed if env var not present.
+#[cfg(feature = "std")]
 pub fn env_var_or_parse<T>(name: &str, default: &str) -> Result<T, EnvConfigError>
 where
     T: FromStr,
-    T::Err: std::fmt::Display,
+    T::Err: core::fmt::Display,
 {
     match std::env::var(name) {
         Ok(value) => value
@@ -226,16 +666,65 @@ where
         Err(std::env::VarError::NotPresent) => default
             .parse::<T>()
             .map_err(|e| EnvConfigError::Parse(format!("default for {}", name), e.to_string())),
-        Err(std::env::VarError::NotUnicode(_)) => Err(EnvConfigError::Parse(
-            name.to_string(),
-            "Invalid Unicode".to_string(),
-        )),
+        Err(std::env::VarError::NotUnicode(value)) => Err(not_unicode(name, &value)),
+    }
+}
+
+/// Load an optional environment variable with a default if not present.
+///
+/// Unlike [`env_var_or`], the result stays optional, so the default may itself be `None`. See
+/// [`env_var_or`] for the other defaulting helpers.
+#[cfg(feature = "std")]
+pub fn env_var_optional_or<T>(name: &str, default: Option<T>) -> Result<Option<T>, EnvConfigError>
+where
+    T: FromStr,
+    T::Err: core::fmt::Display,
+{
+    Ok(env_var_optional(name)?.or(default))
+}
+
+/// Load an optional environment variable with a string default that gets parsed if env var not
+/// present.
+///
+/// Unlike [`env_var_or_parse`], the result stays optional, so the default may itself be `None`.
+#[cfg(feature = "std")]
+pub fn env_var_optional_or_parse<T>(
+    name: &str,
+    default: Option<&str>,
+) -> Result<Option<T>, EnvConfigError>
+where
+    T: FromStr,
+    T::Err: core::fmt::Display,
+{
+    match default {
+        Some(default) => env_var_or_parse(name, default).map(Some),
+        None => env_var_optional(name),
+    }
+}
+
+/// Load an environment variable, falling back to another variable if it is not set.
+///
+/// The `Missing` error names both variables, e.g. `READ_DATABASE_URL (or DATABASE_URL)`.
+#[cfg(feature = "std")]
+pub fn env_var_or_env<T>(name: &str, fallback: &str) -> Result<T, EnvConfigError>
+where
+    T: FromStr,
+    T::Err: core::fmt::Display,
+{
+    match env_var_optional(name)? {
+        Some(value) => Ok(value),
+        None => env_var_optional(fallback)?
+            .ok_or_else(|| EnvConfigError::Missing(format!("{name} (or {fallback})"))),
     }
 }
 
 /// Load a required environment variable and parse it using a custom parser function.
 /// The parser function should take a String and return the target type T.
 /// Any panics or errors from the parser function will bubble up naturally.
+///
+/// A value that is not valid Unicode is reported as [`EnvConfigError::Missing`], like by
+/// [`env_var`].
+#[cfg(feature = "std")]
 pub fn env_var_with_parser<T, F>(name: &str, parser: F) -> Result<T, EnvConfigError>
 where
     F: FnOnce(String) -> T,
@@ -248,6 +737,7 @@ where
 /// Returns None if the variable is not set.
 /// The parser function should take a String and return the target type T.
 /// Any panics or errors from the parser function will bubble up naturally.
+#[cfg(feature = "std")]
 pub fn env_var_optional_with_parser<T, F>(
     name: &str,
     parser: F,
@@ -258,9 +748,992 @@ where
     match std::env::var(name) {
         Ok(value) => Ok(Some(parser(value))),
         Err(std::env::VarError::NotPresent) => Ok(None),
-        Err(std::env::VarError::NotUnicode(_)) => Err(EnvConfigError::Parse(
-            name.to_string(),
-            "Invalid Unicode".to_string(),
+        Err(std::env::VarError::NotUnicode(value)) => Err(not_unicode(name, &value)),
+    }
+}
+
+/// Load a required environment variable and parse it using a fallible custom parser function.
+/// The parser function should take a String and return `Result<T, E>`; an `Err` is returned as
+/// [`EnvConfigError::Parse`] with the error message.
+#[cfg(feature = "std")]
+pub fn env_var_try_with_parser<T, E, F>(name: &str, parser: F) -> Result<T, EnvConfigError>
+where
+    F: FnOnce(String) -> Result<T, E>,
+    E: core::fmt::Display,
+{
+    env_var_optional_try_with_parser(name, parser)?
+        .ok_or_else(|| EnvConfigError::Missing(name.to_string()))
+}
+
+/// Load an optional environment variable and parse it using a fallible custom parser function.
+/// Returns None if the variable is not set.
+/// The parser function should take a String and return `Result<T, E>`; an `Err` is returned as
+/// [`EnvConfigError::Parse`] with the error message.
+#[cfg(feature = "std")]
+pub fn env_var_optional_try_with_parser<T, E, F>(
+    name: &str,
+    parser: F,
+) -> Result<Option<T>, EnvConfigError>
+where
+    F: FnOnce(String) -> Result<T, E>,
+    E: core::fmt::Display,
+{
+    match std::env::var(name) {
+        Ok(value) => parser(value)
+            .map(Some)
+            .map_err(|e| EnvConfigError::Parse(name.to_string(), e.to_string())),
+        Err(std::env::VarError::NotPresent) => Ok(None),
+        Err(std::env::VarError::NotUnicode(value)) => Err(not_unicode(name, &value)),
+    }
+}
+
+/// Load a required environment variable and convert it with `T::try_from`, for types that
+/// implement `TryFrom<String>` but not `FromStr`. A conversion error is returned as
+/// [`EnvConfigError::Parse`] with the error message.
+#[cfg(feature = "std")]
+pub fn env_var_try_from<T>(name: &str) -> Result<T, EnvConfigError>
+where
+    T: TryFrom<String>,
+    T::Error: core::fmt::Display,
+{
+    env_var_try_with_parser(name, T::try_from)
+}
+
+/// Load an optional environment variable and convert it with `T::try_from`.
+/// Returns None if the variable is not set.
+#[cfg(feature = "std")]
+pub fn env_var_optional_try_from<T>(name: &str) -> Result<Option<T>, EnvConfigError>
+where
+    T: TryFrom<String>,
+    T::Error: core::fmt::Display,
+{
+    env_var_optional_try_with_parser(name, T::try_from)
+}
+
+/// Load an environment variable and parse it using a custom parser function, or return
+/// `default` if the variable is not set.
+/// The parser function should take a String and return the target type T.
+#[cfg(feature = "std")]
+pub fn env_var_parse_with_default<T, F>(
+    name: &str,
+    parser: F,
+    default: T,
+) -> Result<T, EnvConfigError>
+where
+    F: FnOnce(String) -> T,
+{
+    Ok(env_var_optional_with_parser(name, parser)?.unwrap_or(default))
+}
+
+/// Load the environment variables `NAME_0`, `NAME_1`, ... up to the first index that is not set,
+/// parsing each to the target type. See [`indexed_list_from`].
+#[cfg(feature = "std")]
+pub fn env_var_indexed_list<T>(name: &str) -> Result<Vec<T>, EnvConfigError>
+where
+    T: FromStr,
+    T::Err: core::fmt::Display,
+{
+    indexed_list_from(&SystemEnv, name)
+}
+
+/// Load a required environment variable as an [`OsString`], which may hold values that are not
+/// valid Unicode (such as some paths).
+#[cfg(feature = "std")]
+pub fn env_var_os(name: &str) -> Result<OsString, EnvConfigError> {
+    std::env::var_os(name).ok_or_else(|| EnvConfigError::Missing(name.to_string()))
+}
+
+/// Load an optional environment variable as an [`OsString`].
+/// Returns `None` if the variable is not set.
+#[cfg(feature = "std")]
+pub fn env_var_os_optional(name: &str) -> Result<Option<OsString>, EnvConfigError> {
+    Ok(std::env::var_os(name))
+}
+
+// Building blocks used by the derive macro. Generated code loads the raw value of a variable,
+// runs it through any preprocessing steps (such as `expand`), and then parses it.
+
+#[doc(hidden)]
+pub mod __private {
+    // Paths used by generated code, which must also resolve in `no_std` crates
+    pub use alloc::string::{String, ToString};
+    pub use alloc::vec::Vec;
+    pub use alloc::{format, vec};
+
+    #[cfg(feature = "std")]
+    use super::OsString;
+    use super::{BTreeMap, Box, EnvConfig, EnvConfigError, EnvSource};
+
+    /// Remove the value shown in the error for a value that is not valid Unicode, for fields
+    /// holding secrets
+    pub fn redact_not_unicode(error: EnvConfigError) -> EnvConfigError {
+        match error {
+            EnvConfigError::Parse(name, message) if message.starts_with(super::INVALID_UNICODE) => {
+                EnvConfigError::Parse(name, format!("{} (value redacted)", super::INVALID_UNICODE))
+            }
+            other => other,
+        }
+    }
+
+    /// `value` limited to `min..=max`, and the original value if it was out of range
+    pub fn clamp<T: PartialOrd>(value: T, min: T, max: T) -> (T, Option<T>) {
+        if value < min {
+            (min, Some(value))
+        } else if value > max {
+            (max, Some(value))
+        } else {
+            (value, None)
+        }
+    }
+
+    /// Context of an error loading a nested config, naming its type as written and, if that is
+    /// an alias or a relative path, the type it resolved to
+    pub fn nested_context<T: ?Sized>(written: &str) -> String {
+        let resolved = core::any::type_name::<T>();
+        if resolved.ends_with(written) || without_paths(resolved) == without_paths(written) {
+            format!("nested {written}")
+        } else {
+            format!("nested {written} ({resolved})")
+        }
+    }
+
+    /// A type name with the module path of every type removed, e.g. `Cache<RedisBackend>` for
+    /// `app::Cache<app::backends::RedisBackend>`
+    fn without_paths(type_name: &str) -> String {
+        let mut short = String::new();
+        for c in type_name.chars() {
+            short.push(c);
+            if short.ends_with("::") {
+                short.truncate(short.len() - 2);
+                let segment_start = short
+                    .rfind(|c: char| !(c.is_alphanumeric() || c == '_'))
+                    .map_or(0, |i| i + 1);
+                short.truncate(segment_start);
+            }
+        }
+        short
+    }
+
+    /// Variables returned by a `derive_with` function, keyed by variable name
+    pub fn derived_vars<I, K, V, E>(
+        name: &str,
+        split: Result<I, E>,
+    ) -> Result<BTreeMap<String, String>, EnvConfigError>
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<String>,
+        V: Into<String>,
+        E: core::fmt::Display,
+    {
+        match split {
+            Ok(vars) => Ok(vars
+                .into_iter()
+                .map(|(name, value)| (name.into(), value.into()))
+                .collect()),
+            Err(e) => Err(EnvConfigError::Parse(name.to_string(), e.to_string())),
+        }
+    }
+
+    /// Check that the variables of a `group` of fields are set all together or not at all.
+    /// `members` are the names of the fields' variables and whether they are set
+    pub fn check_group(config: &str, members: &[(&str, bool)]) -> Result<(), EnvConfigError> {
+        let (set, unset): (Vec<_>, Vec<_>) = members.iter().partition(|(_, is_set)| *is_set);
+        if set.is_empty() || unset.is_empty() {
+            return Ok(());
+        }
+        let names = |members: Vec<&(&str, bool)>| {
+            members
+                .iter()
+                .map(|(name, _)| *name)
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        Err(EnvConfigError::Invalid(
+            config.to_string(),
+            format!(
+                "{} set, but not {}; set all of the group or none",
+                names(set),
+                names(unset)
+            ),
+        ))
+    }
+
+    /// Defaults given to a `nested` config by its parent, keyed by variable name
+    pub fn defaults(vars: &[(&str, &str)]) -> BTreeMap<String, String> {
+        vars.iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect()
+    }
+
+    /// A source reading `fallback` for variables that are not set in `source`
+    pub struct Layered<'a> {
+        pub source: &'a dyn EnvSource,
+        pub fallback: &'a BTreeMap<String, String>,
+    }
+
+    impl EnvSource for Layered<'_> {
+        fn get(&self, name: &str) -> Result<Option<String>, EnvConfigError> {
+            match self.source.get(name)? {
+                Some(value) => Ok(Some(value)),
+                None => Ok(self.fallback.get(name).cloned()),
+            }
+        }
+
+        fn get_secret(&self, name: &str) -> Result<Option<String>, EnvConfigError> {
+            match self.source.get_secret(name)? {
+                Some(value) => Ok(Some(value)),
+                None => Ok(self.fallback.get(name).cloned()),
+            }
+        }
+
+        #[cfg(feature = "std")]
+        fn get_os(&self, name: &str) -> Result<Option<OsString>, EnvConfigError> {
+            match self.source.get_os(name)? {
+                Some(value) => Ok(Some(value)),
+                None => Ok(self.fallback.get(name).cloned().map(OsString::from)),
+            }
+        }
+
+        fn names(&self) -> Vec<String> {
+            let mut names = self.source.names();
+            names.extend(self.fallback.keys().cloned());
+            names
+        }
+    }
+
+    /// Expand `${VAR}` references in the value of the variable `name`, for `expand` fields.
+    /// Errors about the value itself name the variable rather than showing the value
+    pub fn expand_var(
+        source: &dyn EnvSource,
+        name: &str,
+        value: &str,
+        missing_as_empty: bool,
+    ) -> Result<String, EnvConfigError> {
+        super::expand_vars_inner(source, Some(name), value, missing_as_empty, 0)
+    }
+
+    /// A source treating the variables `names` as unset, used to load the default of a field
+    /// for `redundant_vars`
+    pub struct Unset<'a> {
+        pub source: &'a dyn EnvSource,
+        pub names: &'a [&'a str],
+    }
+
+    impl EnvSource for Unset<'_> {
+        fn get(&self, name: &str) -> Result<Option<String>, EnvConfigError> {
+            if self.names.contains(&name) {
+                return Ok(None);
+            }
+            self.source.get(name)
+        }
+
+        fn get_secret(&self, name: &str) -> Result<Option<String>, EnvConfigError> {
+            if self.names.contains(&name) {
+                return Ok(None);
+            }
+            self.source.get_secret(name)
+        }
+
+        #[cfg(feature = "std")]
+        fn get_os(&self, name: &str) -> Result<Option<OsString>, EnvConfigError> {
+            if self.names.contains(&name) {
+                return Ok(None);
+            }
+            self.source.get_os(name)
+        }
+
+        fn names(&self) -> Vec<String> {
+            let mut names = self.source.names();
+            names.retain(|name| !self.names.contains(&name.as_str()));
+            names
+        }
+    }
+
+    /// A source reading every variable with `prefix` prepended, for nested configs of a struct
+    /// with `propagate_prefix`
+    pub struct Prefixed<'a> {
+        pub source: &'a dyn EnvSource,
+        pub prefix: &'a str,
+        /// Join the prefix with `.` and keep it lowercase, for structs with `dotted`
+        pub dotted: bool,
+    }
+
+    impl Prefixed<'_> {
+        /// The variable read for `name`
+        pub fn name(&self, name: &str) -> String {
+            if self.dotted {
+                let prefix = self.prefix.trim_end_matches('.').to_ascii_lowercase();
+                return format!("{prefix}.{}", name.trim_start_matches('.'));
+            }
+            let prefix = self.prefix.trim_end_matches('_').to_ascii_uppercase();
+            let name = name.trim_start_matches('_');
+            format!("{prefix}_{name}")
+        }
+
+        /// Context of an error loading the nested config `T`, like [`nested_context`] but also
+        /// naming the prefix, e.g. `nested DatabaseConfig (prefix=TENANT_A)`
+        pub fn nested_context<T: ?Sized>(&self, written: &str) -> String {
+            let prefix = self.name("");
+            let prefix = prefix.trim_end_matches(['_', '.']);
+            format!("{} (prefix={prefix})", nested_context::<T>(written))
+        }
+
+        /// A label naming variables, as in errors and `missing_vars`, with every variable it names
+        /// prefixed, e.g. `APP_HOST (or APP_SERVER)` for `HOST (or SERVER)` or `default for
+        /// APP_PORT` for `default for PORT`
+        pub fn label(&self, label: &str) -> String {
+            if let Some(name) = label.strip_prefix("default for ") {
+                return format!("default for {}", self.label(name));
+            }
+            let fallbacks = label
+                .strip_suffix(')')
+                .and_then(|label| label.split_once(" (or "));
+            match fallbacks {
+                Some((name, fallbacks)) => {
+                    let fallbacks: Vec<_> = fallbacks
+                        .split(" or ")
+                        .map(|name| self.name(name))
+                        .collect();
+                    format!("{} (or {})", self.name(name), fallbacks.join(" or "))
+                }
+                None => self.name(label),
+            }
+        }
+
+        /// Name the variables that were actually read in an error of the nested config
+        pub fn error(&self, error: EnvConfigError) -> EnvConfigError {
+            match error {
+                // A name too long to be set is reported as such, rather than as missing
+                EnvConfigError::Missing(name)
+                    if !name.contains(' ') && self.name(&name).len() > super::MAX_ENV_NAME_LEN =>
+                {
+                    let name = self.name(&name);
+                    let message = format!(
+                        "the name is {} characters long, but names longer than {} characters can't be set on every platform",
+                        name.len(),
+                        super::MAX_ENV_NAME_LEN
+                    );
+                    EnvConfigError::Invalid(name, message)
+                }
+                EnvConfigError::Missing(name) => EnvConfigError::Missing(self.label(&name)),
+                // Errors of configs nested further already name their own context
+                EnvConfigError::Parse(name, message) if !name.starts_with("nested ") => {
+                    EnvConfigError::Parse(self.label(&name), message)
+                }
+                EnvConfigError::Nested { context, source } => EnvConfigError::Nested {
+                    context,
+                    source: Box::new(self.error(*source)),
+                },
+                other => other,
+            }
+        }
+    }
+
+    impl EnvSource for Prefixed<'_> {
+        fn get(&self, name: &str) -> Result<Option<String>, EnvConfigError> {
+            self.source.get(&self.name(name))
+        }
+
+        fn get_secret(&self, name: &str) -> Result<Option<String>, EnvConfigError> {
+            self.source.get_secret(&self.name(name))
+        }
+
+        #[cfg(feature = "std")]
+        fn get_os(&self, name: &str) -> Result<Option<OsString>, EnvConfigError> {
+            self.source.get_os(&self.name(name))
+        }
+
+        fn names(&self) -> Vec<String> {
+            let prefix = self.name("");
+            self.source
+                .names()
+                .into_iter()
+                .filter_map(|name| Some(name.strip_prefix(&prefix)?.to_string()))
+                .collect()
+        }
+    }
+
+    /// A source reading `<PROFILE>_<NAME>` before `NAME` while a profile is active, for structs
+    /// with `profile_var`
+    pub struct Profiled<'a> {
+        pub source: &'a dyn EnvSource,
+        /// The active profile, e.g. `prod`. No profile is active if it is unset or empty
+        pub profile: Option<String>,
+        /// Join the profile with `.` and keep it lowercase, for structs with `dotted`
+        pub dotted: bool,
+    }
+
+    impl Profiled<'_> {
+        /// The variable overriding `name` in the active profile
+        fn override_name(&self, name: &str) -> Option<String> {
+            let profile = self
+                .profile
+                .as_deref()
+                .filter(|profile| !profile.is_empty())?;
+            let prefixed = Prefixed {
+                source: self.source,
+                prefix: profile,
+                dotted: self.dotted,
+            };
+            Some(prefixed.name(name))
+        }
+    }
+
+    impl EnvSource for Profiled<'_> {
+        fn get(&self, name: &str) -> Result<Option<String>, EnvConfigError> {
+            if let Some(override_name) = self.override_name(name)
+                && let Some(value) = self.source.get(&override_name)?
+            {
+                return Ok(Some(value));
+            }
+            self.source.get(name)
+        }
+
+        fn get_secret(&self, name: &str) -> Result<Option<String>, EnvConfigError> {
+            if let Some(override_name) = self.override_name(name)
+                && let Some(value) = self.source.get_secret(&override_name)?
+            {
+                return Ok(Some(value));
+            }
+            self.source.get_secret(name)
+        }
+
+        #[cfg(feature = "std")]
+        fn get_os(&self, name: &str) -> Result<Option<OsString>, EnvConfigError> {
+            if let Some(override_name) = self.override_name(name)
+                && let Some(value) = self.source.get_os(&override_name)?
+            {
+                return Ok(Some(value));
+            }
+            self.source.get_os(name)
+        }
+
+        fn names(&self) -> Vec<String> {
+            self.source.names()
+        }
+    }
+
+    /// Variables required by the sections of `T` found in `source`, see [`crate::load_sections`]
+    pub fn missing_section_vars<T: EnvConfig>(source: &dyn EnvSource, prefix: &str) -> Vec<String> {
+        let prefix = prefix.trim_end_matches('_');
+        super::section_names::<T>(source, prefix)
+            .into_iter()
+            .flat_map(|name| {
+                let section_prefix = format!("{prefix}_{name}");
+                let section = Prefixed {
+                    source,
+                    prefix: &section_prefix,
+                    dotted: false,
+                };
+                T::missing_vars(&section)
+                    .iter()
+                    .map(|name| section.label(name))
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+
+    /// Env var name of a field with a prefix read from a const, e.g. `prefix = SERVICE_PREFIX`
+    pub fn prefixed_name(prefix: &str, field_name: &str) -> String {
+        let prefix = prefix.trim_end_matches('_');
+        let field_name = field_name.trim_start_matches('_');
+        format!("{prefix}_{field_name}").to_ascii_uppercase()
+    }
+
+    /// Custom env var name with `{prefix}` replaced by the value of a prefix const
+    pub fn prefixed_template(template: &str, prefix: &str) -> String {
+        template.replace("{prefix}", &prefix.to_ascii_uppercase())
+    }
+
+    /// Dotted key of a field with a prefix read from a const, e.g. `app.port`. Keep in sync with
+    /// `join_prefixed` in the derive
+    pub fn dotted_name(prefix: &str, field_name: &str) -> String {
+        let prefix = prefix.trim_end_matches('.');
+        let field_name = field_name.trim_start_matches(['.', '_']);
+        format!("{prefix}.{field_name}").to_ascii_lowercase()
+    }
+
+    /// Custom dotted key with `{prefix}` replaced by the value of a prefix const
+    pub fn dotted_template(template: &str, prefix: &str) -> String {
+        template.replace("{prefix}", &prefix.to_ascii_lowercase())
+    }
+}
+
+/// Names of the sections of `T` in `source`: for every variable `PREFIX_<NAME>_<VAR>` where
+/// `<VAR>` is a variable of `T`, the distinct `<NAME>`s in order. See [`load_sections`].
+///
+/// A name may contain `_`, e.g. `EU_WEST` in `UPSTREAM_EU_WEST_HOST`. The shortest name for which
+/// [`EnvConfig::any_vars_set`] finds a variable of `T` is used.
+pub fn section_names<T: EnvConfig>(source: &dyn EnvSource, prefix: &str) -> Vec<String> {
+    let prefix = format!("{}_", prefix.trim_end_matches('_').to_ascii_uppercase());
+    let mut names = BTreeSet::new();
+    for var in source.names() {
+        let Some(rest) = var.strip_prefix(&prefix) else {
+            continue;
+        };
+        let section = rest
+            .match_indices('_')
+            .map(|(end, _)| &rest[..end])
+            .find(|name| {
+                !name.is_empty()
+                    && T::any_vars_set(&__private::Prefixed {
+                        source,
+                        prefix: &format!("{prefix}{name}"),
+                        dotted: false,
+                    })
+            });
+        if let Some(name) = section {
+            names.insert(name.to_string());
+        }
+    }
+    names.into_iter().collect()
+}
+
+/// Load every section of `T` in `source`, keyed by the section name and ordered by it.
+///
+/// A section is a copy of `T`'s variables with `PREFIX_<NAME>_` prepended, like
+/// `UPSTREAM_EU_HOST` and `UPSTREAM_EU_PORT` for the section `EU` of `prefix` `UPSTREAM`, so `T`
+/// usually uses `#[env_cfg(no_prefix)]`. Sections are discovered with [`EnvSource::names`] (see
+/// [`section_names`]). An error in a section, like a missing variable, names the section.
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use env_cfg::{EnvConfig, load_sections};
+///
+/// #[derive(Debug, EnvConfig)]
+/// #[env_cfg(no_prefix)]
+/// struct Upstream {
+///     host: String,
+///     port: u16,
+/// }
+///
+/// let vars = HashMap::from([
+///     ("UPSTREAM_EU_WEST_HOST".to_string(), "eu.example.com".to_string()),
+///     ("UPSTREAM_EU_WEST_PORT".to_string(), "443".to_string()),
+///     ("UPSTREAM_US_HOST".to_string(), "us.example.com".to_string()),
+/// ]);
+/// let sections = load_sections::<Upstream>(&vars, "UPSTREAM");
+/// assert_eq!(
+///     sections.unwrap_err().to_string(),
+///     "section `US` of `UPSTREAM`: Missing environment variable: `UPSTREAM_US_PORT`"
+/// );
+/// ```
+pub fn load_sections<T>(
+    source: &dyn EnvSource,
+    prefix: &str,
+) -> Result<Vec<(String, T)>, EnvConfigError>
+where
+    T: EnvConfig<Error = EnvConfigError>,
+{
+    let prefix = prefix.trim_end_matches('_');
+    section_names::<T>(source, prefix)
+        .into_iter()
+        .map(|name| {
+            let section_prefix = format!("{prefix}_{name}");
+            let section = __private::Prefixed {
+                source,
+                prefix: &section_prefix,
+                dotted: false,
+            };
+            let config = T::from_source(&section).map_err(|e| {
+                section
+                    .error(e)
+                    .context(format!("section `{name}` of `{prefix}`"))
+            })?;
+            Ok((name, config))
+        })
+        .collect()
+}
+
+/// Load the raw value of an environment variable.
+/// Returns `None` if the variable is not set.
+#[cfg(feature = "std")]
+pub fn env_var_raw(name: &str) -> Result<Option<String>, EnvConfigError> {
+    SystemEnv.get(name)
+}
+
+/// Unwrap a raw value loaded from `name`, failing with [`EnvConfigError::Missing`] if it is absent.
+pub fn require(name: &str, value: Option<String>) -> Result<String, EnvConfigError> {
+    value.ok_or_else(|| EnvConfigError::Missing(name.to_string()))
+}
+
+/// Parse a raw value loaded from the environment variable `name` to the target type.
+pub fn parse_value<T>(name: &str, value: &str) -> Result<T, EnvConfigError>
+where
+    T: FromStr,
+    T::Err: core::fmt::Display,
+{
+    value
+        .parse::<T>()
+        .map_err(|e| EnvConfigError::Parse(name.to_string(), e.to_string()))
+}
+
+/// Convert a raw value loaded from the environment variable `name` with `T::try_from`.
+pub fn parse_try_from<T>(name: &str, value: String) -> Result<T, EnvConfigError>
+where
+    T: TryFrom<String>,
+    T::Error: core::fmt::Display,
+{
+    T::try_from(value).map_err(|e| EnvConfigError::Parse(name.to_string(), e.to_string()))
+}
+
+/// Load the variables `NAME_0`, `NAME_1`, ... from `source` up to the first index that is not
+/// set, parsing each to the target type. No entries result in an empty `Vec`.
+///
+/// Entries must be consecutive: if the index after the first unset one is set (e.g. `NAME_0` and
+/// `NAME_2` without `NAME_1`), this fails with [`EnvConfigError::Parse`] for that variable.
+pub fn indexed_list_from<T>(source: &dyn EnvSource, name: &str) -> Result<Vec<T>, EnvConfigError>
+where
+    T: FromStr,
+    T::Err: core::fmt::Display,
+{
+    let mut values = Vec::new();
+    loop {
+        let entry_name = format!("{name}_{}", values.len());
+        match source.get(&entry_name)? {
+            Some(value) => values.push(parse_value(&entry_name, &value)?),
+            None => {
+                let next_name = format!("{name}_{}", values.len() + 1);
+                if source.get(&next_name)?.is_some() {
+                    return Err(EnvConfigError::Parse(
+                        next_name,
+                        format!("`{entry_name}` is not set, indexed entries must be consecutive"),
+                    ));
+                }
+                return Ok(values);
+            }
+        }
+    }
+}
+
+/// Deserialize a JSON value loaded from the environment variable `name`.
+/// Used by `#[env_cfg(json_env = "VAR_NAME")]` to load a whole struct from a single variable.
+#[cfg(feature = "serde")]
+pub fn parse_json<T>(name: &str, value: &str) -> Result<T, EnvConfigError>
+where
+    T: serde::de::DeserializeOwned,
+{
+    serde_json::from_str(value).map_err(|e| EnvConfigError::Parse(name.to_string(), e.to_string()))
+}
+
+/// Maximum depth of nested `${VAR}` references followed by [`expand_vars`].
+const MAX_EXPANSION_DEPTH: usize = 8;
+
+/// Expand `${VAR}` references in `value` using the current environment.
+///
+/// `$$` is replaced with a literal `$`, and a `$` that does not start a reference is kept as is.
+/// Values of referenced variables are expanded too, up to a bounded depth.
+/// Fails with [`EnvConfigError::Missing`] if a referenced variable is not set, and with
+/// [`EnvConfigError::Invalid`] if a `${` is not closed. Errors never include `value` itself, as
+/// it may hold a secret.
+#[cfg(feature = "std")]
+pub fn expand_vars(value: &str) -> Result<String, EnvConfigError> {
+    expand_vars_inner(&SystemEnv, None, value, false, 0)
+}
+
+/// Expand `${VAR}` references in `value` like [`expand_vars`], but replace references to
+/// variables that are not set with an empty string.
+#[cfg(feature = "std")]
+pub fn expand_vars_or_empty(value: &str) -> Result<String, EnvConfigError> {
+    expand_vars_inner(&SystemEnv, None, value, true, 0)
+}
+
+/// Expand `${VAR}` references in `value` like [`expand_vars`], looking variables up in `source`.
+pub fn expand_vars_from(source: &dyn EnvSource, value: &str) -> Result<String, EnvConfigError> {
+    expand_vars_inner(source, None, value, false, 0)
+}
+
+/// Expand `${VAR}` references in `value` like [`expand_vars_or_empty`], looking variables up in
+/// `source`.
+pub fn expand_vars_or_empty_from(
+    source: &dyn EnvSource,
+    value: &str,
+) -> Result<String, EnvConfigError> {
+    expand_vars_inner(source, None, value, true, 0)
+}
+
+/// Expand the references in `value`, the value of the variable `name` if it is known, which
+/// errors about the value itself are reported for
+fn expand_vars_inner(
+    source: &dyn EnvSource,
+    name: Option<&str>,
+    value: &str,
+    missing_as_empty: bool,
+    depth: usize,
+) -> Result<String, EnvConfigError> {
+    let mut expanded = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(index) = rest.find('$') {
+        expanded.push_str(&rest[..index]);
+        rest = &rest[index..];
+        if let Some(after) = rest.strip_prefix("$$") {
+            expanded.push('$');
+            rest = after;
+        } else if let Some(after) = rest.strip_prefix("${") {
+            let end = after.find('}').ok_or_else(|| {
+                let message = "Unterminated `${` reference".to_string();
+                match name {
+                    Some(name) => EnvConfigError::Parse(name.to_string(), message),
+                    None => EnvConfigError::Invalid("value".to_string(), message),
+                }
+            })?;
+            let referenced = &after[..end];
+            match source.get(referenced)? {
+                Some(_) if depth == MAX_EXPANSION_DEPTH => {
+                    return Err(EnvConfigError::Parse(
+                        referenced.to_string(),
+                        format!("Exceeded the maximum expansion depth of {MAX_EXPANSION_DEPTH}"),
+                    ));
+                }
+                Some(inner) => expanded.push_str(&expand_vars_inner(
+                    source,
+                    Some(referenced),
+                    &inner,
+                    missing_as_empty,
+                    depth + 1,
+                )?),
+                None if missing_as_empty => {}
+                None => return Err(EnvConfigError::Missing(referenced.to_string())),
+            }
+            rest = &after[end + 1..];
+        } else {
+            expanded.push('$');
+            rest = &rest[1..];
+        }
+    }
+    expanded.push_str(rest);
+    Ok(expanded)
+}
+
+/// Parse a human-written integer, allowing `_` and `,` as digit group separators
+/// (e.g. `1_000_000` or `1,000,000`).
+///
+/// Separators must sit between two digits, so inputs like `1,000,` or `1__000` are rejected.
+pub fn parse_human_int<T>(s: &str) -> Result<T, String>
+where
+    T: FromStr,
+    T::Err: core::fmt::Display,
+{
+    let mut digits = String::with_capacity(s.len());
+    let mut previous = None;
+    let mut chars = s.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '_' || c == ',' {
+            let between_digits = previous.is_some_and(|p: char| p.is_ascii_digit())
+                && chars.peek().is_some_and(|n| n.is_ascii_digit());
+            if !between_digits {
+                return Err(format!("Misplaced digit group separator `{c}` in `{s}`"));
+            }
+        } else {
+            digits.push(c);
+        }
+        previous = Some(c);
+    }
+    digits.parse::<T>().map_err(|e| e.to_string())
+}
+
+/// Parse the value of a flag: an empty value is `true`, and otherwise `true`/`false`, `1`/`0`,
+/// `yes`/`no` and `on`/`off` are accepted in any case.
+///
+/// ```rust
+/// assert_eq!(env_cfg::parse_flag(""), Ok(true));
+/// assert_eq!(env_cfg::parse_flag("Off"), Ok(false));
+/// assert!(env_cfg::parse_flag("maybe").is_err());
+/// ```
+pub fn parse_flag(s: &str) -> Result<bool, String> {
+    match s.trim().to_ascii_lowercase().as_str() {
+        "" | "true" | "1" | "yes" | "on" => Ok(true),
+        "false" | "0" | "no" | "off" => Ok(false),
+        _ => Err(format!(
+            "Expected an empty value or one of true/false, 1/0, yes/no, on/off, got `{s}`"
         )),
     }
 }
+
+/// Types built from a list of flag names, like `logging,metrics`, see [`parse_flags`] and
+/// `#[env_cfg(flags)]`.
+///
+/// ```rust
+/// use env_cfg::FromFlags;
+///
+/// #[derive(Debug, Default, PartialEq)]
+/// struct Features {
+///     logging: bool,
+///     metrics: bool,
+/// }
+///
+/// impl FromFlags for Features {
+///     fn from_flags(flags: &[&str]) -> Result<Self, String> {
+///         let mut features = Features::default();
+///         for flag in flags {
+///             match *flag {
+///                 "logging" => features.logging = true,
+///                 "metrics" => features.metrics = true,
+///                 other => return Err(format!("unknown feature `{other}`")),
+///             }
+///         }
+///         Ok(features)
+///     }
+/// }
+///
+/// assert_eq!(
+///     env_cfg::parse_flags::<Features>("metrics"),
+///     Ok(Features { logging: false, metrics: true })
+/// );
+/// assert!(env_cfg::parse_flags::<Features>("metrics,tracing").is_err());
+/// ```
+pub trait FromFlags: Sized {
+    /// Build the value from the flag names, in the order they were given. An unknown name should
+    /// be an error naming it.
+    fn from_flags(flags: &[&str]) -> Result<Self, String>;
+}
+
+impl FromFlags for Vec<String> {
+    fn from_flags(flags: &[&str]) -> Result<Self, String> {
+        Ok(flags.iter().map(|flag| flag.to_string()).collect())
+    }
+}
+
+impl FromFlags for BTreeSet<String> {
+    fn from_flags(flags: &[&str]) -> Result<Self, String> {
+        Ok(flags.iter().map(|flag| flag.to_string()).collect())
+    }
+}
+
+#[cfg(feature = "std")]
+impl<S: std::hash::BuildHasher + Default> FromFlags for std::collections::HashSet<String, S> {
+    fn from_flags(flags: &[&str]) -> Result<Self, String> {
+        Ok(flags.iter().map(|flag| flag.to_string()).collect())
+    }
+}
+
+/// Parse a comma-separated list of flag names (e.g. `logging,metrics`) with [`FromFlags`].
+///
+/// Whitespace around names is trimmed and empty names are skipped, so an empty value sets no
+/// flags.
+pub fn parse_flags<T: FromFlags>(s: &str) -> Result<T, String> {
+    let flags: Vec<&str> = s
+        .split(',')
+        .map(str::trim)
+        .filter(|flag| !flag.is_empty())
+        .collect();
+    T::from_flags(&flags)
+}
+
+/// Integer types that can be parsed in a given radix, see [`parse_int_radix`].
+pub trait FromStrRadix: Sized {
+    /// Parse `s` in `radix`, like the inherent `from_str_radix` of the integer types.
+    fn from_str_radix(s: &str, radix: u32) -> Result<Self, core::num::ParseIntError>;
+}
+
+macro_rules! impl_from_str_radix {
+    ($($int:ty),*) => {
+        $(
+            impl FromStrRadix for $int {
+                fn from_str_radix(s: &str, radix: u32) -> Result<Self, core::num::ParseIntError> {
+                    <$int>::from_str_radix(s, radix)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_str_radix!(
+    u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize
+);
+
+/// Parse an integer in the given radix (2 to 36), e.g. `ff` with radix 16.
+///
+/// The matching `0x`, `0o` or `0b` prefix is accepted for radix 16, 8 and 2, after the sign.
+///
+/// ```rust
+/// assert_eq!(env_cfg::parse_int_radix::<u8>("0xFF", 16), Ok(255));
+/// assert_eq!(env_cfg::parse_int_radix::<i32>("-101", 2), Ok(-5));
+/// assert!(env_cfg::parse_int_radix::<u8>("12", 2).is_err());
+/// ```
+pub fn parse_int_radix<T: FromStrRadix>(s: &str, radix: u32) -> Result<T, String> {
+    let (sign, unsigned) = split_sign(s);
+    let digits = match radix {
+        16 => strip_radix_prefix(unsigned, "0x"),
+        8 => strip_radix_prefix(unsigned, "0o"),
+        2 => strip_radix_prefix(unsigned, "0b"),
+        _ => None,
+    }
+    .unwrap_or(unsigned);
+    parse_digits(s, sign, digits, radix)
+}
+
+/// Parse an integer with its radix chosen by its prefix: `0x` for hexadecimal, `0o` for octal,
+/// `0b` for binary, and decimal otherwise (e.g. `0o755`).
+///
+/// ```rust
+/// assert_eq!(env_cfg::parse_int_radix_auto::<u32>("0o755"), Ok(0o755));
+/// assert_eq!(env_cfg::parse_int_radix_auto::<i16>("-0x10"), Ok(-16));
+/// assert_eq!(env_cfg::parse_int_radix_auto::<u8>("42"), Ok(42));
+/// ```
+pub fn parse_int_radix_auto<T: FromStrRadix>(s: &str) -> Result<T, String> {
+    let (sign, unsigned) = split_sign(s);
+    let (digits, radix) = [("0x", 16), ("0o", 8), ("0b", 2)]
+        .into_iter()
+        .find_map(|(prefix, radix)| Some((strip_radix_prefix(unsigned, prefix)?, radix)))
+        .unwrap_or((unsigned, 10));
+    parse_digits(s, sign, digits, radix)
+}
+
+fn split_sign(s: &str) -> (&str, &str) {
+    match s.strip_prefix(['+', '-']) {
+        Some(unsigned) => (&s[..1], unsigned),
+        None => ("", s),
+    }
+}
+
+fn strip_radix_prefix<'a>(s: &'a str, prefix: &str) -> Option<&'a str> {
+    s.get(..prefix.len())
+        .filter(|start| start.eq_ignore_ascii_case(prefix))
+        .map(|_| &s[prefix.len()..])
+}
+
+fn parse_digits<T: FromStrRadix>(
+    s: &str,
+    sign: &str,
+    digits: &str,
+    radix: u32,
+) -> Result<T, String> {
+    if !(2..=36).contains(&radix) {
+        return Err(format!("Radix must be between 2 and 36, got {radix}"));
+    }
+    // A sign after the prefix (`0x-1`) is not accepted
+    if digits.starts_with(['+', '-']) {
+        return Err(format!("`{s}` is not a valid base {radix} integer"));
+    }
+    T::from_str_radix(&format!("{sign}{digits}"), radix)
+        .map_err(|e| format!("`{s}` is not a valid base {radix} integer: {e}"))
+}
+
+/// Parse a number of seconds with an optional fractional part (e.g. `0.5`) as a [`Duration`].
+///
+/// Negative, NaN, infinite and too large values are rejected.
+pub fn parse_duration_secs_f64(s: &str) -> Result<Duration, String> {
+    let secs = s.parse::<f64>().map_err(|e| e.to_string())?;
+    if !secs.is_finite() {
+        return Err(format!(
+            "Duration must be a finite number of seconds, got `{s}`"
+        ));
+    }
+    if secs < 0.0 {
+        return Err(format!("Duration must not be negative, got `{s}`"));
+    }
+    Duration::try_from_secs_f64(secs).map_err(|e| format!("Invalid duration `{s}`: {e}"))
+}
+
+/// Strip one leading and one trailing occurrence of a character in `chars` from `value`.
+///
+/// Only strips when both ends hold the same character, so values that merely start or end
+/// with a quote are left untouched.
+pub fn trim_matching_pair<'a>(value: &'a str, chars: &str) -> &'a str {
+    let mut value_chars = value.chars();
+    match (value_chars.next(), value_chars.next_back()) {
+        (Some(first), Some(last)) if first == last && chars.contains(first) => value_chars.as_str(),
+        _ => value,
+    }
+}
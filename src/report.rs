@@ -0,0 +1,126 @@
+//! A record of the variables consulted while loading a config.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::cell::RefCell;
+use core::fmt;
+#[cfg(feature = "std")]
+use std::ffi::OsString;
+
+use crate::{EnvConfigError, EnvSource};
+
+/// Every variable looked up while loading a config, in the order of the lookups, see
+/// [`EnvConfig::from_source_with_report`](crate::EnvConfig::from_source_with_report).
+///
+/// A field reading several names (like `env = [...]`, `alias` or `default_env`) records each name
+/// it tried, so the first one that is set is the one the value came from. The values of
+/// `SecretString` and `SecretBytes` fields are never recorded.
+///
+/// `Display` prints one lookup per line, e.g. `DB_HOST=localhost`, `DB_PORT (unset)` or
+/// `DB_PASSWORD=[REDACTED]`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LoadReport {
+    /// The lookups in the order they were made
+    pub lookups: Vec<Lookup>,
+}
+
+/// A single variable lookup recorded in a [`LoadReport`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Lookup {
+    /// Name of the variable
+    pub name: String,
+    /// What the source returned for it
+    pub outcome: LookupOutcome,
+}
+
+/// The result of a [`Lookup`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LookupOutcome {
+    /// The variable is set to this value. Values that are not valid Unicode are converted lossily
+    Set(String),
+    /// The variable is set, but holds a secret whose value is left out
+    Redacted,
+    /// The variable is not set
+    Unset,
+    /// The source failed to read the variable, e.g. because its value is not valid Unicode
+    Failed,
+}
+
+impl LoadReport {
+    /// The value each variable that is set was found with, skipping those that are unset or
+    /// redacted
+    pub fn values(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.lookups
+            .iter()
+            .filter_map(|lookup| match &lookup.outcome {
+                LookupOutcome::Set(value) => Some((lookup.name.as_str(), value.as_str())),
+                _ => None,
+            })
+    }
+}
+
+impl fmt::Display for LoadReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for lookup in &self.lookups {
+            match &lookup.outcome {
+                LookupOutcome::Set(value) => writeln!(f, "{}={value}", lookup.name)?,
+                LookupOutcome::Redacted => writeln!(f, "{}=[REDACTED]", lookup.name)?,
+                LookupOutcome::Unset => writeln!(f, "{} (unset)", lookup.name)?,
+                LookupOutcome::Failed => writeln!(f, "{} (failed)", lookup.name)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A source recording every lookup made through it into a [`LoadReport`]
+pub(crate) struct Recording<'a> {
+    pub(crate) source: &'a dyn EnvSource,
+    pub(crate) report: RefCell<LoadReport>,
+}
+
+impl Recording<'_> {
+    fn record<T>(
+        &self,
+        name: &str,
+        result: &Result<Option<T>, EnvConfigError>,
+        outcome: impl FnOnce(&T) -> LookupOutcome,
+    ) {
+        let outcome = match result {
+            Ok(Some(value)) => outcome(value),
+            Ok(None) => LookupOutcome::Unset,
+            Err(_) => LookupOutcome::Failed,
+        };
+        self.report.borrow_mut().lookups.push(Lookup {
+            name: String::from(name),
+            outcome,
+        });
+    }
+}
+
+impl EnvSource for Recording<'_> {
+    fn get(&self, name: &str) -> Result<Option<String>, EnvConfigError> {
+        let result = self.source.get(name);
+        self.record(name, &result, |value| LookupOutcome::Set(value.clone()));
+        result
+    }
+
+    fn get_secret(&self, name: &str) -> Result<Option<String>, EnvConfigError> {
+        let result = self.source.get_secret(name);
+        self.record(name, &result, |_| LookupOutcome::Redacted);
+        result
+    }
+
+    #[cfg(feature = "std")]
+    fn get_os(&self, name: &str) -> Result<Option<OsString>, EnvConfigError> {
+        let result = self.source.get_os(name);
+        self.record(name, &result, |value| {
+            LookupOutcome::Set(value.to_string_lossy().into_owned())
+        });
+        result
+    }
+
+    fn names(&self) -> Vec<String> {
+        self.source.names()
+    }
+}
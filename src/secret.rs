@@ -0,0 +1,139 @@
+//! Secret values that are zeroized from memory when dropped.
+//!
+//! Enabled with the `zeroize` feature.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+use core::str::FromStr;
+
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+/// A string, like a password or an API key, that is overwritten with zeros when dropped.
+///
+/// `Debug` never prints the value, read it with [`expose_secret`](Self::expose_secret). A derived
+/// config field of this type (or `Option<SecretString>`) takes ownership of the loaded value
+/// instead of copying it, so no copy is left behind unzeroized.
+///
+/// ```rust
+/// use env_cfg::{EnvConfig, SecretString};
+/// use std::collections::BTreeMap;
+///
+/// #[derive(Debug, EnvConfig)]
+/// #[env_cfg(prefix = "DB")]
+/// struct DatabaseConfig {
+///     password: SecretString,
+/// }
+///
+/// let vars = BTreeMap::from([("DB_PASSWORD".to_string(), "hunter2".to_string())]);
+/// let config = DatabaseConfig::from_source(&vars).unwrap();
+/// assert_eq!(config.password.expose_secret(), "hunter2");
+/// assert!(!format!("{config:?}").contains("hunter2"));
+/// ```
+#[derive(Clone, Default)]
+pub struct SecretString(String);
+
+impl SecretString {
+    /// Wrap a string, taking ownership of its buffer
+    pub fn new(secret: String) -> Self {
+        SecretString(secret)
+    }
+
+    /// The secret value
+    pub fn expose_secret(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<String> for SecretString {
+    fn from(secret: String) -> Self {
+        SecretString(secret)
+    }
+}
+
+impl FromStr for SecretString {
+    type Err = core::convert::Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(SecretString(String::from(s)))
+    }
+}
+
+impl fmt::Debug for SecretString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SecretString([REDACTED])")
+    }
+}
+
+impl Zeroize for SecretString {
+    fn zeroize(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl Drop for SecretString {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for SecretString {}
+
+/// Bytes, like a key, that are overwritten with zeros when dropped.
+///
+/// Loaded from the UTF-8 bytes of the value. Like [`SecretString`], `Debug` never prints the
+/// value and derived config fields take ownership of the loaded value.
+#[derive(Clone, Default)]
+pub struct SecretBytes(Vec<u8>);
+
+impl SecretBytes {
+    /// Wrap bytes, taking ownership of their buffer
+    pub fn new(secret: Vec<u8>) -> Self {
+        SecretBytes(secret)
+    }
+
+    /// The secret value
+    pub fn expose_secret(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl From<Vec<u8>> for SecretBytes {
+    fn from(secret: Vec<u8>) -> Self {
+        SecretBytes(secret)
+    }
+}
+
+impl From<String> for SecretBytes {
+    fn from(secret: String) -> Self {
+        SecretBytes(secret.into_bytes())
+    }
+}
+
+impl FromStr for SecretBytes {
+    type Err = core::convert::Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(SecretBytes(Vec::from(s.as_bytes())))
+    }
+}
+
+impl fmt::Debug for SecretBytes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SecretBytes([REDACTED])")
+    }
+}
+
+impl Zeroize for SecretBytes {
+    fn zeroize(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl Drop for SecretBytes {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for SecretBytes {}
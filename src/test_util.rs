@@ -0,0 +1,98 @@
+//! Helpers for testing configs loaded from the process environment.
+//!
+//! Enabled with the `test-util` feature.
+
+use std::ffi::OsString;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+/// Serializes all [`ScopedEnv`] guards in the process.
+static ENV_LOCK: Mutex<()> = Mutex::new(());
+
+/// Sets environment variables for as long as it is alive, and restores their previous values
+/// (or removes them, if they were unset) when dropped.
+///
+/// Only one `ScopedEnv` exists at a time: creating one waits until any other guard is dropped,
+/// so tests running in parallel don't see each other's variables. Creating a second guard on the
+/// same thread while the first is alive deadlocks.
+///
+/// ```rust
+/// use env_cfg::{EnvConfig, test_util::ScopedEnv};
+///
+/// #[derive(EnvConfig)]
+/// #[env_cfg(prefix = "SERVICE")]
+/// struct ServiceConfig {
+///     port: u16,
+/// }
+///
+/// // SAFETY: no other thread reads or writes the environment outside of a `ScopedEnv`
+/// let env = unsafe { ScopedEnv::new(&[("SERVICE_PORT", "8080")]) };
+/// assert_eq!(ServiceConfig::from_env().unwrap().port, 8080);
+/// drop(env);
+/// assert!(std::env::var("SERVICE_PORT").is_err());
+/// ```
+#[must_use = "the variables are restored as soon as the guard is dropped"]
+pub struct ScopedEnv {
+    /// Variables set by this guard with their previous values, in the order they were set
+    previous: Vec<(String, Option<OsString>)>,
+    _lock: MutexGuard<'static, ()>,
+}
+
+impl ScopedEnv {
+    /// Set the given variables until the returned guard is dropped.
+    ///
+    /// # Safety
+    ///
+    /// See [`std::env::set_var`]: no other thread may read or write the environment while the
+    /// guard is alive, except through another `ScopedEnv` (which waits for this one) or while
+    /// holding one.
+    pub unsafe fn new(vars: &[(&str, &str)]) -> Self {
+        // A test that panicked while holding the lock still restored its variables on drop
+        let lock = ENV_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+        let mut env = ScopedEnv {
+            previous: Vec::with_capacity(vars.len()),
+            _lock: lock,
+        };
+        for (name, value) in vars {
+            // SAFETY: upheld by the caller
+            unsafe { env.set(name, value) };
+        }
+        env
+    }
+
+    /// Set another variable until the guard is dropped.
+    ///
+    /// # Safety
+    ///
+    /// Same as [`ScopedEnv::new`].
+    pub unsafe fn set(&mut self, name: &str, value: &str) {
+        self.previous
+            .push((name.to_string(), std::env::var_os(name)));
+        // SAFETY: upheld by the caller
+        unsafe { std::env::set_var(name, value) };
+    }
+
+    /// Remove a variable until the guard is dropped.
+    ///
+    /// # Safety
+    ///
+    /// Same as [`ScopedEnv::new`].
+    pub unsafe fn remove(&mut self, name: &str) {
+        self.previous
+            .push((name.to_string(), std::env::var_os(name)));
+        // SAFETY: upheld by the caller
+        unsafe { std::env::remove_var(name) };
+    }
+}
+
+impl Drop for ScopedEnv {
+    fn drop(&mut self) {
+        // Restore in reverse, so a variable set twice ends up with its original value
+        for (name, value) in self.previous.drain(..).rev() {
+            // SAFETY: the caller of `new` guaranteed exclusive access while the guard is alive
+            match value {
+                Some(value) => unsafe { std::env::set_var(&name, value) },
+                None => unsafe { std::env::remove_var(&name) },
+            }
+        }
+    }
+}
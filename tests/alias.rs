@@ -0,0 +1,71 @@
+// `alias` tests
+use env_cfg::{EnvConfig, EnvConfigError};
+
+mod common;
+
+#[derive(Debug, PartialEq)]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+/// The current format, `x,y`
+fn parse_point(value: String) -> Point {
+    let (x, y) = value.split_once(',').unwrap();
+    Point {
+        x: x.trim().parse().unwrap(),
+        y: y.trim().parse().unwrap(),
+    }
+}
+
+/// The legacy format, `x:y`
+fn parse_legacy_point(value: String) -> Point {
+    let (x, y) = value.split_once(':').unwrap();
+    Point {
+        x: x.parse().unwrap(),
+        y: y.parse().unwrap(),
+    }
+}
+
+#[derive(Debug, EnvConfig)]
+#[env_cfg(no_prefix)]
+struct CanvasConfig {
+    #[env_cfg(
+        env = "NEW_POINT",
+        parse_with = "parse_point",
+        alias(env = "OLD_POINT", parse_with = "parse_legacy_point")
+    )]
+    origin: Point,
+    #[env_cfg(alias(env = "LEGACY_SCALE"))]
+    scale: Option<u32>, // -> SCALE, or LEGACY_SCALE parsed with `FromStr`
+}
+
+#[test]
+fn should_parse_new_variable_with_field_parser() {
+    const ENV_KEYS_VALUES: &[(&str, &str)] = &[("NEW_POINT", "3, 4"), ("OLD_POINT", "1:2")];
+    let config =
+        unsafe { common::with_env_vars(ENV_KEYS_VALUES, || CanvasConfig::from_env().unwrap()) };
+
+    assert_eq!(config.origin, Point { x: 3, y: 4 });
+    assert_eq!(config.scale, None);
+}
+
+#[test]
+fn should_parse_alias_with_its_own_parser() {
+    const ENV_KEYS_VALUES: &[(&str, &str)] = &[("OLD_POINT", "1:2"), ("LEGACY_SCALE", "2")];
+    let config =
+        unsafe { common::with_env_vars(ENV_KEYS_VALUES, || CanvasConfig::from_env().unwrap()) };
+
+    assert_eq!(config.origin, Point { x: 1, y: 2 });
+    assert_eq!(config.scale, Some(2));
+}
+
+#[test]
+fn should_report_missing_variable_with_aliases() {
+    let result = unsafe { common::with_env_vars(&[], CanvasConfig::from_env) };
+
+    assert_eq!(
+        result.unwrap_err(),
+        EnvConfigError::Missing("NEW_POINT (or OLD_POINT)".to_string())
+    );
+}
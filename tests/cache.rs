@@ -0,0 +1,49 @@
+// `cached_from_env` tests
+#![cfg(feature = "cache")]
+use env_cfg::EnvConfig;
+use env_cfg::EnvConfigError;
+
+mod common;
+
+// Each test uses its own struct, since the cache is shared by all callers of a type
+#[derive(Debug, EnvConfig)]
+#[env_cfg(prefix = "CACHED")]
+struct CachedConfig {
+    url: String, // -> CACHED_URL
+}
+
+#[derive(Debug, EnvConfig)]
+#[env_cfg(prefix = "RETRIED")]
+struct RetriedConfig {
+    url: String, // -> RETRIED_URL
+}
+
+#[test]
+fn should_load_once_and_return_cached_config() {
+    let first = unsafe {
+        common::with_env_vars(&[("CACHED_URL", "first")], || {
+            CachedConfig::cached_from_env().unwrap()
+        })
+    };
+    let second = unsafe {
+        common::with_env_vars(&[("CACHED_URL", "second")], || {
+            CachedConfig::cached_from_env().unwrap()
+        })
+    };
+
+    assert_eq!(first.url, "first");
+    assert!(std::ptr::eq(first, second));
+}
+
+#[test]
+fn should_not_cache_failures() {
+    let failed = unsafe { common::with_env_vars(&[], RetriedConfig::cached_from_env) };
+    let loaded = unsafe {
+        common::with_env_vars(&[("RETRIED_URL", "retried")], || {
+            RetriedConfig::cached_from_env().unwrap()
+        })
+    };
+
+    assert!(matches!(failed, Err(EnvConfigError::Missing(var)) if var == "RETRIED_URL"));
+    assert_eq!(loaded.url, "retried");
+}
@@ -0,0 +1,113 @@
+// `capture` tests
+use std::collections::HashMap;
+
+use env_cfg::EnvConfig;
+
+mod common;
+
+#[derive(Debug, PartialEq, EnvConfig)]
+#[env_cfg(no_prefix)]
+struct DatabaseConfig {
+    #[env_cfg(env = ["DATABASE_URL", "DB_URL"])]
+    url: String, // -> DATABASE_URL, or DB_URL
+    #[env_cfg(default = 10)]
+    pool_size: u32, // -> POOL_SIZE
+}
+
+#[derive(Debug, PartialEq, EnvConfig)]
+#[env_cfg(prefix = "APP")]
+struct AppConfig {
+    name: String,      // -> APP_NAME
+    port: Option<u16>, // -> APP_PORT
+    #[env_cfg(nested)]
+    database: DatabaseConfig,
+}
+
+#[test]
+fn should_capture_the_variables_read() {
+    const ENV_KEYS_VALUES: &[(&str, &str)] = &[
+        ("APP_NAME", "api"),
+        ("DB_URL", "postgres://db"),
+        ("POOL_SIZE", "20"),
+        ("UNRELATED", "ignored"),
+    ];
+    let (config, vars) =
+        unsafe { common::with_env_vars(ENV_KEYS_VALUES, || AppConfig::capture().unwrap()) };
+
+    let expected = HashMap::from([
+        ("APP_NAME".to_string(), "api".to_string()),
+        ("DB_URL".to_string(), "postgres://db".to_string()),
+        ("POOL_SIZE".to_string(), "20".to_string()),
+    ]);
+    assert_eq!(vars, expected);
+    assert_eq!(AppConfig::from_source(&vars).unwrap(), config);
+}
+
+#[test]
+fn should_replay_a_golden_snapshot() {
+    // A map captured once and committed alongside the test
+    const GOLDEN: &[(&str, &str)] = &[
+        ("APP_NAME", "api"),
+        ("APP_PORT", "8080"),
+        ("DATABASE_URL", "postgres://db"),
+    ];
+    let vars: HashMap<String, String> = GOLDEN
+        .iter()
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .collect();
+
+    let config = AppConfig::from_source(&vars).unwrap();
+    assert_eq!(
+        config,
+        AppConfig {
+            name: "api".to_string(),
+            port: Some(8080),
+            database: DatabaseConfig {
+                url: "postgres://db".to_string(),
+                pool_size: 10,
+            },
+        }
+    );
+}
+
+#[test]
+fn should_fail_capture_when_loading_fails() {
+    const ENV_KEYS_VALUES: &[(&str, &str)] = &[("APP_NAME", "api")];
+    let result = unsafe { common::with_env_vars(ENV_KEYS_VALUES, AppConfig::capture) };
+
+    assert!(result.is_err());
+}
+
+#[cfg(feature = "zeroize")]
+#[derive(Debug, EnvConfig)]
+#[env_cfg(prefix = "VAULT")]
+struct VaultConfig {
+    address: String,              // -> VAULT_ADDRESS
+    token: env_cfg::SecretString, // -> VAULT_TOKEN
+}
+
+#[cfg(feature = "zeroize")]
+#[test]
+fn should_leave_secrets_out_of_the_capture() {
+    const ENV_KEYS_VALUES: &[(&str, &str)] = &[
+        ("VAULT_ADDRESS", "https://vault.internal"),
+        ("VAULT_TOKEN", "s.hunter2"),
+    ];
+    let (config, vars) =
+        unsafe { common::with_env_vars(ENV_KEYS_VALUES, || VaultConfig::capture().unwrap()) };
+
+    assert_eq!(config.address, "https://vault.internal");
+    assert_eq!(config.token.expose_secret(), "s.hunter2");
+    assert_eq!(
+        vars,
+        HashMap::from([(
+            "VAULT_ADDRESS".to_string(),
+            "https://vault.internal".to_string()
+        )])
+    );
+    // The secret has to be added to the map before it replays
+    assert_eq!(
+        VaultConfig::from_source(&vars).unwrap_err(),
+        env_cfg::EnvConfigError::Missing("VAULT_TOKEN".to_string())
+    );
+}
@@ -0,0 +1,75 @@
+// `clamp` tests
+use std::sync::Mutex;
+
+use env_cfg::EnvConfig;
+
+mod common;
+
+/// Variables clamped by `record_clamped`, with the value that was set
+static CLAMPED: Mutex<Vec<(String, u32)>> = Mutex::new(Vec::new());
+
+fn record_clamped(name: &str, original: &u32, _clamped: &u32) {
+    CLAMPED.lock().unwrap().push((name.to_string(), *original));
+}
+
+#[derive(Debug, EnvConfig)]
+#[env_cfg(prefix = "TUNING")]
+struct TuningConfig {
+    #[env_cfg(clamp(1, 256), on_clamp = "record_clamped")]
+    workers: u32, // -> TUNING_WORKERS
+    #[env_cfg(clamp(0.0, 1.0), default = "0.5")]
+    sample_rate: f64, // -> TUNING_SAMPLE_RATE (with default)
+    #[env_cfg(clamp(-10, 10))]
+    offset: Option<i32>, // -> TUNING_OFFSET (optional)
+}
+
+fn load(vars: &[(&str, &str)]) -> TuningConfig {
+    unsafe { common::with_env_vars(vars, || TuningConfig::from_env().unwrap()) }
+}
+
+#[test]
+fn should_keep_values_in_range() {
+    let config = load(&[("TUNING_WORKERS", "8"), ("TUNING_OFFSET", "-3")]);
+
+    assert_eq!(config.workers, 8);
+    assert_eq!(config.sample_rate, 0.5);
+    assert_eq!(config.offset, Some(-3));
+}
+
+#[test]
+fn should_clamp_values_below_range() {
+    let config = load(&[
+        ("TUNING_WORKERS", "0"),
+        ("TUNING_SAMPLE_RATE", "-0.25"),
+        ("TUNING_OFFSET", "-50"),
+    ]);
+
+    assert_eq!(config.workers, 1);
+    assert_eq!(config.sample_rate, 0.0);
+    assert_eq!(config.offset, Some(-10));
+    assert!(
+        CLAMPED
+            .lock()
+            .unwrap()
+            .contains(&("TUNING_WORKERS".to_string(), 0))
+    );
+}
+
+#[test]
+fn should_clamp_values_above_range() {
+    let config = load(&[
+        ("TUNING_WORKERS", "1024"),
+        ("TUNING_SAMPLE_RATE", "4"),
+        ("TUNING_OFFSET", "11"),
+    ]);
+
+    assert_eq!(config.workers, 256);
+    assert_eq!(config.sample_rate, 1.0);
+    assert_eq!(config.offset, Some(10));
+    assert!(
+        CLAMPED
+            .lock()
+            .unwrap()
+            .contains(&("TUNING_WORKERS".to_string(), 1024))
+    );
+}
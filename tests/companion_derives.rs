@@ -0,0 +1,87 @@
+// Deriving `EnvConfig` together with common companion derives
+use serde::Serialize;
+
+use env_cfg::EnvConfig;
+
+mod common;
+
+#[derive(Debug, Clone, PartialEq, Default, Serialize, EnvConfig)]
+#[env_cfg(prefix = "INNER")]
+struct CompanionInner {
+    url: String, // -> INNER_URL
+    #[env_cfg(default = "3")]
+    retries: u8, // -> INNER_RETRIES (with default)
+}
+
+#[derive(Debug, Clone, PartialEq, Default, Serialize, EnvConfig)]
+#[env_cfg(prefix = "COMPANION")]
+struct CompanionConfig {
+    name: String,         // -> COMPANION_NAME
+    timeout: Option<u64>, // -> COMPANION_TIMEOUT (optional)
+    #[env_cfg(skip)]
+    tags: Vec<String>, // Skipped - uses Default::default()
+    #[env_cfg(default_from = "name")]
+    display_name: String, // -> COMPANION_DISPLAY_NAME (defaults to name)
+    #[env_cfg(nested)]
+    inner: CompanionInner,
+    // Field names matching identifiers used by the generated code
+    source: Option<String>, // -> COMPANION_SOURCE (optional)
+    value: Option<String>,  // -> COMPANION_VALUE (optional)
+    map: Option<String>,    // -> COMPANION_MAP (optional)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, EnvConfig)]
+#[env_cfg(no_prefix)]
+struct CopyConfig {
+    #[env_cfg(default = "8080")]
+    copy_port: u16, // -> COPY_PORT (with default)
+    copy_debug: Option<bool>, // -> COPY_DEBUG (optional)
+}
+
+fn assert_serialize<T: Serialize>(_: &T) {}
+
+#[test]
+fn should_derive_alongside_clone_partial_eq_default_and_serialize() {
+    const ENV_KEYS_VALUES: &[(&str, &str)] = &[
+        ("COMPANION_NAME", "app"),
+        ("COMPANION_TAGS", "ignored"),
+        ("COMPANION_SOURCE", "file"),
+        ("COMPANION_VALUE", "42"),
+        ("INNER_URL", "postgres://localhost"),
+    ];
+    let config =
+        unsafe { common::with_env_vars(ENV_KEYS_VALUES, || CompanionConfig::from_env().unwrap()) };
+
+    let expected = CompanionConfig {
+        name: "app".to_string(),
+        display_name: "app".to_string(),
+        inner: CompanionInner {
+            url: "postgres://localhost".to_string(),
+            retries: 3,
+        },
+        source: Some("file".to_string()),
+        value: Some("42".to_string()),
+        ..Default::default()
+    };
+    assert_eq!(config, expected);
+    assert_eq!(config.clone(), config);
+    assert_ne!(config, CompanionConfig::default());
+    assert_serialize(&config);
+}
+
+#[test]
+fn should_derive_alongside_copy() {
+    const ENV_KEYS_VALUES: &[(&str, &str)] = &[("COPY_DEBUG", "true")];
+    let config =
+        unsafe { common::with_env_vars(ENV_KEYS_VALUES, || CopyConfig::from_env().unwrap()) };
+    let copy = config;
+
+    assert_eq!(
+        config,
+        CopyConfig {
+            copy_port: 8080,
+            copy_debug: Some(true),
+        }
+    );
+    assert_eq!(copy, config);
+}
@@ -0,0 +1,7 @@
+// Attribute validation tests: each fixture in `tests/ui` must fail to compile with the error in
+// the matching `.stderr` file. Regenerate the expected output with `TRYBUILD=overwrite`.
+#[test]
+fn should_reject_invalid_attributes() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/*.rs");
+}
@@ -1,5 +1,5 @@
 // Derive macro tests
-use env_cfg::{EnvConfig, EnvConfigError};
+use env_cfg::{EnvConfig, EnvConfigError, EnvSource};
 
 mod common;
 
@@ -427,6 +427,29 @@ fn should_parse_with_complex_defaults() {
     assert!((config.rate - 3.999).abs() < f64::EPSILON);
 }
 
+#[test]
+fn should_fail_on_invalid_default_only_when_variable_is_unset() {
+    #[derive(Debug, EnvConfig)]
+    #[env_cfg(no_prefix)]
+    struct InvalidDefaultTest {
+        // Not a primitive, so the default is only parsed at runtime
+        #[env_cfg(default = "not-an-address")]
+        bind_address: std::net::IpAddr, // -> BIND_ADDRESS
+    }
+
+    let result = unsafe { common::with_env_vars(&[], InvalidDefaultTest::from_env) };
+    assert!(matches!(
+        result,
+        Err(EnvConfigError::Parse(var, _)) if var == "default for BIND_ADDRESS"
+    ));
+
+    const ENV_KEYS_VALUES: &[(&str, &str)] = &[("BIND_ADDRESS", "127.0.0.1")];
+    let config = unsafe {
+        common::with_env_vars(ENV_KEYS_VALUES, || InvalidDefaultTest::from_env().unwrap())
+    };
+    assert_eq!(config.bind_address.to_string(), "127.0.0.1");
+}
+
 #[test]
 fn should_parse_edge_case_field_names() {
     #[derive(Debug, EnvConfig)]
@@ -458,4 +481,648 @@ fn should_parse_edge_case_field_names() {
     assert_eq!(config.a_b_c, "value_a_b_c");
     assert_eq!(config.field_, "value_field_");
     assert_eq!(config._field, "value__field");
+
+    // With a prefix, the underscores where prefix and field name meet collapse into one
+    #[derive(Debug, EnvConfig)]
+    #[env_cfg(prefix = "EDGE_")]
+    struct PrefixedEdgeCaseNamesTest {
+        field_: String, // -> EDGE_FIELD_
+        _other: String, // -> EDGE_OTHER
+    }
+
+    #[derive(Debug, EnvConfig)]
+    struct Edge {
+        _field: String, // -> EDGE_FIELD
+    }
+
+    const PREFIXED_KEYS_VALUES: &[(&str, &str)] = &[
+        ("EDGE_FIELD_", "value_field_"),
+        ("EDGE_OTHER", "value__other"),
+        ("EDGE_FIELD", "value__field"),
+    ];
+    let (prefixed, struct_prefixed) = unsafe {
+        common::with_env_vars(PREFIXED_KEYS_VALUES, || {
+            (
+                PrefixedEdgeCaseNamesTest::from_env().unwrap(),
+                Edge::from_env().unwrap(),
+            )
+        })
+    };
+
+    assert_eq!(prefixed.field_, "value_field_");
+    assert_eq!(prefixed._other, "value__other");
+    assert_eq!(struct_prefixed._field, "value__field");
+}
+
+mod parsers {
+    pub fn parse_tripled_int(s: String) -> i32 {
+        let base: i32 = s.parse().expect("Invalid number");
+        base * 3
+    }
+}
+
+#[test]
+fn should_parse_with_local_and_relative_parsers() {
+    fn parse_upper(s: String) -> String {
+        s.to_uppercase()
+    }
+
+    #[derive(Debug, EnvConfig)]
+    #[env_cfg(no_prefix)]
+    struct LocalParserTest {
+        #[env_cfg(parse_with = "parse_upper")]
+        local_value: String, // -> LOCAL_VALUE (parser defined in this fn)
+        #[env_cfg(parse_with = "parsers::parse_tripled_int")]
+        tripled_value: i32, // -> TRIPLED_VALUE (parser referenced by relative path)
+        #[env_cfg(parse_with = "self::parse_doubled_int")]
+        doubled_value: Option<i32>, // -> DOUBLED_VALUE (optional, module-level parser)
+    }
+
+    const ENV_KEYS_VALUES: &[(&str, &str)] = &[
+        ("LOCAL_VALUE", "shout"),
+        ("TRIPLED_VALUE", "3"),
+        ("DOUBLED_VALUE", "4"),
+    ];
+    let config =
+        unsafe { common::with_env_vars(ENV_KEYS_VALUES, || LocalParserTest::from_env().unwrap()) };
+
+    assert_eq!(config.local_value, "SHOUT");
+    assert_eq!(config.tripled_value, 9);
+    assert_eq!(config.doubled_value, Some(8));
+}
+
+fn parse_csv<T: std::str::FromStr>(s: String) -> Vec<T>
+where
+    T::Err: std::fmt::Debug,
+{
+    s.split(',')
+        .map(|item| item.trim().parse().expect("Invalid list item"))
+        .collect()
+}
+
+#[test]
+fn should_parse_with_generic_parsers() {
+    #[derive(Debug, EnvConfig)]
+    #[env_cfg(no_prefix)]
+    struct GenericParserTest {
+        #[env_cfg(parse_with = "parse_csv::<u16>")]
+        ports: Vec<u16>, // -> PORTS (turbofish)
+        #[env_cfg(parse_with = "parse_csv::<f64>")]
+        weights: Option<Vec<f64>>, // -> WEIGHTS (optional, turbofish)
+        #[env_cfg(parse_with = "parse_csv")]
+        hosts: Vec<String>, // -> HOSTS (element type inferred from the field)
+    }
+
+    const ENV_KEYS_VALUES: &[(&str, &str)] = &[
+        ("PORTS", "80, 443"),
+        ("WEIGHTS", "0.5,1.5"),
+        ("HOSTS", "a.internal,b.internal"),
+    ];
+    let config = unsafe {
+        common::with_env_vars(ENV_KEYS_VALUES, || GenericParserTest::from_env().unwrap())
+    };
+
+    assert_eq!(config.ports, vec![80, 443]);
+    assert_eq!(config.weights, Some(vec![0.5, 1.5]));
+    assert_eq!(config.hosts, vec!["a.internal", "b.internal"]);
+}
+
+#[derive(Debug, PartialEq)]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+impl Point {
+    fn parse(value: String) -> Self {
+        let (x, y) = value.split_once(',').expect("points are `x,y`");
+        Point {
+            x: x.trim().parse().expect("Invalid x"),
+            y: y.trim().parse().expect("Invalid y"),
+        }
+    }
+}
+
+#[test]
+fn should_parse_with_associated_functions() {
+    #[derive(Debug, EnvConfig)]
+    #[env_cfg(prefix = "CANVAS")]
+    struct AssociatedParserTest {
+        #[env_cfg(parse_with = "Point::parse")]
+        origin: Point, // -> CANVAS_ORIGIN
+        #[env_cfg(parse_with = "Point::parse", default = "100, 50")]
+        size: Point, // -> CANVAS_SIZE (default parsed with `Point::parse`)
+        #[env_cfg(parse_with = "<Point>::parse")]
+        cursor: Option<Point>, // -> CANVAS_CURSOR
+    }
+
+    const ENV_KEYS_VALUES: &[(&str, &str)] = &[("CANVAS_ORIGIN", "1,2"), ("CANVAS_CURSOR", "3, 4")];
+    let config = unsafe {
+        common::with_env_vars(ENV_KEYS_VALUES, || {
+            AssociatedParserTest::from_env().unwrap()
+        })
+    };
+
+    assert_eq!(config.origin, Point { x: 1, y: 2 });
+    assert_eq!(config.size, Point { x: 100, y: 50 });
+    assert_eq!(config.cursor, Some(Point { x: 3, y: 4 }));
+}
+
+#[test]
+fn should_parse_optional_fields_with_defaults() {
+    #[derive(Debug, EnvConfig)]
+    #[env_cfg(no_prefix)]
+    struct OptionalDefaultsTest {
+        #[env_cfg(default = "30")]
+        timeout: Option<u64>, // -> TIMEOUT (optional with default)
+        #[env_cfg(default = "localhost")]
+        host: Option<String>, // -> HOST (optional with default)
+    }
+
+    const ENV_KEYS_VALUES: &[(&str, &str)] = &[("HOST", "example.com")];
+    let config = unsafe {
+        common::with_env_vars(ENV_KEYS_VALUES, || {
+            OptionalDefaultsTest::from_env().unwrap()
+        })
+    };
+
+    assert_eq!(config.timeout, Some(30)); // default
+    assert_eq!(config.host, Some("example.com".to_string()));
+}
+
+#[test]
+fn should_default_from_earlier_field() {
+    #[derive(Debug, EnvConfig)]
+    #[env_cfg(prefix = "SERVER")]
+    struct DefaultFromTest {
+        bind_addr: String, // -> SERVER_BIND_ADDR
+        #[env_cfg(default_from = "bind_addr")]
+        public_url: String, // -> SERVER_PUBLIC_URL (defaults to bind_addr)
+        #[env_cfg(default_from = "bind_addr")]
+        admin_url: String, // -> SERVER_ADMIN_URL (defaults to bind_addr)
+    }
+
+    const ENV_KEYS_VALUES: &[(&str, &str)] = &[
+        ("SERVER_BIND_ADDR", "0.0.0.0:8080"),
+        ("SERVER_ADMIN_URL", "127.0.0.1:9090"),
+    ];
+    let config =
+        unsafe { common::with_env_vars(ENV_KEYS_VALUES, || DefaultFromTest::from_env().unwrap()) };
+
+    assert_eq!(config.bind_addr, "0.0.0.0:8080");
+    assert_eq!(config.public_url, "0.0.0.0:8080"); // from bind_addr
+    assert_eq!(config.admin_url, "127.0.0.1:9090");
+}
+
+#[derive(Debug, PartialEq)]
+struct Credentials {
+    user: String,
+    password: String,
+}
+
+fn load_credentials(source: &dyn EnvSource) -> Result<Credentials, EnvConfigError> {
+    let user = source
+        .get("CREDENTIALS_USER")?
+        .ok_or_else(|| EnvConfigError::Missing("CREDENTIALS_USER".to_string()))?;
+    let password = source.get("CREDENTIALS_PASSWORD")?.unwrap_or_default();
+    Ok(Credentials { user, password })
+}
+
+#[test]
+fn should_load_field_with_source_function() {
+    #[derive(Debug, EnvConfig)]
+    #[env_cfg(no_prefix)]
+    struct ParseWithEnvTest {
+        #[env_cfg(parse_with_env = "load_credentials")]
+        credentials: Credentials, // -> CREDENTIALS_USER, CREDENTIALS_PASSWORD
+    }
+
+    const ENV_KEYS_VALUES: &[(&str, &str)] = &[
+        ("CREDENTIALS_USER", "admin"),
+        ("CREDENTIALS_PASSWORD", "hunter2"),
+    ];
+    let config =
+        unsafe { common::with_env_vars(ENV_KEYS_VALUES, || ParseWithEnvTest::from_env().unwrap()) };
+
+    assert_eq!(
+        config.credentials,
+        Credentials {
+            user: "admin".to_string(),
+            password: "hunter2".to_string(),
+        }
+    );
+
+    let result = unsafe { common::with_env_vars(&[], ParseWithEnvTest::from_env) };
+    assert!(matches!(result, Err(EnvConfigError::Missing(var)) if var == "CREDENTIALS_USER"));
+}
+
+#[test]
+fn should_capture_field_errors_in_result_fields() {
+    #[derive(Debug, EnvConfig)]
+    #[env_cfg(prefix = "BEST_EFFORT")]
+    struct BestEffortTest {
+        name: String,                        // -> BEST_EFFORT_NAME
+        port: Result<u16, EnvConfigError>,   // -> BEST_EFFORT_PORT (error captured)
+        workers: Result<u8, EnvConfigError>, // -> BEST_EFFORT_WORKERS (error captured)
+        #[env_cfg(default = "30")]
+        timeout: Result<u64, EnvConfigError>, // -> BEST_EFFORT_TIMEOUT (with default)
+        level: Result<Option<u8>, EnvConfigError>, // -> BEST_EFFORT_LEVEL (optional)
+    }
+
+    const ENV_KEYS_VALUES: &[(&str, &str)] = &[
+        ("BEST_EFFORT_NAME", "app"),
+        ("BEST_EFFORT_PORT", "not a port"),
+    ];
+    let config =
+        unsafe { common::with_env_vars(ENV_KEYS_VALUES, || BestEffortTest::from_env().unwrap()) };
+
+    assert_eq!(config.name, "app");
+    assert!(matches!(config.port, Err(EnvConfigError::Parse(var, _)) if var == "BEST_EFFORT_PORT"));
+    assert!(
+        matches!(config.workers, Err(EnvConfigError::Missing(var)) if var == "BEST_EFFORT_WORKERS")
+    );
+    assert_eq!(config.timeout.unwrap(), 30);
+    assert_eq!(config.level.unwrap(), None);
+}
+
+#[test]
+fn should_parse_defaults_with_custom_parser() {
+    #[derive(Debug, EnvConfig)]
+    #[env_cfg(no_prefix)]
+    struct ParseWithDefaultTest {
+        #[env_cfg(parse_with = "parse_custom_struct", default = "1,origin")]
+        origin: CustomStruct, // -> ORIGIN (custom parser, with default)
+        #[env_cfg(parse_with = "parse_custom_struct", default = "0,target")]
+        target: CustomStruct, // -> TARGET (custom parser, with default)
+    }
+
+    const ENV_KEYS_VALUES: &[(&str, &str)] = &[("TARGET", "7,custom")];
+    let config = unsafe {
+        common::with_env_vars(ENV_KEYS_VALUES, || {
+            ParseWithDefaultTest::from_env().unwrap()
+        })
+    };
+
+    assert_eq!(
+        config.origin,
+        CustomStruct {
+            value: 1,
+            name: "origin".to_string()
+        }
+    );
+    assert_eq!(
+        config.target,
+        CustomStruct {
+            value: 7,
+            name: "custom".to_string()
+        }
+    );
+}
+
+#[derive(Debug, EnvConfig)]
+#[env_cfg(prefix = "ANCHOR")]
+struct OptionalParseWithDefaultTest {
+    #[env_cfg(parse_with = "parse_custom_struct", default = "0,origin")]
+    point: Option<CustomStruct>, // -> ANCHOR_POINT (custom parser, default when unset)
+}
+
+#[test]
+fn should_parse_defaults_of_optional_fields_with_custom_parser() {
+    const ENV_KEYS_VALUES: &[(&str, &str)] = &[("ANCHOR_POINT", "3,custom")];
+    let present = unsafe {
+        common::with_env_vars(ENV_KEYS_VALUES, || {
+            OptionalParseWithDefaultTest::from_env().unwrap()
+        })
+    };
+    assert_eq!(
+        present.point,
+        Some(CustomStruct {
+            value: 3,
+            name: "custom".to_string()
+        })
+    );
+
+    let absent =
+        unsafe { common::with_env_vars(&[], || OptionalParseWithDefaultTest::from_env().unwrap()) };
+    assert_eq!(
+        absent.point,
+        Some(CustomStruct {
+            value: 0,
+            name: "origin".to_string()
+        })
+    );
+
+    // The parser decides how malformed values fail, here by panicking
+    const MALFORMED: &[(&str, &str)] = &[("ANCHOR_POINT", "not-a-point")];
+    let malformed = std::panic::catch_unwind(|| unsafe {
+        common::with_env_vars(MALFORMED, OptionalParseWithDefaultTest::from_env)
+    });
+    assert!(malformed.is_err());
+}
+
+#[derive(Debug, EnvConfig)]
+#[env_cfg(prefix = "POOL", validate = "check_pool")]
+struct ValidatedPoolConfig {
+    min_size: u32, // -> POOL_MIN_SIZE
+    max_size: u32, // -> POOL_MAX_SIZE
+}
+
+fn check_pool(config: &ValidatedPoolConfig) -> Result<(), String> {
+    if config.min_size > config.max_size {
+        return Err(format!(
+            "min_size ({}) must not exceed max_size ({})",
+            config.min_size, config.max_size
+        ));
+    }
+    Ok(())
+}
+
+#[test]
+fn should_validate_struct_after_loading() {
+    const VALID: &[(&str, &str)] = &[("POOL_MIN_SIZE", "2"), ("POOL_MAX_SIZE", "10")];
+    const INVALID: &[(&str, &str)] = &[("POOL_MIN_SIZE", "20"), ("POOL_MAX_SIZE", "10")];
+    let valid = unsafe { common::with_env_vars(VALID, ValidatedPoolConfig::from_env) };
+    let invalid = unsafe { common::with_env_vars(INVALID, ValidatedPoolConfig::from_env) };
+
+    let valid = valid.unwrap();
+    assert_eq!((valid.min_size, valid.max_size), (2, 10));
+    assert!(matches!(
+        invalid,
+        Err(EnvConfigError::Invalid(config, msg))
+            if config == "ValidatedPoolConfig" && msg == "min_size (20) must not exceed max_size (10)"
+    ));
+}
+
+#[derive(Debug, EnvConfig, PartialEq)]
+#[env_cfg(prefix = "WORKER", constructor)]
+struct ConstructedConfig {
+    queue: String,
+    #[env_cfg(default = "4")]
+    concurrency: u8,
+    #[env_cfg(skip)]
+    label: Option<String>,
+}
+
+#[test]
+fn should_generate_constructor_taking_every_field() {
+    const ENV_KEYS_VALUES: &[(&str, &str)] = &[("WORKER_QUEUE", "jobs")];
+
+    let constructed = ConstructedConfig::new("jobs".to_string(), 4, None);
+    let loaded = unsafe {
+        common::with_env_vars(ENV_KEYS_VALUES, || ConstructedConfig::from_env().unwrap())
+    };
+
+    assert_eq!(constructed, loaded);
+}
+
+#[derive(Debug, EnvConfig)]
+#[env_cfg(prefix = "SERVER", keys)]
+struct KeyedConfig {
+    port: u16,
+    #[env_cfg(env = "BIND_ADDRESS")]
+    host: String,
+    workers: Option<u32>,
+    timeout_secs: Option<u64>,
+    #[env_cfg(skip)]
+    #[allow(dead_code)]
+    started: bool,
+}
+
+#[test]
+fn should_generate_key_enum_for_fields() {
+    const ENV_KEYS_VALUES: &[(&str, &str)] = &[
+        ("SERVER_PORT", "8080"),
+        ("BIND_ADDRESS", "::1"),
+        ("SERVER_WORKERS", "4"),
+    ];
+    let config =
+        unsafe { common::with_env_vars(ENV_KEYS_VALUES, || KeyedConfig::from_env().unwrap()) };
+
+    assert_eq!(KeyedConfig::env_name(KeyedConfigKey::Port), "SERVER_PORT");
+    assert_eq!(KeyedConfig::env_name(KeyedConfigKey::Host), "BIND_ADDRESS");
+    assert_eq!(config.get(KeyedConfigKey::Port).as_deref(), Some("8080"));
+    assert_eq!(config.get(KeyedConfigKey::Host).as_deref(), Some("::1"));
+    assert_eq!(config.get(KeyedConfigKey::Workers).as_deref(), Some("4"));
+    assert_eq!(config.get(KeyedConfigKey::TimeoutSecs), None);
+}
+
+#[derive(Debug, EnvConfig)]
+#[env_cfg(no_prefix)]
+struct ReplicaConfig {
+    #[env_cfg(default_env = "DATABASE_URL")]
+    read_database_url: String, // -> READ_DATABASE_URL, then DATABASE_URL
+    #[env_cfg(default_env = "POOL_SIZE", default = "5")]
+    read_pool_size: u32, // -> READ_POOL_SIZE, then POOL_SIZE, then 5
+}
+
+#[test]
+fn should_fall_back_to_default_env_variable() {
+    const PRIMARY: &[(&str, &str)] = &[
+        ("READ_DATABASE_URL", "postgres://replica"),
+        ("DATABASE_URL", "postgres://primary"),
+    ];
+    const FALLBACK: &[(&str, &str)] = &[("DATABASE_URL", "postgres://primary"), ("POOL_SIZE", "8")];
+    const INVALID: &[(&str, &str)] = &[("DATABASE_URL", "postgres://primary"), ("POOL_SIZE", "x")];
+
+    let primary = unsafe { common::with_env_vars(PRIMARY, || ReplicaConfig::from_env().unwrap()) };
+    let fallback =
+        unsafe { common::with_env_vars(FALLBACK, || ReplicaConfig::from_env().unwrap()) };
+    let invalid = unsafe { common::with_env_vars(INVALID, ReplicaConfig::from_env) };
+    let (missing, missing_vars) = unsafe {
+        common::with_env_vars(&[], || {
+            (ReplicaConfig::from_env(), ReplicaConfig::missing_env_vars())
+        })
+    };
+
+    assert_eq!(primary.read_database_url, "postgres://replica");
+    assert_eq!(primary.read_pool_size, 5);
+    assert_eq!(fallback.read_database_url, "postgres://primary");
+    assert_eq!(fallback.read_pool_size, 8);
+    assert!(matches!(
+        invalid,
+        Err(EnvConfigError::Parse(var, _)) if var == "READ_POOL_SIZE (or POOL_SIZE)"
+    ));
+    assert!(matches!(
+        missing,
+        Err(EnvConfigError::Missing(var)) if var == "READ_DATABASE_URL (or DATABASE_URL)"
+    ));
+    assert_eq!(missing_vars, ["READ_DATABASE_URL (or DATABASE_URL)"]);
+}
+
+#[derive(Debug, EnvConfig)]
+#[env_cfg(prefix = "WORKER")]
+struct GuardedSkipConfig {
+    queue: String,
+    #[env_cfg(skip, error_if_set)]
+    scratch: Vec<String>, // WORKER_SCRATCH must not be set
+    #[env_cfg(skip, error_if_set, env = "LEGACY_THREADS")]
+    threads: u8,
+}
+
+#[test]
+fn should_err_if_variable_of_guarded_skip_field_is_set() {
+    const UNSET: &[(&str, &str)] = &[("WORKER_QUEUE", "jobs")];
+    const SET: &[(&str, &str)] = &[("WORKER_QUEUE", "jobs"), ("LEGACY_THREADS", "4")];
+
+    let unset = unsafe { common::with_env_vars(UNSET, GuardedSkipConfig::from_env) };
+    let set = unsafe { common::with_env_vars(SET, GuardedSkipConfig::from_env) };
+
+    let unset = unset.unwrap();
+    assert_eq!(unset.queue, "jobs");
+    assert!(unset.scratch.is_empty());
+    assert_eq!(unset.threads, 0);
+    assert_eq!(
+        set.unwrap_err(),
+        EnvConfigError::Invalid(
+            "LEGACY_THREADS".into(),
+            "set, but field `threads` is skipped and ignores it".into()
+        )
+    );
+}
+
+fn parse_weighted(value: String) -> Vec<(String, u32)> {
+    value
+        .split(',')
+        .map(|entry| {
+            let (name, weight) = entry.split_once(':').expect("entries are `name:weight`");
+            (
+                name.to_string(),
+                weight.parse().expect("weights are integers"),
+            )
+        })
+        .collect()
+}
+
+#[derive(Debug, EnvConfig)]
+#[env_cfg(prefix = "BALANCER")]
+struct WeightedConfig {
+    #[env_cfg(parse_with = "parse_weighted")]
+    backends: Vec<(String, u32)>, // -> BALANCER_BACKENDS
+    #[env_cfg(parse_with = "parse_weighted")]
+    fallbacks: Option<Vec<(String, u32)>>, // -> BALANCER_FALLBACKS (optional)
+    #[env_cfg(parse_with = "parse_weighted", default = "local:1")]
+    canaries: Vec<(String, u32)>, // -> BALANCER_CANARIES (with default)
+}
+
+#[test]
+fn should_parse_whole_list_with_custom_parser() {
+    const ENV_KEYS_VALUES: &[(&str, &str)] = &[("BALANCER_BACKENDS", "a:3,b:1")];
+
+    let config =
+        unsafe { common::with_env_vars(ENV_KEYS_VALUES, || WeightedConfig::from_env().unwrap()) };
+
+    assert_eq!(
+        config.backends,
+        [("a".to_string(), 3), ("b".to_string(), 1)]
+    );
+    assert_eq!(config.fallbacks, None);
+    assert_eq!(config.canaries, [("local".to_string(), 1)]);
+
+    const WITH_FALLBACKS: &[(&str, &str)] =
+        &[("BALANCER_BACKENDS", "a:3"), ("BALANCER_FALLBACKS", "c:2")];
+    let config =
+        unsafe { common::with_env_vars(WITH_FALLBACKS, || WeightedConfig::from_env().unwrap()) };
+
+    assert_eq!(config.fallbacks, Some(vec![("c".to_string(), 2)]));
+}
+
+#[derive(Debug, PartialEq)]
+enum Verbosity {
+    Quiet,
+    Normal,
+    Loud,
+}
+
+impl std::str::FromStr for Verbosity {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "quiet" => Ok(Verbosity::Quiet),
+            "normal" => Ok(Verbosity::Normal),
+            "loud" => Ok(Verbosity::Loud),
+            other => Err(format!("unknown verbosity '{other}'")),
+        }
+    }
+}
+
+#[derive(Debug, EnvConfig)]
+#[env_cfg(prefix = "CLI")]
+struct TypedDefaultConfig {
+    #[env_cfg(default = Verbosity::Normal)]
+    verbosity: Verbosity, // -> CLI_VERBOSITY (defaults to a variant)
+    #[env_cfg(default = Verbosity::Quiet)]
+    log_verbosity: Option<Verbosity>, // -> CLI_LOG_VERBOSITY
+    #[env_cfg(default = 4)]
+    jobs: usize, // -> CLI_JOBS
+}
+
+#[test]
+fn should_use_typed_defaults() {
+    let unset = unsafe { common::with_env_vars(&[], || TypedDefaultConfig::from_env().unwrap()) };
+
+    assert_eq!(unset.verbosity, Verbosity::Normal);
+    assert_eq!(unset.log_verbosity, Some(Verbosity::Quiet));
+    assert_eq!(unset.jobs, 4);
+
+    const ENV_KEYS_VALUES: &[(&str, &str)] = &[
+        ("CLI_VERBOSITY", "loud"),
+        ("CLI_LOG_VERBOSITY", "normal"),
+        ("CLI_JOBS", "16"),
+    ];
+    let set = unsafe {
+        common::with_env_vars(ENV_KEYS_VALUES, || TypedDefaultConfig::from_env().unwrap())
+    };
+
+    assert_eq!(set.verbosity, Verbosity::Loud);
+    assert_eq!(set.log_verbosity, Some(Verbosity::Normal));
+    assert_eq!(set.jobs, 16);
+    assert!(TypedDefaultConfig::missing_env_vars().is_empty());
+}
+
+struct ServiceDefaults {
+    port: u16,
+    host: &'static str,
+    retries: Option<u8>,
+}
+
+static SERVICE_DEFAULTS: ServiceDefaults = ServiceDefaults {
+    port: 8080,
+    host: "0.0.0.0",
+    retries: Some(3),
+};
+
+const fn default_timeout_secs() -> u64 {
+    30
+}
+
+#[derive(Debug, EnvConfig)]
+#[env_cfg(prefix = "SERVICE")]
+struct SharedDefaultConfig {
+    #[env_cfg(default = SERVICE_DEFAULTS.port)]
+    port: u16, // -> SERVICE_PORT (defaults to a field of a static)
+    #[env_cfg(default = SERVICE_DEFAULTS.host.to_string())]
+    host: String, // -> SERVICE_HOST
+    #[env_cfg(default = SERVICE_DEFAULTS.retries.unwrap_or(1))]
+    retries: Option<u8>, // -> SERVICE_RETRIES
+    #[env_cfg(default = default_timeout_secs() * 2)]
+    timeout_secs: u64, // -> SERVICE_TIMEOUT_SECS
+}
+
+#[test]
+fn should_use_expressions_referencing_items_as_defaults() {
+    let unset = unsafe { common::with_env_vars(&[], || SharedDefaultConfig::from_env().unwrap()) };
+
+    assert_eq!(unset.port, 8080);
+    assert_eq!(unset.host, "0.0.0.0");
+    assert_eq!(unset.retries, Some(3));
+    assert_eq!(unset.timeout_secs, 60);
+
+    const ENV_KEYS_VALUES: &[(&str, &str)] = &[("SERVICE_PORT", "9090")];
+    let set = unsafe {
+        common::with_env_vars(ENV_KEYS_VALUES, || SharedDefaultConfig::from_env().unwrap())
+    };
+
+    assert_eq!(set.port, 9090);
+    assert_eq!(set.host, "0.0.0.0");
 }
@@ -0,0 +1,99 @@
+// Tests for fields split from a single variable with `derive_from`
+use env_cfg::{EnvConfig, EnvConfigError};
+
+mod common;
+
+/// Split `postgres://host:port/database` into the variables of the config's fields
+fn split_database_url(url: String) -> Result<Vec<(&'static str, String)>, String> {
+    let rest = url
+        .strip_prefix("postgres://")
+        .ok_or("expected a postgres:// URL")?;
+    let (address, database) = rest.split_once('/').ok_or("missing database name")?;
+    let (host, port) = address.split_once(':').unwrap_or((address, "5432"));
+    Ok(vec![
+        ("DB_HOST", host.to_string()),
+        ("DB_PORT", port.to_string()),
+        ("DB_DATABASE", database.to_string()),
+    ])
+}
+
+#[derive(Debug, EnvConfig)]
+#[env_cfg(
+    prefix = "DB",
+    derive_from = "DATABASE_URL",
+    derive_with = "split_database_url"
+)]
+struct DatabaseConfig {
+    host: String,     // -> DB_HOST
+    port: u16,        // -> DB_PORT
+    database: String, // -> DB_DATABASE
+    #[env_cfg(default = "10")]
+    pool_size: u32, // -> DB_POOL_SIZE (not part of the URL)
+}
+
+#[test]
+fn should_load_fields_split_from_one_variable() {
+    const ENV_KEYS_VALUES: &[(&str, &str)] = &[
+        ("DATABASE_URL", "postgres://db.internal:6432/billing"),
+        ("DB_POOL_SIZE", "20"),
+    ];
+
+    let config =
+        unsafe { common::with_env_vars(ENV_KEYS_VALUES, || DatabaseConfig::from_env().unwrap()) };
+
+    assert_eq!(config.host, "db.internal");
+    assert_eq!(config.port, 6432);
+    assert_eq!(config.database, "billing");
+    assert_eq!(config.pool_size, 20);
+}
+
+#[test]
+fn should_prefer_variables_set_per_field() {
+    const ENV_KEYS_VALUES: &[(&str, &str)] = &[
+        ("DATABASE_URL", "postgres://db.internal/billing"),
+        ("DB_HOST", "replica.internal"),
+    ];
+
+    let config =
+        unsafe { common::with_env_vars(ENV_KEYS_VALUES, || DatabaseConfig::from_env().unwrap()) };
+
+    assert_eq!(config.host, "replica.internal");
+    assert_eq!(config.port, 5432);
+    assert_eq!(config.database, "billing");
+}
+
+#[test]
+fn should_load_fields_individually_without_split_variable() {
+    const ENV_KEYS_VALUES: &[(&str, &str)] = &[
+        ("DB_HOST", "localhost"),
+        ("DB_PORT", "5432"),
+        ("DB_DATABASE", "billing"),
+    ];
+
+    let config =
+        unsafe { common::with_env_vars(ENV_KEYS_VALUES, || DatabaseConfig::from_env().unwrap()) };
+    let missing = unsafe { common::with_env_vars(&[], DatabaseConfig::missing_env_vars) };
+
+    assert_eq!(config.host, "localhost");
+    assert_eq!(missing, ["DB_HOST", "DB_PORT", "DB_DATABASE"]);
+}
+
+#[test]
+fn should_err_if_split_variable_is_invalid() {
+    const ENV_KEYS_VALUES: &[(&str, &str)] = &[("DATABASE_URL", "mysql://localhost/billing")];
+
+    let (result, missing) = unsafe {
+        common::with_env_vars(ENV_KEYS_VALUES, || {
+            (
+                DatabaseConfig::from_env(),
+                DatabaseConfig::missing_env_vars(),
+            )
+        })
+    };
+
+    assert_eq!(
+        result.unwrap_err(),
+        EnvConfigError::Parse("DATABASE_URL".into(), "expected a postgres:// URL".into())
+    );
+    assert_eq!(missing, ["DB_HOST", "DB_PORT", "DB_DATABASE"]);
+}
@@ -0,0 +1,124 @@
+// `dotted` naming and dotted `env` keys, loaded from maps like config sources that use dotted
+// namespacing
+use std::collections::HashMap;
+
+use env_cfg::{EnvConfig, EnvConfigError};
+
+const SERVICE_PREFIX: &str = "Service";
+
+#[derive(Debug, EnvConfig, PartialEq)]
+#[env_cfg(dotted, prefix = "database")]
+struct DottedDatabaseConfig {
+    host: String, // -> database.host
+    #[env_cfg(default = "5432")]
+    port: u16, // -> database.port (with default)
+}
+
+#[derive(Debug, EnvConfig, PartialEq)]
+#[env_cfg(dotted, prefix = "app", propagate_prefix)]
+struct DottedAppConfig {
+    #[env_cfg(nested)]
+    database: DottedDatabaseConfig, // -> app.database.*
+    #[env_cfg(env = "app.log.level")]
+    log_level: String, // -> app.log.level (verbatim)
+    #[env_cfg(env = "{prefix}.region")]
+    region: Option<String>, // -> app.region
+}
+
+#[derive(Debug, EnvConfig, PartialEq)]
+#[env_cfg(dotted)]
+struct ServerConfig {
+    max_connections: u32, // -> server_config.max_connections
+}
+
+#[derive(Debug, EnvConfig, PartialEq)]
+#[env_cfg(dotted, prefix = SERVICE_PREFIX)]
+struct DottedServiceConfig {
+    name: String, // -> service.name
+}
+
+#[derive(Debug, EnvConfig, PartialEq)]
+struct UndottedConfig {
+    #[env_cfg(env = "legacy.endpoint")]
+    endpoint: String, // -> legacy.endpoint (verbatim)
+}
+
+fn map(entries: &[(&str, &str)]) -> HashMap<String, String> {
+    entries
+        .iter()
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect()
+}
+
+// NOTE: these tests do not need to run with `common::with_env_vars` because they never read
+// ENV variables
+
+#[test]
+fn should_load_dotted_hierarchy() {
+    let vars = map(&[
+        ("app.database.host", "db.internal"),
+        ("app.log.level", "debug"),
+        ("app.region", "eu-west"),
+    ]);
+    let config = DottedAppConfig::from_source(&vars).unwrap();
+    assert_eq!(
+        config,
+        DottedAppConfig {
+            database: DottedDatabaseConfig {
+                host: "db.internal".to_string(),
+                port: 5432,
+            },
+            log_level: "debug".to_string(),
+            region: Some("eu-west".to_string()),
+        }
+    );
+}
+
+#[test]
+fn should_name_dotted_keys_in_errors() {
+    let vars = map(&[("app.log.level", "debug")]);
+    assert_eq!(
+        DottedAppConfig::missing_vars(&vars),
+        vec!["app.database.host".to_string()]
+    );
+
+    let vars = map(&[
+        ("app.database.host", "db.internal"),
+        ("app.database.port", "not-a-port"),
+        ("app.log.level", "debug"),
+    ]);
+    let error = DottedAppConfig::from_source(&vars).unwrap_err();
+    assert!(matches!(error, EnvConfigError::Nested { .. }), "{error:?}");
+    assert_eq!(error.var_name(), Some("app.database.port"));
+}
+
+#[test]
+fn should_derive_dotted_keys_from_struct_name_and_const_prefix() {
+    let vars = map(&[
+        ("server_config.max_connections", "64"),
+        ("service.name", "billing"),
+    ]);
+    assert_eq!(
+        ServerConfig::from_source(&vars).unwrap(),
+        ServerConfig {
+            max_connections: 64
+        }
+    );
+    assert_eq!(
+        DottedServiceConfig::from_source(&vars).unwrap(),
+        DottedServiceConfig {
+            name: "billing".to_string(),
+        }
+    );
+}
+
+#[test]
+fn should_read_dotted_env_names_verbatim() {
+    let vars = map(&[("legacy.endpoint", "https://example.com")]);
+    assert_eq!(
+        UndottedConfig::from_source(&vars).unwrap(),
+        UndottedConfig {
+            endpoint: "https://example.com".to_string(),
+        }
+    );
+}
@@ -0,0 +1,56 @@
+// `duration_secs_f64` parsing tests
+use std::time::Duration;
+
+use env_cfg::{EnvConfig, EnvConfigError, parse_duration_secs_f64};
+
+mod common;
+
+#[derive(Debug, EnvConfig)]
+#[env_cfg(no_prefix)]
+struct PollingConfig {
+    #[env_cfg(duration_secs_f64)]
+    poll_interval: Duration, // -> POLL_INTERVAL
+    #[env_cfg(duration_secs_f64)]
+    poll_jitter: Option<Duration>, // -> POLL_JITTER (optional)
+    #[env_cfg(duration_secs_f64, default = "2.5")]
+    poll_timeout: Duration, // -> POLL_TIMEOUT (with default)
+}
+
+#[test]
+fn should_parse_fractional_seconds() {
+    const ENV_KEYS_VALUES: &[(&str, &str)] = &[("POLL_INTERVAL", "0.5"), ("POLL_JITTER", "3")];
+    let config =
+        unsafe { common::with_env_vars(ENV_KEYS_VALUES, || PollingConfig::from_env().unwrap()) };
+
+    assert_eq!(config.poll_interval, Duration::from_millis(500));
+    assert_eq!(config.poll_jitter, Some(Duration::from_secs(3)));
+    assert_eq!(config.poll_timeout, Duration::from_millis(2500));
+}
+
+#[test]
+fn should_err_on_invalid_fractional_seconds() {
+    for value in ["-1", "NaN", "inf", "1e300", "soon"] {
+        let result =
+            unsafe { common::with_env_vars(&[("POLL_INTERVAL", value)], PollingConfig::from_env) };
+        assert!(
+            matches!(&result, Err(EnvConfigError::Parse(var, _)) if var == "POLL_INTERVAL"),
+            "{value}: {result:?}"
+        );
+    }
+}
+
+#[test]
+fn should_explain_rejected_durations() {
+    assert_eq!(
+        parse_duration_secs_f64("-0.5"),
+        Err("Duration must not be negative, got `-0.5`".to_string())
+    );
+    assert_eq!(
+        parse_duration_secs_f64("NaN"),
+        Err("Duration must be a finite number of seconds, got `NaN`".to_string())
+    );
+    assert_eq!(
+        parse_duration_secs_f64("0.25"),
+        Ok(Duration::from_millis(250))
+    );
+}
@@ -0,0 +1,96 @@
+// Empty value handling tests
+use env_cfg::{EnvConfig, EnvConfigError};
+
+mod common;
+
+#[derive(Debug, EnvConfig)]
+#[env_cfg(no_prefix)]
+struct FieldEmptyConfig {
+    #[env_cfg(empty_as_none)]
+    empty_timeout: Option<u64>, // -> EMPTY_TIMEOUT (empty is None)
+    empty_label: Option<String>, // -> EMPTY_LABEL (empty is kept)
+    #[env_cfg(empty_as_none, default = "8080")]
+    empty_port: u16, // -> EMPTY_PORT (empty uses default)
+}
+
+#[derive(Debug, EnvConfig)]
+#[env_cfg(prefix = "EMPTY", empty_as_none)]
+struct StructEmptyConfig {
+    timeout: Option<u64>, // -> EMPTY_TIMEOUT (empty is None)
+    #[env_cfg(allow_empty)]
+    label: Option<String>, // -> EMPTY_LABEL (opts out, empty is kept)
+    name: String,         // -> EMPTY_NAME (empty is missing)
+}
+
+#[test]
+fn should_treat_empty_as_none_per_field() {
+    const ENV_KEYS_VALUES: &[(&str, &str)] = &[
+        ("EMPTY_TIMEOUT", ""),
+        ("EMPTY_LABEL", ""),
+        ("EMPTY_PORT", ""),
+    ];
+    let config =
+        unsafe { common::with_env_vars(ENV_KEYS_VALUES, || FieldEmptyConfig::from_env().unwrap()) };
+
+    assert_eq!(config.empty_timeout, None);
+    assert_eq!(config.empty_label, Some(String::new()));
+    assert_eq!(config.empty_port, 8080);
+}
+
+#[test]
+fn should_allow_fields_to_opt_out_of_struct_policy() {
+    const ENV_KEYS_VALUES: &[(&str, &str)] = &[
+        ("EMPTY_TIMEOUT", ""),
+        ("EMPTY_LABEL", ""),
+        ("EMPTY_NAME", "name"),
+    ];
+    let config = unsafe {
+        common::with_env_vars(ENV_KEYS_VALUES, || StructEmptyConfig::from_env().unwrap())
+    };
+
+    assert_eq!(config.timeout, None);
+    assert_eq!(config.label, Some(String::new()));
+    assert_eq!(config.name, "name");
+}
+
+#[test]
+fn should_err_if_required_value_is_empty() {
+    const ENV_KEYS_VALUES: &[(&str, &str)] = &[("EMPTY_NAME", "")];
+    let result = unsafe { common::with_env_vars(ENV_KEYS_VALUES, StructEmptyConfig::from_env) };
+
+    assert!(matches!(result, Err(EnvConfigError::Missing(var)) if var == "EMPTY_NAME"));
+}
+
+#[derive(Debug, EnvConfig)]
+#[env_cfg(no_prefix)]
+struct RequiredEmptyConfig {
+    #[env_cfg(required, empty_as_none)]
+    feature_limit: Option<u32>, // -> FEATURE_LIMIT (must be set, empty disables)
+}
+
+#[test]
+fn should_err_if_required_optional_is_missing() {
+    let result = unsafe { common::with_env_vars(&[], RequiredEmptyConfig::from_env) };
+
+    assert!(matches!(result, Err(EnvConfigError::Missing(var)) if var == "FEATURE_LIMIT"));
+}
+
+#[test]
+fn should_load_required_optional_as_none_if_empty() {
+    const ENV_KEYS_VALUES: &[(&str, &str)] = &[("FEATURE_LIMIT", "")];
+    let config = unsafe {
+        common::with_env_vars(ENV_KEYS_VALUES, || RequiredEmptyConfig::from_env().unwrap())
+    };
+
+    assert_eq!(config.feature_limit, None);
+}
+
+#[test]
+fn should_load_required_optional_as_some_if_set() {
+    const ENV_KEYS_VALUES: &[(&str, &str)] = &[("FEATURE_LIMIT", "10")];
+    let config = unsafe {
+        common::with_env_vars(ENV_KEYS_VALUES, || RequiredEmptyConfig::from_env().unwrap())
+    };
+
+    assert_eq!(config.feature_limit, Some(10));
+}
@@ -0,0 +1,72 @@
+// Error helper tests
+use std::error::Error;
+
+use env_cfg::{EnvConfigError, ResultExt, env_var};
+
+mod common;
+
+#[test]
+fn should_wrap_errors_with_context() {
+    let result = unsafe {
+        common::with_env_vars(&[], || {
+            env_var::<String>("DB_HOST").context("while loading database settings")
+        })
+    };
+
+    let Err(error) = result else {
+        panic!("Expected an error");
+    };
+    assert_eq!(
+        error.to_string(),
+        "while loading database settings: Missing environment variable: `DB_HOST`"
+    );
+    match &error {
+        EnvConfigError::Nested { context, source } => {
+            assert_eq!(context, "while loading database settings");
+            assert!(matches!(**source, EnvConfigError::Missing(ref var) if var == "DB_HOST"));
+        }
+        other => panic!("Expected Nested error, got {other:?}"),
+    }
+    assert!(error.source().is_some());
+}
+
+#[test]
+fn should_layer_multiple_contexts() {
+    let error = EnvConfigError::Parse("PORT".to_string(), "invalid digit".to_string())
+        .context("database")
+        .context("app");
+
+    assert_eq!(
+        error.to_string(),
+        "app: database: Failed to parse environment variable: 'PORT': invalid digit"
+    );
+}
+
+#[test]
+fn should_classify_each_variant() {
+    let missing = EnvConfigError::Missing("DB_HOST".to_string());
+    let parse = EnvConfigError::Parse("PORT".to_string(), "invalid digit".to_string());
+    let invalid = EnvConfigError::Invalid("PoolConfig".to_string(), "too small".to_string());
+
+    assert!(missing.is_missing() && !missing.is_parse() && !missing.is_invalid());
+    assert!(!parse.is_missing() && parse.is_parse() && !parse.is_invalid());
+    assert!(!invalid.is_missing() && !invalid.is_parse() && invalid.is_invalid());
+    assert_eq!(missing.var_name(), Some("DB_HOST"));
+    assert_eq!(parse.var_name(), Some("PORT"));
+    assert_eq!(invalid.var_name(), None);
+}
+
+#[test]
+fn should_classify_through_context() {
+    let nested = EnvConfigError::Parse("PORT".to_string(), "invalid digit".to_string())
+        .context("database")
+        .context("app");
+
+    assert!(nested.is_parse());
+    assert!(!nested.is_missing());
+    assert_eq!(nested.var_name(), Some("PORT"));
+    assert_eq!(
+        nested.root_cause(),
+        &EnvConfigError::Parse("PORT".to_string(), "invalid digit".to_string())
+    );
+}
@@ -0,0 +1,162 @@
+// `${VAR}` expansion tests
+use env_cfg::{EnvConfig, EnvConfigError, expand_vars, expand_vars_or_empty};
+
+mod common;
+
+#[derive(Debug, EnvConfig)]
+#[env_cfg(no_prefix)]
+struct FieldExpandConfig {
+    #[env_cfg(expand)]
+    log_dir: String, // -> LOG_DIR (expanded)
+    #[env_cfg(expand = "empty")]
+    cache_dir: Option<String>, // -> CACHE_DIR (expanded, unset references are empty)
+    raw_dir: String, // -> RAW_DIR (not expanded)
+}
+
+#[derive(Debug, EnvConfig)]
+#[env_cfg(prefix = "EXPAND", expand)]
+struct StructExpandConfig {
+    url: String, // -> EXPAND_URL (expanded)
+    #[env_cfg(default = "8080")]
+    port: u16, // -> EXPAND_PORT (expanded, with default)
+}
+
+#[test]
+fn should_expand_field_values() {
+    const ENV_KEYS_VALUES: &[(&str, &str)] = &[
+        ("TEST_HOME", "/home/app"),
+        ("LOG_DIR", "${TEST_HOME}/logs"),
+        ("CACHE_DIR", "${TEST_HOME}/cache${TEST_UNSET_SUFFIX}"),
+        ("RAW_DIR", "${TEST_HOME}/raw"),
+    ];
+    let config = unsafe {
+        common::with_env_vars(ENV_KEYS_VALUES, || FieldExpandConfig::from_env().unwrap())
+    };
+
+    assert_eq!(config.log_dir, "/home/app/logs");
+    assert_eq!(config.cache_dir, Some("/home/app/cache".to_string()));
+    assert_eq!(config.raw_dir, "${TEST_HOME}/raw");
+}
+
+#[test]
+fn should_err_if_expanded_variable_is_missing() {
+    const ENV_KEYS_VALUES: &[(&str, &str)] =
+        &[("LOG_DIR", "${TEST_HOME}/logs"), ("RAW_DIR", "/raw")];
+    let result = unsafe { common::with_env_vars(ENV_KEYS_VALUES, FieldExpandConfig::from_env) };
+
+    assert!(matches!(result, Err(EnvConfigError::Missing(var)) if var == "TEST_HOME"));
+}
+
+#[test]
+fn should_expand_all_fields_with_struct_attribute() {
+    const ENV_KEYS_VALUES: &[(&str, &str)] = &[
+        ("TEST_HOST", "localhost"),
+        ("TEST_PORT", "9090"),
+        ("EXPAND_URL", "http://${TEST_HOST}:${TEST_PORT}"),
+        ("EXPAND_PORT", "${TEST_PORT}"),
+    ];
+    let config = unsafe {
+        common::with_env_vars(ENV_KEYS_VALUES, || StructExpandConfig::from_env().unwrap())
+    };
+
+    assert_eq!(config.url, "http://localhost:9090");
+    assert_eq!(config.port, 9090);
+}
+
+#[test]
+fn should_expand_nested_references_and_escapes() {
+    const ENV_KEYS_VALUES: &[(&str, &str)] =
+        &[("TEST_ROOT", "/srv"), ("TEST_APP_DIR", "${TEST_ROOT}/app")];
+    let (nested, escaped, lone) = unsafe {
+        common::with_env_vars(ENV_KEYS_VALUES, || {
+            (
+                expand_vars("${TEST_APP_DIR}/data").unwrap(),
+                expand_vars("cost: $$5 (${TEST_ROOT})").unwrap(),
+                expand_vars("a $ b").unwrap(),
+            )
+        })
+    };
+
+    assert_eq!(nested, "/srv/app/data");
+    assert_eq!(escaped, "cost: $5 (/srv)");
+    assert_eq!(lone, "a $ b");
+}
+
+#[test]
+fn should_bound_expansion_depth() {
+    const ENV_KEYS_VALUES: &[(&str, &str)] = &[("TEST_LOOP", "${TEST_LOOP}")];
+    let result = unsafe { common::with_env_vars(ENV_KEYS_VALUES, || expand_vars("${TEST_LOOP}")) };
+
+    assert!(matches!(result, Err(EnvConfigError::Parse(var, _)) if var == "TEST_LOOP"));
+}
+
+#[test]
+fn should_err_on_unterminated_reference() {
+    let result = unsafe { common::with_env_vars(&[], || expand_vars_or_empty("${TEST_HOME")) };
+
+    // The value itself is never put into the error
+    assert_eq!(
+        result.unwrap_err(),
+        EnvConfigError::Invalid(
+            "value".to_string(),
+            "Unterminated `${` reference".to_string()
+        )
+    );
+}
+
+#[test]
+fn should_name_the_field_variable_on_unterminated_reference() {
+    const ENV_KEYS_VALUES: &[(&str, &str)] = &[
+        ("LOG_DIR", "${X"),
+        ("RAW_DIR", "/raw"),
+        ("TEST_NESTED", "s3cr${t"),
+    ];
+    let result = unsafe { common::with_env_vars(ENV_KEYS_VALUES, FieldExpandConfig::from_env) };
+
+    let error = result.unwrap_err();
+    assert_eq!(error.var_name(), Some("LOG_DIR"));
+    assert!(!error.to_string().contains("${X"), "{error}");
+
+    // A referenced variable whose own value is unterminated is named instead
+    const NESTED: &[(&str, &str)] = &[
+        ("LOG_DIR", "${TEST_NESTED}/logs"),
+        ("RAW_DIR", "/raw"),
+        ("TEST_NESTED", "s3cr${t"),
+    ];
+    let result = unsafe { common::with_env_vars(NESTED, FieldExpandConfig::from_env) };
+
+    let error = result.unwrap_err();
+    assert_eq!(error.var_name(), Some("TEST_NESTED"));
+    assert!(!error.to_string().contains("s3cr"), "{error}");
+}
+
+#[derive(Debug, EnvConfig)]
+#[env_cfg(no_prefix)]
+struct DefaultExpandConfig {
+    #[env_cfg(default = "${TEST_HOME}/cache", expand)]
+    data_dir: String, // -> DATA_DIR (expanded, default expanded too)
+    #[env_cfg(default = "${TEST_HOME}/tmp")]
+    temp_dir: String, // -> TEMP_DIR (not expanded)
+}
+
+#[test]
+fn should_expand_defaults() {
+    const ENV_KEYS_VALUES: &[(&str, &str)] = &[("TEST_HOME", "/home/app")];
+    let defaulted = unsafe {
+        common::with_env_vars(ENV_KEYS_VALUES, || DefaultExpandConfig::from_env().unwrap())
+    };
+
+    const ENV_KEYS_VALUES_SET: &[(&str, &str)] = &[
+        ("TEST_HOME", "/home/app"),
+        ("DATA_DIR", "${TEST_HOME}/data"),
+    ];
+    let set = unsafe {
+        common::with_env_vars(ENV_KEYS_VALUES_SET, || {
+            DefaultExpandConfig::from_env().unwrap()
+        })
+    };
+
+    assert_eq!(defaulted.data_dir, "/home/app/cache");
+    assert_eq!(defaulted.temp_dir, "${TEST_HOME}/tmp");
+    assert_eq!(set.data_dir, "/home/app/data");
+}
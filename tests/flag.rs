@@ -0,0 +1,130 @@
+// `flag` and `flags` parsing tests
+use std::collections::BTreeSet;
+
+use env_cfg::{EnvConfig, EnvConfigError, FromFlags};
+
+mod common;
+
+#[derive(Debug, EnvConfig)]
+#[env_cfg(prefix = "FEATURE", empty_as_none)]
+struct FeatureConfig {
+    #[env_cfg(flag)]
+    beta: bool, // -> FEATURE_BETA (false when unset)
+    #[env_cfg(flag, default = true)]
+    telemetry: bool, // -> FEATURE_TELEMETRY (true when unset)
+    #[env_cfg(flag)]
+    tracing: Option<bool>, // -> FEATURE_TRACING (None when unset)
+}
+
+#[test]
+fn should_use_default_when_unset() {
+    let config = unsafe { common::with_env_vars(&[], || FeatureConfig::from_env().unwrap()) };
+
+    assert!(!config.beta);
+    assert!(config.telemetry);
+    assert_eq!(config.tracing, None);
+    assert!(FeatureConfig::missing_vars(&std::collections::BTreeMap::new()).is_empty());
+}
+
+#[test]
+fn should_enable_flag_set_to_empty_value() {
+    // The struct-level `empty_as_none` does not apply to flags
+    const ENV_KEYS_VALUES: &[(&str, &str)] = &[
+        ("FEATURE_BETA", ""),
+        ("FEATURE_TELEMETRY", ""),
+        ("FEATURE_TRACING", ""),
+    ];
+    let config =
+        unsafe { common::with_env_vars(ENV_KEYS_VALUES, || FeatureConfig::from_env().unwrap()) };
+
+    assert!(config.beta);
+    assert!(config.telemetry);
+    assert_eq!(config.tracing, Some(true));
+}
+
+#[test]
+fn should_parse_flag_values() {
+    const ENV_KEYS_VALUES: &[(&str, &str)] = &[
+        ("FEATURE_BETA", "yes"),
+        ("FEATURE_TELEMETRY", "OFF"),
+        ("FEATURE_TRACING", "0"),
+    ];
+    let config =
+        unsafe { common::with_env_vars(ENV_KEYS_VALUES, || FeatureConfig::from_env().unwrap()) };
+
+    assert!(config.beta);
+    assert!(!config.telemetry);
+    assert_eq!(config.tracing, Some(false));
+
+    const INVALID: &[(&str, &str)] = &[("FEATURE_BETA", "maybe")];
+    let result = unsafe { common::with_env_vars(INVALID, FeatureConfig::from_env) };
+    assert!(matches!(result, Err(EnvConfigError::Parse(var, _)) if var == "FEATURE_BETA"));
+}
+
+#[derive(Debug, Default, PartialEq)]
+struct Features {
+    logging: bool,
+    metrics: bool,
+    tracing: bool,
+}
+
+impl FromFlags for Features {
+    fn from_flags(flags: &[&str]) -> Result<Self, String> {
+        let mut features = Features::default();
+        for flag in flags {
+            match *flag {
+                "logging" => features.logging = true,
+                "metrics" => features.metrics = true,
+                "tracing" => features.tracing = true,
+                other => return Err(format!("unknown feature `{other}`")),
+            }
+        }
+        Ok(features)
+    }
+}
+
+#[derive(Debug, EnvConfig)]
+#[env_cfg(prefix = "SERVICE")]
+struct ServiceFeaturesConfig {
+    #[env_cfg(flags)]
+    features: Features, // -> SERVICE_FEATURES
+    #[env_cfg(flags, default = "")]
+    tags: BTreeSet<String>, // -> SERVICE_TAGS (with default)
+}
+
+#[test]
+fn should_parse_flag_names() {
+    const ENV_KEYS_VALUES: &[(&str, &str)] = &[
+        ("SERVICE_FEATURES", "logging, tracing"),
+        ("SERVICE_TAGS", "edge,,beta"),
+    ];
+    let config = unsafe {
+        common::with_env_vars(ENV_KEYS_VALUES, || {
+            ServiceFeaturesConfig::from_env().unwrap()
+        })
+    };
+
+    assert_eq!(
+        config.features,
+        Features {
+            logging: true,
+            metrics: false,
+            tracing: true,
+        }
+    );
+    assert_eq!(config.tags, BTreeSet::from(["beta".into(), "edge".into()]));
+}
+
+#[test]
+fn should_report_unknown_flag_names() {
+    const ENV_KEYS_VALUES: &[(&str, &str)] = &[("SERVICE_FEATURES", "logging,profiling")];
+    let result = unsafe { common::with_env_vars(ENV_KEYS_VALUES, ServiceFeaturesConfig::from_env) };
+
+    assert_eq!(
+        result.unwrap_err(),
+        EnvConfigError::Parse(
+            "SERVICE_FEATURES".into(),
+            "unknown feature `profiling`".into()
+        )
+    );
+}
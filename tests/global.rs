@@ -0,0 +1,36 @@
+// Process-wide config tests
+use env_cfg::{EnvConfig, EnvConfigError};
+
+mod common;
+
+#[derive(Debug, EnvConfig)]
+#[env_cfg(prefix = "SINGLETON", global)]
+struct SingletonConfig {
+    name: String, // -> SINGLETON_NAME
+}
+
+// A single test, since the instance is shared by the whole test binary
+#[test]
+fn should_init_global_once() {
+    let before_init = std::panic::catch_unwind(SingletonConfig::global);
+    assert_eq!(
+        *before_init.unwrap_err().downcast::<String>().unwrap(),
+        "SingletonConfig::global() called before SingletonConfig::init()"
+    );
+
+    // A failed load leaves it uninitialized
+    let failed = unsafe { common::with_env_vars(&[], SingletonConfig::init) };
+    assert!(matches!(failed, Err(EnvConfigError::Missing(var)) if var == "SINGLETON_NAME"));
+
+    const FIRST: &[(&str, &str)] = &[("SINGLETON_NAME", "first")];
+    unsafe { common::with_env_vars(FIRST, SingletonConfig::init) }.unwrap();
+    assert_eq!(SingletonConfig::global().name, "first");
+
+    const SECOND: &[(&str, &str)] = &[("SINGLETON_NAME", "second")];
+    let reinit = unsafe { common::with_env_vars(SECOND, SingletonConfig::init) };
+    assert_eq!(
+        reinit.unwrap_err(),
+        EnvConfigError::Invalid("SingletonConfig".into(), "already initialized".into())
+    );
+    assert_eq!(SingletonConfig::global().name, "first");
+}
@@ -0,0 +1,91 @@
+// `group` tests
+use env_cfg::{EnvConfig, EnvConfigError};
+
+mod common;
+
+#[derive(Debug, EnvConfig)]
+#[env_cfg(prefix = "APP", group("db_host", "db_port", "db_name"))]
+struct AppConfig {
+    #[env_cfg(default = "localhost")]
+    db_host: String, // -> APP_DB_HOST
+    #[env_cfg(default = 5432)]
+    db_port: u16, // -> APP_DB_PORT
+    #[env_cfg(env = "DATABASE")]
+    db_name: Option<String>, // -> DATABASE
+    #[env_cfg(default = 8080)]
+    port: u16, // -> APP_PORT (not grouped)
+}
+
+#[test]
+fn should_load_group_when_every_variable_is_set() {
+    const ENV_KEYS_VALUES: &[(&str, &str)] = &[
+        ("APP_DB_HOST", "db.internal"),
+        ("APP_DB_PORT", "6432"),
+        ("DATABASE", "app"),
+    ];
+    let config =
+        unsafe { common::with_env_vars(ENV_KEYS_VALUES, || AppConfig::from_env().unwrap()) };
+
+    assert_eq!(config.db_host, "db.internal");
+    assert_eq!(config.db_port, 6432);
+    assert_eq!(config.db_name.as_deref(), Some("app"));
+    assert_eq!(config.port, 8080);
+}
+
+#[test]
+fn should_load_defaults_when_no_variable_of_group_is_set() {
+    const ENV_KEYS_VALUES: &[(&str, &str)] = &[("APP_PORT", "9090")];
+    let config =
+        unsafe { common::with_env_vars(ENV_KEYS_VALUES, || AppConfig::from_env().unwrap()) };
+
+    assert_eq!(config.db_host, "localhost");
+    assert_eq!(config.db_port, 5432);
+    assert_eq!(config.db_name, None);
+    assert_eq!(config.port, 9090);
+}
+
+#[test]
+fn should_fail_when_group_is_partially_set() {
+    const ENV_KEYS_VALUES: &[(&str, &str)] = &[("APP_DB_HOST", "db.internal"), ("DATABASE", "app")];
+    let result = unsafe { common::with_env_vars(ENV_KEYS_VALUES, AppConfig::from_env) };
+
+    match result {
+        Err(EnvConfigError::Invalid(config, message)) => {
+            assert_eq!(config, "AppConfig");
+            assert_eq!(
+                message,
+                "APP_DB_HOST, DATABASE set, but not APP_DB_PORT; set all of the group or none"
+            );
+        }
+        other => panic!("expected a partially set group to be invalid, got {other:?}"),
+    }
+}
+
+#[derive(Debug, EnvConfig)]
+#[env_cfg(prefix = "CACHE", group("host", "port"), group("user", "password"))]
+struct CacheConfig {
+    host: Option<String>,     // -> CACHE_HOST
+    port: Option<u16>,        // -> CACHE_PORT
+    user: Option<String>,     // -> CACHE_USER
+    password: Option<String>, // -> CACHE_PASSWORD
+}
+
+#[test]
+fn should_check_each_group_separately() {
+    const ENV_KEYS_VALUES: &[(&str, &str)] = &[("CACHE_HOST", "cache"), ("CACHE_PORT", "6379")];
+    let config =
+        unsafe { common::with_env_vars(ENV_KEYS_VALUES, || CacheConfig::from_env().unwrap()) };
+
+    assert_eq!(config.host.as_deref(), Some("cache"));
+    assert_eq!(config.port, Some(6379));
+    assert_eq!(config.user, None);
+    assert_eq!(config.password, None);
+
+    const PARTIAL: &[(&str, &str)] = &[
+        ("CACHE_HOST", "cache"),
+        ("CACHE_PORT", "6379"),
+        ("CACHE_PASSWORD", "hunter2"),
+    ];
+    let result = unsafe { common::with_env_vars(PARTIAL, CacheConfig::from_env) };
+    assert!(matches!(result, Err(EnvConfigError::Invalid(_, _))));
+}
@@ -0,0 +1,59 @@
+// `human_int` parsing tests
+use env_cfg::{EnvConfig, EnvConfigError, parse_human_int};
+
+mod common;
+
+#[derive(Debug, EnvConfig)]
+#[env_cfg(no_prefix)]
+struct HumanIntConfig {
+    #[env_cfg(human_int)]
+    max_bytes: u64, // -> MAX_BYTES
+    #[env_cfg(human_int)]
+    offset: Option<i32>, // -> OFFSET (optional)
+    #[env_cfg(human_int, default = "10_000")]
+    max_connections: u32, // -> MAX_CONNECTIONS (with default)
+}
+
+#[test]
+fn should_parse_grouped_integers() {
+    const ENV_KEYS_VALUES: &[(&str, &str)] = &[
+        ("MAX_BYTES", "1_000_000"),
+        ("OFFSET", "-2,500"),
+        ("MAX_CONNECTIONS", "1,024"),
+    ];
+    let config =
+        unsafe { common::with_env_vars(ENV_KEYS_VALUES, || HumanIntConfig::from_env().unwrap()) };
+
+    assert_eq!(config.max_bytes, 1_000_000);
+    assert_eq!(config.offset, Some(-2_500));
+    assert_eq!(config.max_connections, 1_024);
+}
+
+#[test]
+fn should_parse_human_int_defaults() {
+    const ENV_KEYS_VALUES: &[(&str, &str)] = &[("MAX_BYTES", "42")];
+    let config =
+        unsafe { common::with_env_vars(ENV_KEYS_VALUES, || HumanIntConfig::from_env().unwrap()) };
+
+    assert_eq!(config.max_bytes, 42);
+    assert_eq!(config.offset, None);
+    assert_eq!(config.max_connections, 10_000);
+}
+
+#[test]
+fn should_err_on_misplaced_separators() {
+    const ENV_KEYS_VALUES: &[(&str, &str)] = &[("MAX_BYTES", "1,000,")];
+    let result = unsafe { common::with_env_vars(ENV_KEYS_VALUES, HumanIntConfig::from_env) };
+
+    assert!(matches!(result, Err(EnvConfigError::Parse(var, _)) if var == "MAX_BYTES"));
+}
+
+#[test]
+fn should_reject_ambiguous_human_ints() {
+    assert_eq!(parse_human_int::<u32>("1_000"), Ok(1_000));
+    assert!(parse_human_int::<u32>(",1000").is_err());
+    assert!(parse_human_int::<u32>("1000,").is_err());
+    assert!(parse_human_int::<u32>("1__000").is_err());
+    assert!(parse_human_int::<u32>("1,_000").is_err());
+    assert!(parse_human_int::<u32>("one_thousand").is_err());
+}
@@ -0,0 +1,58 @@
+// Lists loaded from numbered env vars (`NAME_0`, `NAME_1`, ...)
+use env_cfg::{EnvConfig, EnvConfigError, env_var_indexed_list};
+
+mod common;
+
+#[derive(Debug, EnvConfig)]
+#[env_cfg(prefix = "PROXY")]
+struct IndexedListConfig {
+    #[env_cfg(indexed_list)]
+    header: Vec<String>, // -> PROXY_HEADER_0, PROXY_HEADER_1, ...
+    #[env_cfg(indexed_list, env = "UPSTREAM_PORT")]
+    ports: Vec<u16>, // -> UPSTREAM_PORT_0, UPSTREAM_PORT_1, ...
+    #[env_cfg(indexed_list)]
+    allow: Option<Vec<String>>, // -> PROXY_ALLOW_0, ... (None if empty)
+}
+
+#[test]
+fn should_load_indexed_lists() {
+    const ENV_KEYS_VALUES: &[(&str, &str)] = &[
+        ("PROXY_HEADER_0", "X-Forwarded-For: a, b"),
+        ("PROXY_HEADER_1", "X-Real-IP: c"),
+        ("UPSTREAM_PORT_0", "8080"),
+    ];
+    let config = unsafe {
+        common::with_env_vars(ENV_KEYS_VALUES, || IndexedListConfig::from_env().unwrap())
+    };
+
+    assert_eq!(config.header, ["X-Forwarded-For: a, b", "X-Real-IP: c"]);
+    assert_eq!(config.ports, [8080]);
+    assert_eq!(config.allow, None);
+}
+
+#[test]
+fn should_err_on_gap_or_unparseable_entry() {
+    const GAP: &[(&str, &str)] = &[("PROXY_HEADER_0", "a"), ("PROXY_HEADER_2", "c")];
+    const UNPARSEABLE: &[(&str, &str)] = &[("UPSTREAM_PORT_0", "80"), ("UPSTREAM_PORT_1", "x")];
+    let gap = unsafe { common::with_env_vars(GAP, IndexedListConfig::from_env) };
+    let unparseable = unsafe { common::with_env_vars(UNPARSEABLE, IndexedListConfig::from_env) };
+
+    assert!(matches!(gap, Err(EnvConfigError::Parse(var, _)) if var == "PROXY_HEADER_2"));
+    assert!(matches!(unparseable, Err(EnvConfigError::Parse(var, _)) if var == "UPSTREAM_PORT_1"));
+}
+
+#[test]
+fn should_load_indexed_list_with_helper() {
+    const ENV_KEYS_VALUES: &[(&str, &str)] = &[("TEST_PEER_0", "1"), ("TEST_PEER_1", "2")];
+    let (peers, empty) = unsafe {
+        common::with_env_vars(ENV_KEYS_VALUES, || {
+            (
+                env_var_indexed_list::<u8>("TEST_PEER").unwrap(),
+                env_var_indexed_list::<u8>("TEST_UNSET").unwrap(),
+            )
+        })
+    };
+
+    assert_eq!(peers, [1, 2]);
+    assert!(empty.is_empty());
+}
@@ -0,0 +1,51 @@
+// Whole-struct JSON loading tests
+#![cfg(feature = "serde")]
+
+use env_cfg::{EnvConfig, EnvConfigError};
+use serde::Deserialize;
+
+mod common;
+
+#[derive(Debug, EnvConfig, Deserialize, PartialEq)]
+#[env_cfg(prefix = "JSON", json_env = "JSON_CONFIG")]
+struct JsonConfig {
+    url: String, // -> JSON_URL
+    #[env_cfg(default = "8080")]
+    port: u16, // -> JSON_PORT (with default)
+}
+
+#[test]
+fn should_load_from_json_env_var() {
+    const ENV_KEYS_VALUES: &[(&str, &str)] = &[
+        ("JSON_CONFIG", r#"{"url": "http://json", "port": 9090}"#),
+        ("JSON_URL", "http://per-field"),
+    ];
+    let config =
+        unsafe { common::with_env_vars(ENV_KEYS_VALUES, || JsonConfig::from_env().unwrap()) };
+
+    assert_eq!(
+        config,
+        JsonConfig {
+            url: "http://json".to_string(),
+            port: 9090
+        }
+    );
+}
+
+#[test]
+fn should_fall_back_to_per_field_loading() {
+    const ENV_KEYS_VALUES: &[(&str, &str)] = &[("JSON_URL", "http://per-field")];
+    let config =
+        unsafe { common::with_env_vars(ENV_KEYS_VALUES, || JsonConfig::from_env().unwrap()) };
+
+    assert_eq!(config.url, "http://per-field");
+    assert_eq!(config.port, 8080);
+}
+
+#[test]
+fn should_report_json_errors_with_blob_var_name() {
+    const ENV_KEYS_VALUES: &[(&str, &str)] = &[("JSON_CONFIG", r#"{"url": 42}"#)];
+    let result = unsafe { common::with_env_vars(ENV_KEYS_VALUES, JsonConfig::from_env) };
+
+    assert!(matches!(result, Err(EnvConfigError::Parse(var, _)) if var == "JSON_CONFIG"));
+}
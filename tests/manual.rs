@@ -1,5 +1,9 @@
 // Manual implementation tests (what users can do without macros)
-use env_cfg::{EnvConfig, EnvConfigError, env_var, env_var_optional};
+use env_cfg::{
+    EnvConfig, EnvConfigError, env_var, env_var_optional, env_var_optional_or,
+    env_var_optional_or_parse, env_var_optional_try_with_parser, env_var_or, env_var_or_env,
+    env_var_or_parse, env_var_parse_with_default, env_var_try_with_parser,
+};
 
 mod common;
 
@@ -79,3 +83,162 @@ fn should_err_if_field_is_not_parseable() {
     let result = unsafe { common::with_env_vars(ENV_KEYS_VALUES, ManualConfig::from_env) };
     assert!(matches!(result, Err(EnvConfigError::Parse(_, _))));
 }
+
+#[test]
+fn should_apply_defaults_across_helper_matrix() {
+    const ENV_KEYS_VALUES: &[(&str, &str)] = &[("PORT", "9090")];
+
+    let results = unsafe {
+        common::with_env_vars(ENV_KEYS_VALUES, || {
+            (
+                env_var_or::<u16>("PORT", 8080).unwrap(),
+                env_var_or::<u16>("TIMEOUT", 30).unwrap(),
+                env_var_or_parse::<u16>("TIMEOUT", "30").unwrap(),
+                env_var_optional_or::<u16>("PORT", None).unwrap(),
+                env_var_optional_or::<u16>("TIMEOUT", Some(30)).unwrap(),
+                env_var_optional_or::<u16>("TIMEOUT", None).unwrap(),
+                env_var_optional_or_parse::<u16>("TIMEOUT", Some("30")).unwrap(),
+                env_var_optional_or_parse::<u16>("TIMEOUT", None).unwrap(),
+            )
+        })
+    };
+
+    assert_eq!(
+        results,
+        (9090, 30, 30, Some(9090), Some(30), None, Some(30), None)
+    );
+}
+
+#[test]
+fn should_err_if_string_default_is_not_parseable() {
+    let result = unsafe {
+        common::with_env_vars(&[], || env_var_optional_or_parse::<u16>("PORT", Some("x")))
+    };
+    assert!(matches!(result, Err(EnvConfigError::Parse(var, _)) if var == "default for PORT"));
+}
+
+#[test]
+fn should_parse_with_custom_parser_or_default() {
+    const ENV_KEYS_VALUES: &[(&str, &str)] = &[("HOSTS", "a;b")];
+    let split = |value: String| -> Vec<String> { value.split(';').map(String::from).collect() };
+
+    let (set, unset) = unsafe {
+        common::with_env_vars(ENV_KEYS_VALUES, || {
+            (
+                env_var_parse_with_default("HOSTS", split, vec!["localhost".to_string()]).unwrap(),
+                env_var_parse_with_default("PEERS", split, vec!["localhost".to_string()]).unwrap(),
+            )
+        })
+    };
+
+    assert_eq!(set, ["a", "b"]);
+    assert_eq!(unset, ["localhost"]);
+}
+
+#[test]
+fn should_compare_errors_with_assert_eq() {
+    const ENV_KEYS_VALUES: &[(&str, &str)] = &[
+        ("NATS_AUTH", "test_auth"),
+        ("NATS_SEED", "test_seed"),
+        ("PORT", "not_a_number"),
+    ];
+
+    let missing = unsafe { common::with_env_vars(&[], || env_var::<String>("NATS_AUTH")) };
+    assert_eq!(missing, Err(EnvConfigError::Missing("NATS_AUTH".into())));
+
+    let parse = unsafe { common::with_env_vars(ENV_KEYS_VALUES, || env_var::<u16>("PORT")) };
+    assert_eq!(
+        parse,
+        Err(EnvConfigError::Parse(
+            "PORT".into(),
+            "invalid digit found in string".into()
+        ))
+    );
+
+    assert_eq!(
+        EnvConfigError::Missing("NATS_SEED".into()).context("loading NATS settings"),
+        EnvConfigError::Nested {
+            context: "loading NATS settings".into(),
+            source: Box::new(EnvConfigError::Missing("NATS_SEED".into())),
+        }
+    );
+}
+
+fn parse_port(value: String) -> Result<u16, String> {
+    match value.parse::<u16>() {
+        Ok(0) => Err("port must not be 0".to_string()),
+        Ok(port) => Ok(port),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+#[test]
+fn should_load_with_fallible_parser() {
+    const ENV_KEYS_VALUES: &[(&str, &str)] = &[("PORT", "9090"), ("ADMIN_PORT", "0")];
+
+    let results = unsafe {
+        common::with_env_vars(ENV_KEYS_VALUES, || {
+            (
+                env_var_try_with_parser("PORT", parse_port),
+                env_var_try_with_parser("ADMIN_PORT", parse_port),
+                env_var_try_with_parser("DEBUG_PORT", parse_port),
+                env_var_optional_try_with_parser("PORT", parse_port),
+                env_var_optional_try_with_parser("ADMIN_PORT", parse_port),
+                env_var_optional_try_with_parser("DEBUG_PORT", parse_port),
+            )
+        })
+    };
+
+    let parse_error = || EnvConfigError::Parse("ADMIN_PORT".into(), "port must not be 0".into());
+    assert_eq!(results.0, Ok(9090));
+    assert_eq!(results.1, Err(parse_error()));
+    assert_eq!(results.2, Err(EnvConfigError::Missing("DEBUG_PORT".into())));
+    assert_eq!(results.3, Ok(Some(9090)));
+    assert_eq!(results.4, Err(parse_error()));
+    assert_eq!(results.5, Ok(None));
+}
+
+#[cfg(unix)]
+#[test]
+fn should_err_on_non_unicode_with_fallible_parser() {
+    use std::os::unix::ffi::OsStrExt;
+
+    let (required, optional) = unsafe {
+        common::with_env_vars(&[], || {
+            std::env::set_var("PORT", std::ffi::OsStr::from_bytes(b"80\xFF"));
+            let results = (
+                env_var_try_with_parser("PORT", parse_port),
+                env_var_optional_try_with_parser("PORT", parse_port),
+            );
+            std::env::remove_var("PORT");
+            results
+        })
+    };
+
+    let unicode_error =
+        || EnvConfigError::Parse("PORT".into(), "Invalid Unicode: \"80\u{FFFD}\"".into());
+    assert_eq!(required, Err(unicode_error()));
+    assert_eq!(optional, Err(unicode_error()));
+}
+
+#[test]
+fn should_fall_back_to_other_variable() {
+    const ENV_KEYS_VALUES: &[(&str, &str)] = &[("DATABASE_URL", "postgres://primary")];
+
+    let (fallback, missing) = unsafe {
+        common::with_env_vars(ENV_KEYS_VALUES, || {
+            (
+                env_var_or_env::<String>("READ_DATABASE_URL", "DATABASE_URL"),
+                env_var_or_env::<String>("READ_CACHE_URL", "CACHE_URL"),
+            )
+        })
+    };
+
+    assert_eq!(fallback, Ok("postgres://primary".to_string()));
+    assert_eq!(
+        missing,
+        Err(EnvConfigError::Missing(
+            "READ_CACHE_URL (or CACHE_URL)".into()
+        ))
+    );
+}
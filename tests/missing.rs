@@ -0,0 +1,84 @@
+// Reporting unset required variables without loading
+use env_cfg::EnvConfig;
+
+mod common;
+
+// The configs are never loaded, only their variable names are used
+
+#[allow(dead_code)]
+#[derive(Debug, EnvConfig)]
+#[env_cfg(prefix = "DB")]
+struct MissingDatabaseConfig {
+    host: String, // -> DB_HOST (required)
+    #[env_cfg(default = "5432")]
+    port: u16, // -> DB_PORT (with default)
+    #[env_cfg(empty_as_none)]
+    user: String, // -> DB_USER (required, empty counts as unset)
+}
+
+#[allow(dead_code)]
+#[derive(Debug, EnvConfig)]
+#[env_cfg(prefix = "APP")]
+struct MissingAppConfig {
+    name: String,         // -> APP_NAME (required)
+    timeout: Option<u64>, // -> APP_TIMEOUT (optional)
+    #[env_cfg(required)]
+    region: Option<String>, // -> APP_REGION (required optional)
+    #[env_cfg(default_from = "name")]
+    display_name: String, // -> APP_DISPLAY_NAME (defaults to name)
+    #[env_cfg(skip)]
+    internal: String, // Skipped
+    #[env_cfg(nested)]
+    database: MissingDatabaseConfig,
+}
+
+#[test]
+fn should_report_unset_required_variables() {
+    const ENV_KEYS_VALUES: &[(&str, &str)] = &[("APP_NAME", "app"), ("DB_USER", "")];
+    let missing =
+        unsafe { common::with_env_vars(ENV_KEYS_VALUES, MissingAppConfig::missing_env_vars) };
+
+    assert_eq!(missing, ["APP_REGION", "DB_HOST", "DB_USER"]);
+}
+
+#[test]
+fn should_report_nothing_when_all_required_variables_are_set() {
+    const ENV_KEYS_VALUES: &[(&str, &str)] = &[
+        ("APP_NAME", "app"),
+        ("APP_REGION", "eu"),
+        ("DB_HOST", "localhost"),
+        ("DB_USER", "postgres"),
+        // Presence is all that is checked, so an invalid value is not reported
+        ("DB_PORT", "not a port"),
+    ];
+    let missing =
+        unsafe { common::with_env_vars(ENV_KEYS_VALUES, MissingAppConfig::missing_env_vars) };
+
+    assert!(missing.is_empty());
+}
+
+#[derive(Debug, EnvConfig, PartialEq)]
+#[env_cfg(prefix = "OPT")]
+struct RequiredEmptyConfig {
+    #[env_cfg(required, empty_as_none)]
+    name: Option<String>, // -> OPT_NAME (required, empty loads as `None`)
+}
+
+#[test]
+fn should_not_report_required_empty_as_none_variable_set_to_empty() {
+    const ENV_KEYS_VALUES: &[(&str, &str)] = &[("OPT_NAME", "")];
+    let (config, missing) = unsafe {
+        common::with_env_vars(ENV_KEYS_VALUES, || {
+            (
+                RequiredEmptyConfig::from_env(),
+                RequiredEmptyConfig::missing_env_vars(),
+            )
+        })
+    };
+
+    assert_eq!(config, Ok(RequiredEmptyConfig { name: None }));
+    assert!(missing.is_empty());
+
+    let missing = unsafe { common::with_env_vars(&[], RequiredEmptyConfig::missing_env_vars) };
+    assert_eq!(missing, ["OPT_NAME"]);
+}
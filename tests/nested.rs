@@ -108,11 +108,11 @@ fn should_propagate_nested_config_errors() {
 
     let result = unsafe { common::with_env_vars(ENV_VARS, AppConfig::from_env) };
 
-    if let Err(EnvConfigError::Parse(var, _)) = result {
-        assert!(var.contains("nested DatabaseConfig"));
-    } else {
-        panic!("Expected Parse error with nested context");
-    }
+    let Err(EnvConfigError::Nested { context, source }) = result else {
+        panic!("Expected error with nested context");
+    };
+    assert_eq!(context, "nested DatabaseConfig");
+    assert!(matches!(*source, EnvConfigError::Parse(var, _) if var == "PORT"));
 }
 
 #[test]
@@ -125,11 +125,14 @@ fn should_fail_when_nested_required_vars_missing() {
 
     let result = unsafe { common::with_env_vars(ENV_VARS, AppConfig::from_env) };
 
-    if let Err(EnvConfigError::Parse(var, _)) = result {
-        assert!(var.contains("nested DatabaseConfig"));
-    } else {
-        panic!("Expected Parse error with nested context");
-    }
+    let Err(error) = result else {
+        panic!("Expected error with nested context");
+    };
+    assert!(
+        matches!(&error, EnvConfigError::Nested { context, .. } if context == "nested DatabaseConfig")
+    );
+    assert!(error.is_missing());
+    assert_eq!(error.var_name(), Some("HOST"));
 }
 
 #[test]
@@ -153,29 +156,563 @@ fn should_parse_multiple_nested_with_defaults() {
     assert_eq!(config.app_name, "test-app");
 }
 
-// Test validation: nested cannot be combined with other attributes
+#[derive(Debug, EnvConfig, PartialEq)]
+#[env_cfg(prefix = "SERVICE")]
+struct IndependentNestedConfig {
+    #[env_cfg(nested, independent)]
+    redis: RedisConfig,
+
+    name: String, // -> SERVICE_NAME
+}
+
+#[test]
+fn should_load_independent_nested_with_own_prefix() {
+    const ENV_VARS: &[(&str, &str)] = &[
+        ("REDIS_URL", "redis://localhost:6379"),
+        ("SERVICE_NAME", "service"),
+    ];
+
+    let config =
+        unsafe { common::with_env_vars(ENV_VARS, || IndependentNestedConfig::from_env().unwrap()) };
+
+    assert_eq!(config.redis.url, "redis://localhost:6379");
+    assert_eq!(config.redis.timeout, 5); // default
+    assert_eq!(config.name, "service");
+}
+
+#[derive(Debug, EnvConfig, PartialEq)]
+#[env_cfg(prefix = "TENANT", propagate_prefix)]
+struct PropagatedPrefixConfig {
+    #[env_cfg(nested)]
+    database: DatabaseConfig, // -> TENANT_HOST, TENANT_PORT, TENANT_DATABASE
+
+    #[env_cfg(nested, independent)]
+    redis: RedisConfig, // -> REDIS_URL, REDIS_TIMEOUT
+
+    name: String, // -> TENANT_NAME
+}
+
+#[test]
+fn should_propagate_prefix_to_nested_fields() {
+    const ENV_VARS: &[(&str, &str)] = &[
+        ("TENANT_HOST", "db.tenant"),
+        ("TENANT_PORT", "5433"),
+        ("HOST", "localhost"),
+        ("REDIS_URL", "redis://localhost:6379"),
+        ("TENANT_NAME", "acme"),
+    ];
+
+    let config =
+        unsafe { common::with_env_vars(ENV_VARS, || PropagatedPrefixConfig::from_env().unwrap()) };
+
+    assert_eq!(config.database.host, "db.tenant");
+    assert_eq!(config.database.port, 5433);
+    assert_eq!(config.database.database, "myapp"); // default
+    assert_eq!(config.redis.url, "redis://localhost:6379");
+    assert_eq!(config.name, "acme");
+}
+
+#[test]
+fn should_name_propagated_variables_in_errors() {
+    const ENV_VARS: &[(&str, &str)] = &[
+        ("TENANT_PORT", "not-a-port"),
+        ("REDIS_URL", "redis://localhost:6379"),
+        ("TENANT_NAME", "acme"),
+    ];
+
+    let (result, missing) = unsafe {
+        common::with_env_vars(ENV_VARS, || {
+            (
+                PropagatedPrefixConfig::from_env(),
+                PropagatedPrefixConfig::missing_env_vars(),
+            )
+        })
+    };
+
+    assert_eq!(missing, ["TENANT_HOST"]);
+    let Err(EnvConfigError::Nested { context, source }) = result else {
+        panic!("Expected error with nested context");
+    };
+    assert_eq!(context, "nested DatabaseConfig (prefix=TENANT)");
+    assert_eq!(*source, EnvConfigError::Missing("TENANT_HOST".to_string()));
+}
+
+#[derive(Debug, EnvConfig, PartialEq)]
+#[env_cfg(prefix = "P")]
+struct ListenerConfig {
+    #[env_cfg(env = "THREADS", alias(env = "WORKERS"))]
+    threads: u16, // -> THREADS, or WORKERS
+    #[env_cfg(expand, default = "${P_BASE_PORT}")]
+    port: u16, // -> P_PORT
+}
+
+#[derive(Debug, EnvConfig, PartialEq)]
+#[env_cfg(prefix = "APP", propagate_prefix)]
+struct PropagatedListenerConfig {
+    #[env_cfg(nested)]
+    listener: ListenerConfig, // -> APP_THREADS, APP_P_PORT
+}
+
 #[test]
-fn test_nested_with_parse_with_should_not_compile() {
-    // This test exists to document that the following should NOT compile:
-    //
-    // #[derive(EnvConfig)]
-    // struct InvalidConfig {
-    //     #[env_cfg(nested, parse_with = "some_parser")]
-    //     field: SomeType,
-    // }
-    //
-    // The macro should panic with: "Cannot use 'nested' with 'default' or 'parse_with' attributes"
+fn should_name_propagated_aliases_in_errors() {
+    const ENV_VARS: &[(&str, &str)] = &[("APP_WORKERS", "many"), ("APP_P_PORT", "8080")];
+
+    let result = unsafe { common::with_env_vars(ENV_VARS, PropagatedListenerConfig::from_env) };
+    let Err(error) = result else {
+        panic!("Expected an error for the alias");
+    };
+    assert!(error.is_parse());
+    assert_eq!(error.var_name(), Some("APP_THREADS (or APP_WORKERS)"));
+
+    let missing = unsafe { common::with_env_vars(&[], PropagatedListenerConfig::missing_env_vars) };
+    assert_eq!(missing, ["APP_THREADS (or APP_WORKERS)"]);
 }
 
 #[test]
-fn test_nested_with_default_should_not_compile() {
-    // This test exists to document that the following should NOT compile:
-    //
-    // #[derive(EnvConfig)]
-    // struct InvalidConfig {
-    //     #[env_cfg(nested, default = "something")]
-    //     field: SomeType,
-    // }
-    //
-    // The macro should panic with: "Cannot use 'nested' with 'default' or 'parse_with' attributes"
+fn should_name_propagated_defaults_in_errors() {
+    const ENV_VARS: &[(&str, &str)] = &[("APP_THREADS", "4"), ("APP_P_BASE_PORT", "http")];
+
+    let result = unsafe { common::with_env_vars(ENV_VARS, PropagatedListenerConfig::from_env) };
+    let Err(error) = result else {
+        panic!("Expected an error for the expanded default");
+    };
+    assert!(error.is_parse());
+    assert_eq!(error.var_name(), Some("default for APP_P_PORT"));
 }
+
+#[derive(Debug, Default, EnvConfig, PartialEq)]
+#[env_cfg(prefix = "SMTP")]
+struct SmtpConfig {
+    host: String, // -> SMTP_HOST
+    port: u16,    // -> SMTP_PORT
+}
+
+#[derive(Debug, EnvConfig, PartialEq)]
+#[env_cfg(prefix = "APP", propagate_prefix)]
+struct FieldPrefixedConfig {
+    #[env_cfg(nested)]
+    smtp: SmtpConfig, // -> APP_SMTP_HOST, APP_SMTP_PORT (struct prefix)
+
+    #[env_cfg(nested, prefix_from_field)]
+    primary_db: DatabaseConfig, // -> APP_PRIMARY_DB_HOST, ...
+
+    #[env_cfg(nested, prefix_from_field)]
+    replica_db: DatabaseConfig, // -> APP_REPLICA_DB_HOST, ...
+}
+
+#[test]
+fn should_prefix_nested_with_field_name() {
+    const ENV_VARS: &[(&str, &str)] = &[
+        ("APP_SMTP_HOST", "mail.internal"),
+        ("APP_SMTP_PORT", "25"),
+        ("APP_PRIMARY_DB_HOST", "primary.internal"),
+        ("APP_PRIMARY_DB_PORT", "5432"),
+        ("APP_REPLICA_DB_HOST", "replica.internal"),
+        ("APP_REPLICA_DB_PORT", "5433"),
+        ("APP_REPLICA_DB_DATABASE", "reports"),
+    ];
+
+    let config =
+        unsafe { common::with_env_vars(ENV_VARS, || FieldPrefixedConfig::from_env().unwrap()) };
+
+    assert_eq!(config.smtp.host, "mail.internal");
+    assert_eq!(config.primary_db.host, "primary.internal");
+    assert_eq!(config.primary_db.database, "myapp"); // default
+    assert_eq!(config.replica_db.host, "replica.internal");
+    assert_eq!(config.replica_db.port, 5433);
+    assert_eq!(config.replica_db.database, "reports");
+}
+
+#[test]
+fn should_name_field_prefixed_variables_when_missing() {
+    const ENV_VARS: &[(&str, &str)] = &[
+        ("APP_SMTP_HOST", "mail.internal"),
+        ("APP_SMTP_PORT", "25"),
+        ("APP_PRIMARY_DB_HOST", "primary.internal"),
+        ("APP_PRIMARY_DB_PORT", "5432"),
+    ];
+
+    let missing = unsafe { common::with_env_vars(ENV_VARS, FieldPrefixedConfig::missing_env_vars) };
+
+    assert_eq!(missing, ["APP_REPLICA_DB_HOST", "APP_REPLICA_DB_PORT"]);
+}
+
+#[derive(Debug, EnvConfig, PartialEq)]
+#[env_cfg(prefix = "MAIL")]
+struct MailConfig {
+    #[env_cfg(nested, default_if_absent)]
+    smtp: SmtpConfig,
+
+    sender: String, // -> MAIL_SENDER
+}
+
+#[test]
+fn should_default_nested_without_any_of_its_variables() {
+    const ENV_VARS: &[(&str, &str)] = &[("MAIL_SENDER", "noreply@example.com")];
+
+    let (config, missing, smtp_set) = unsafe {
+        common::with_env_vars(ENV_VARS, || {
+            (
+                MailConfig::from_env().unwrap(),
+                MailConfig::missing_env_vars(),
+                SmtpConfig::any_vars_set(&env_cfg::SystemEnv),
+            )
+        })
+    };
+
+    assert_eq!(config.smtp, SmtpConfig::default());
+    assert_eq!(config.sender, "noreply@example.com");
+    assert!(missing.is_empty());
+    assert!(!smtp_set);
+}
+
+#[test]
+fn should_load_nested_with_some_of_its_variables() {
+    const COMPLETE: &[(&str, &str)] = &[
+        ("SMTP_HOST", "mail.example.com"),
+        ("SMTP_PORT", "587"),
+        ("MAIL_SENDER", "noreply@example.com"),
+    ];
+    let config = unsafe { common::with_env_vars(COMPLETE, || MailConfig::from_env().unwrap()) };
+
+    assert_eq!(config.smtp.host, "mail.example.com");
+    assert_eq!(config.smtp.port, 587);
+
+    // A partial config is an error rather than silently replaced by the default
+    const PARTIAL: &[(&str, &str)] =
+        &[("SMTP_PORT", "587"), ("MAIL_SENDER", "noreply@example.com")];
+    let (result, missing) = unsafe {
+        common::with_env_vars(PARTIAL, || {
+            (MailConfig::from_env(), MailConfig::missing_env_vars())
+        })
+    };
+
+    assert!(
+        matches!(result, Err(EnvConfigError::Nested { context, .. }) if context == "nested SmtpConfig")
+    );
+    assert_eq!(missing, ["SMTP_HOST"]);
+}
+
+type DbCfg = DatabaseConfig;
+
+mod settings {
+    pub type Redis = super::RedisConfig;
+}
+
+#[derive(Debug, EnvConfig, PartialEq)]
+#[env_cfg(no_prefix)]
+struct AliasedNestedConfig {
+    #[env_cfg(nested)]
+    database: DbCfg,
+
+    #[env_cfg(nested)]
+    redis: settings::Redis,
+
+    #[env_cfg(nested)]
+    backup: crate::DatabaseConfig,
+}
+
+#[test]
+fn should_load_nested_config_behind_alias_or_path() {
+    const ENV_VARS: &[(&str, &str)] = &[
+        ("HOST", "localhost"),
+        ("PORT", "5432"),
+        ("REDIS_URL", "redis://localhost:6379"),
+    ];
+
+    let config =
+        unsafe { common::with_env_vars(ENV_VARS, || AliasedNestedConfig::from_env().unwrap()) };
+
+    assert_eq!(config.database.host, "localhost");
+    assert_eq!(config.redis.url, "redis://localhost:6379");
+    assert_eq!(config.backup, config.database);
+}
+
+#[test]
+fn should_name_resolved_type_in_aliased_nested_errors() {
+    const ENV_VARS: &[(&str, &str)] = &[("HOST", "localhost"), ("PORT", "5432")];
+
+    let result = unsafe { common::with_env_vars(ENV_VARS, AliasedNestedConfig::from_env) };
+
+    assert!(matches!(
+        result,
+        Err(EnvConfigError::Nested { context, .. })
+            if context == "nested settings::Redis (nested::RedisConfig)"
+    ));
+
+    let result =
+        unsafe { common::with_env_vars(&[("PORT", "5432")], AliasedNestedConfig::from_env) };
+
+    assert!(matches!(
+        result,
+        Err(EnvConfigError::Nested { context, .. })
+            if context == "nested DbCfg (nested::DatabaseConfig)"
+    ));
+}
+
+#[derive(Debug, Default, EnvConfig, PartialEq)]
+#[env_cfg(prefix = "TRACING")]
+struct TracingConfig {
+    endpoint: String, // -> TRACING_ENDPOINT
+    #[env_cfg(default = "1.0")]
+    sample_rate: f64, // -> TRACING_SAMPLE_RATE
+}
+
+#[derive(Debug, EnvConfig)]
+#[env_cfg(no_prefix)]
+struct ToggledConfig {
+    #[env_cfg(nested, enabled_by = "TRACING_ENABLED")]
+    tracing: TracingConfig,
+    #[env_cfg(nested, enabled_by = "REDIS_ENABLED")]
+    redis: Option<RedisConfig>,
+}
+
+#[test]
+fn should_load_nested_config_only_when_enabled() {
+    const DISABLED: &[(&str, &str)] = &[("TRACING_ENABLED", "false")];
+    const ENABLED: &[(&str, &str)] = &[
+        ("TRACING_ENABLED", "true"),
+        ("TRACING_ENDPOINT", "http://collector:4317"),
+        ("REDIS_ENABLED", "true"),
+        ("REDIS_URL", "redis://localhost:6379"),
+    ];
+
+    let disabled =
+        unsafe { common::with_env_vars(DISABLED, || ToggledConfig::from_env().unwrap()) };
+    let enabled = unsafe { common::with_env_vars(ENABLED, || ToggledConfig::from_env().unwrap()) };
+
+    assert_eq!(disabled.tracing, TracingConfig::default());
+    assert_eq!(disabled.redis, None);
+    assert_eq!(enabled.tracing.endpoint, "http://collector:4317");
+    assert_eq!(enabled.tracing.sample_rate, 1.0);
+    assert_eq!(enabled.redis.unwrap().url, "redis://localhost:6379");
+}
+
+#[test]
+fn should_require_nested_variables_only_when_enabled() {
+    const ENABLED: &[(&str, &str)] = &[("TRACING_ENABLED", "true")];
+    const INVALID_FLAG: &[(&str, &str)] = &[("TRACING_ENABLED", "maybe")];
+
+    let (result, missing) = unsafe {
+        common::with_env_vars(ENABLED, || {
+            (ToggledConfig::from_env(), ToggledConfig::missing_env_vars())
+        })
+    };
+    let invalid_flag = unsafe { common::with_env_vars(INVALID_FLAG, ToggledConfig::from_env) };
+    let missing_when_unset = unsafe { common::with_env_vars(&[], ToggledConfig::missing_env_vars) };
+
+    assert!(
+        matches!(result, Err(EnvConfigError::Nested { context, .. }) if context == "nested TracingConfig")
+    );
+    assert_eq!(missing, ["TRACING_ENDPOINT"]);
+    assert!(matches!(invalid_flag, Err(EnvConfigError::Parse(var, _)) if var == "TRACING_ENABLED"));
+    assert!(missing_when_unset.is_empty());
+}
+
+#[test]
+fn should_accept_any_flag_value_for_enabled_by() {
+    const ENABLED: &[(&str, &str)] = &[
+        ("TRACING_ENABLED", "1"),
+        ("TRACING_ENDPOINT", "http://collector:4317"),
+        ("REDIS_ENABLED", "YES"),
+    ];
+    const DISABLED: &[(&str, &str)] = &[("TRACING_ENABLED", "off"), ("REDIS_ENABLED", "0")];
+
+    let (result, missing) = unsafe {
+        common::with_env_vars(ENABLED, || {
+            (ToggledConfig::from_env(), ToggledConfig::missing_env_vars())
+        })
+    };
+    let disabled =
+        unsafe { common::with_env_vars(DISABLED, || ToggledConfig::from_env().unwrap()) };
+
+    assert!(
+        matches!(result, Err(EnvConfigError::Nested { context, .. }) if context == "nested RedisConfig")
+    );
+    assert_eq!(missing, ["REDIS_URL"]);
+    assert_eq!(disabled.tracing, TracingConfig::default());
+    assert_eq!(disabled.redis, None);
+}
+
+#[derive(Debug, EnvConfig)]
+#[env_cfg(no_prefix)]
+struct SharedConfig {
+    #[env_cfg(nested)]
+    database: std::sync::Arc<DatabaseConfig>,
+
+    #[env_cfg(nested)]
+    redis: Box<RedisConfig>,
+
+    #[env_cfg(nested, enabled_by = "TRACING_ENABLED")]
+    tracing: Option<std::sync::Arc<TracingConfig>>,
+}
+
+#[test]
+fn should_load_nested_config_behind_pointer() {
+    const ENV_VARS: &[(&str, &str)] = &[
+        ("HOST", "localhost"),
+        ("PORT", "5432"),
+        ("REDIS_URL", "redis://localhost:6379"),
+        ("TRACING_ENABLED", "true"),
+        ("TRACING_ENDPOINT", "http://collector:4317"),
+    ];
+
+    let config = unsafe { common::with_env_vars(ENV_VARS, || SharedConfig::from_env().unwrap()) };
+
+    assert_eq!(config.database.host, "localhost");
+    assert_eq!(config.redis.url, "redis://localhost:6379");
+    assert_eq!(config.tracing.unwrap().endpoint, "http://collector:4317");
+}
+
+#[test]
+fn should_propagate_errors_through_pointer() {
+    const ENV_VARS: &[(&str, &str)] = &[("HOST", "localhost"), ("PORT", "5432")];
+
+    let (result, missing) = unsafe {
+        common::with_env_vars(ENV_VARS, || {
+            (SharedConfig::from_env(), SharedConfig::missing_env_vars())
+        })
+    };
+
+    assert!(matches!(
+        result,
+        Err(EnvConfigError::Nested { context, .. }) if context == "nested RedisConfig"
+    ));
+    assert_eq!(missing, ["REDIS_URL"]);
+}
+
+#[derive(Debug, EnvConfig, PartialEq)]
+#[env_cfg(prefix = "CACHE")]
+struct Cache<B: EnvConfig<Error = EnvConfigError>> {
+    #[env_cfg(nested)]
+    backend: B,
+    #[env_cfg(default = "60")]
+    ttl_secs: u64, // -> CACHE_TTL_SECS
+}
+
+#[derive(Debug, EnvConfig, PartialEq)]
+#[env_cfg(no_prefix)]
+struct GenericNestedConfig {
+    #[env_cfg(nested)]
+    cache: Cache<RedisConfig>,
+}
+
+#[test]
+fn should_load_nested_generic_config() {
+    const ENV_VARS: &[(&str, &str)] = &[
+        ("REDIS_URL", "redis://localhost:6379"),
+        ("CACHE_TTL_SECS", "300"),
+    ];
+
+    let config =
+        unsafe { common::with_env_vars(ENV_VARS, || GenericNestedConfig::from_env().unwrap()) };
+
+    assert_eq!(config.cache.backend.url, "redis://localhost:6379");
+    assert_eq!(config.cache.ttl_secs, 300);
+}
+
+#[test]
+fn should_name_generic_type_in_nested_errors() {
+    let (result, missing) = unsafe {
+        common::with_env_vars(&[], || {
+            (
+                GenericNestedConfig::from_env(),
+                GenericNestedConfig::missing_env_vars(),
+            )
+        })
+    };
+
+    assert!(matches!(
+        result,
+        Err(EnvConfigError::Nested { context, .. }) if context == "nested Cache<RedisConfig>"
+    ));
+    assert_eq!(missing, ["REDIS_URL"]);
+}
+
+#[derive(Debug, EnvConfig, PartialEq)]
+#[env_cfg(no_prefix)]
+struct DefaultedNestedConfig {
+    #[env_cfg(nested, defaults(database = "reports", port = "5432"))]
+    database: DatabaseConfig,
+}
+
+#[test]
+fn should_use_parent_defaults_for_unset_nested_variables() {
+    const ENV_VARS: &[(&str, &str)] = &[("HOST", "localhost")];
+
+    let (config, missing) = unsafe {
+        common::with_env_vars(ENV_VARS, || {
+            (
+                DefaultedNestedConfig::from_env().unwrap(),
+                DefaultedNestedConfig::missing_env_vars(),
+            )
+        })
+    };
+
+    assert_eq!(
+        config.database,
+        DatabaseConfig {
+            host: "localhost".to_string(),
+            port: 5432,
+            database: "reports".to_string(),
+        }
+    );
+    assert!(missing.is_empty());
+}
+
+#[test]
+fn should_prefer_set_nested_variables_over_parent_defaults() {
+    const ENV_VARS: &[(&str, &str)] = &[
+        ("HOST", "localhost"),
+        ("PORT", "6432"),
+        ("DATABASE", "analytics"),
+    ];
+
+    let config =
+        unsafe { common::with_env_vars(ENV_VARS, || DefaultedNestedConfig::from_env().unwrap()) };
+
+    assert_eq!(config.database.port, 6432);
+    assert_eq!(config.database.database, "analytics");
+
+    let missing = unsafe { common::with_env_vars(&[], DefaultedNestedConfig::missing_env_vars) };
+    assert_eq!(missing, ["HOST"]);
+}
+
+#[derive(Debug, EnvConfig)]
+#[env_cfg(
+    prefix = "ACCOUNTS_RECEIVABLE_RECONCILIATION_ACCOUNTS_RECEIVABLE_RECONCILIATION_ACCOUNTS_RECEIVABLE_RECONCILIATION_ACCOUNTS_RECEIVABLE_RECONCILIATION_ACCOUNTS_RECEIVABLE_RECONCILIATION_ACCOUNTS_RECEIVABLE_RECONCILIATION_ACCOUNTS_RECEIVABLE_RECONCILIATION_WORKER",
+    propagate_prefix
+)]
+#[allow(dead_code)]
+struct LongPrefixConfig {
+    #[env_cfg(nested)]
+    database: DatabaseConfig,
+}
+
+#[test]
+fn should_report_propagated_names_that_are_too_long() {
+    let (result, missing) = unsafe {
+        common::with_env_vars(&[], || {
+            (
+                LongPrefixConfig::from_env(),
+                LongPrefixConfig::missing_env_vars(),
+            )
+        })
+    };
+
+    let Err(EnvConfigError::Nested { context, source }) = result else {
+        panic!("expected a nested error, got {result:?}");
+    };
+    assert!(context.starts_with("nested DatabaseConfig (prefix="));
+    let EnvConfigError::Invalid(name, message) = *source else {
+        panic!("expected the name to be invalid, got {source:?}");
+    };
+    assert!(name.ends_with("_WORKER_HOST"));
+    assert!(message.contains("the name is 256 characters long"));
+    // Only presence is checked, so the name is listed like any other unset variable
+    assert_eq!(missing.len(), 2);
+    assert!(missing[0].ends_with("_WORKER_HOST"));
+}
+
+// Validation of invalid attribute combinations (e.g. `nested` with `default` or `parse_with`)
+// is covered by the compile-fail fixtures in `tests/ui`
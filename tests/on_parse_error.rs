@@ -0,0 +1,53 @@
+// `on_parse_error` tests
+use env_cfg::{EnvConfig, EnvConfigError};
+
+mod common;
+
+#[derive(Debug, EnvConfig)]
+#[env_cfg(prefix = "WORKER")]
+struct WorkerConfig {
+    #[env_cfg(default = "4", on_parse_error = "default")]
+    threads: u8, // -> WORKER_THREADS (default when malformed)
+    #[env_cfg(on_parse_error = "none")]
+    max_jobs: Option<u32>, // -> WORKER_MAX_JOBS (None when malformed)
+    #[env_cfg(default = 30, on_parse_error = "fail")]
+    timeout_secs: u64, // -> WORKER_TIMEOUT_SECS (error when malformed)
+}
+
+#[test]
+fn should_parse_valid_values_regardless_of_policy() {
+    const ENV_KEYS_VALUES: &[(&str, &str)] = &[
+        ("WORKER_THREADS", "8"),
+        ("WORKER_MAX_JOBS", "100"),
+        ("WORKER_TIMEOUT_SECS", "60"),
+    ];
+    let config =
+        unsafe { common::with_env_vars(ENV_KEYS_VALUES, || WorkerConfig::from_env().unwrap()) };
+
+    assert_eq!(config.threads, 8);
+    assert_eq!(config.max_jobs, Some(100));
+    assert_eq!(config.timeout_secs, 60);
+}
+
+#[test]
+fn should_fall_back_on_malformed_values_of_lenient_fields() {
+    const ENV_KEYS_VALUES: &[(&str, &str)] =
+        &[("WORKER_THREADS", "eight"), ("WORKER_MAX_JOBS", "-1")];
+    let config =
+        unsafe { common::with_env_vars(ENV_KEYS_VALUES, || WorkerConfig::from_env().unwrap()) };
+
+    assert_eq!(config.threads, 4);
+    assert_eq!(config.max_jobs, None);
+    assert_eq!(config.timeout_secs, 30);
+}
+
+#[test]
+fn should_fail_on_malformed_values_by_default() {
+    const ENV_KEYS_VALUES: &[(&str, &str)] = &[("WORKER_TIMEOUT_SECS", "1m")];
+    let result = unsafe { common::with_env_vars(ENV_KEYS_VALUES, WorkerConfig::from_env) };
+
+    assert!(matches!(
+        result,
+        Err(EnvConfigError::Parse(var, _)) if var == "WORKER_TIMEOUT_SECS"
+    ));
+}
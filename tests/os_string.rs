@@ -0,0 +1,118 @@
+// Tests for fields that hold values which are not required to be valid Unicode
+use std::ffi::OsString;
+use std::path::PathBuf;
+
+use env_cfg::{EnvConfig, EnvConfigError, env_var_os, env_var_os_optional};
+
+mod common;
+
+#[derive(Debug, EnvConfig)]
+#[env_cfg(no_prefix)]
+struct PathsConfig {
+    data_dir: PathBuf,
+    raw_name: OsString,
+    cache_dir: Option<PathBuf>,
+    #[env_cfg(default = "/tmp")]
+    tmp_dir: PathBuf,
+}
+
+#[test]
+fn should_load_os_string_and_path_fields() {
+    const ENV_KEYS_VALUES: &[(&str, &str)] = &[
+        ("DATA_DIR", "/var/lib/app"),
+        ("RAW_NAME", "app"),
+        ("CACHE_DIR", "/var/cache/app"),
+    ];
+
+    let config =
+        unsafe { common::with_env_vars(ENV_KEYS_VALUES, || PathsConfig::from_env().unwrap()) };
+
+    assert_eq!(config.data_dir, PathBuf::from("/var/lib/app"));
+    assert_eq!(config.raw_name, "app");
+    assert_eq!(config.cache_dir, Some(PathBuf::from("/var/cache/app")));
+    assert_eq!(config.tmp_dir, PathBuf::from("/tmp"));
+}
+
+#[test]
+fn should_report_missing_path_field() {
+    const ENV_KEYS_VALUES: &[(&str, &str)] = &[("RAW_NAME", "app")];
+
+    let (result, missing) = unsafe {
+        common::with_env_vars(ENV_KEYS_VALUES, || {
+            (PathsConfig::from_env(), PathsConfig::missing_env_vars())
+        })
+    };
+
+    assert!(matches!(result, Err(EnvConfigError::Missing(var)) if var == "DATA_DIR"));
+    assert_eq!(missing, ["DATA_DIR"]);
+}
+
+#[cfg(unix)]
+#[test]
+fn should_load_non_unicode_values() {
+    use std::os::unix::ffi::OsStrExt;
+
+    let raw = std::ffi::OsStr::from_bytes(b"/srv/\xFFdata");
+    const ENV_KEYS_VALUES: &[(&str, &str)] = &[("RAW_NAME", "app")];
+
+    let (config, helper) = unsafe {
+        common::with_env_vars(ENV_KEYS_VALUES, || {
+            std::env::set_var("DATA_DIR", raw);
+            let loaded = (PathsConfig::from_env(), env_var_os("DATA_DIR"));
+            std::env::remove_var("DATA_DIR");
+            loaded
+        })
+    };
+
+    assert_eq!(config.unwrap().data_dir.as_os_str(), raw);
+    assert_eq!(helper.unwrap(), raw);
+}
+
+#[test]
+fn should_load_os_values_with_helpers() {
+    const ENV_KEYS_VALUES: &[(&str, &str)] = &[("DATA_DIR", "/var/lib/app")];
+
+    let (set, unset, required) = unsafe {
+        common::with_env_vars(ENV_KEYS_VALUES, || {
+            (
+                env_var_os_optional("DATA_DIR").unwrap(),
+                env_var_os_optional("CACHE_DIR").unwrap(),
+                env_var_os("CACHE_DIR"),
+            )
+        })
+    };
+
+    assert_eq!(set, Some(OsString::from("/var/lib/app")));
+    assert_eq!(unset, None);
+    assert!(matches!(required, Err(EnvConfigError::Missing(var)) if var == "CACHE_DIR"));
+}
+
+#[derive(Debug, EnvConfig)]
+#[env_cfg(no_prefix)]
+struct UnicodeConfig {
+    #[allow(dead_code)]
+    display_name: String,
+}
+
+#[cfg(unix)]
+#[test]
+fn should_show_lossy_value_when_not_unicode() {
+    use std::os::unix::ffi::OsStrExt;
+
+    let result = unsafe {
+        common::with_env_vars(&[], || {
+            std::env::set_var("DISPLAY_NAME", std::ffi::OsStr::from_bytes(b"caf\xE9"));
+            let loaded = UnicodeConfig::from_env();
+            std::env::remove_var("DISPLAY_NAME");
+            loaded
+        })
+    };
+
+    assert_eq!(
+        result.unwrap_err(),
+        EnvConfigError::Parse(
+            "DISPLAY_NAME".to_string(),
+            "Invalid Unicode: \"caf\u{FFFD}\"".to_string()
+        )
+    );
+}
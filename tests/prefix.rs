@@ -34,6 +34,17 @@ struct MixedPrefixConfig {
     port: u16, // This should use TEST_PORT
 }
 
+// Test sub_prefix attribute (added after the struct name prefix)
+#[derive(Debug, EnvConfig)]
+#[env_cfg(sub_prefix = "DB")]
+struct DatabaseConfig {
+    host: String, // -> DATABASE_CONFIG_DB_HOST
+    #[env_cfg(env = "DB_PASSWORD")]
+    password: String, // -> DB_PASSWORD (overrides the prefix)
+    #[env_cfg(env = "{prefix}_USER")]
+    user: String, // -> DATABASE_CONFIG_DB_USER
+}
+
 #[test]
 fn should_use_struct_name_as_default_prefix() {
     const ENV_KEYS_VALUES: &[(&str, &str)] = &[
@@ -106,3 +117,304 @@ fn should_fail_when_using_old_env_var_names_with_default_prefix() {
         matches!(result, Err(EnvConfigError::Missing(var)) if var == "DEFAULT_PREFIX_CONFIG_DATABASE_URL")
     );
 }
+
+// Test `{prefix}` placeholders in field-level env names
+#[derive(Debug, EnvConfig)]
+struct TemplateDefaultConfig {
+    #[env_cfg(env = "{prefix}_CONN_STRING")]
+    url: String, // -> TEMPLATE_DEFAULT_CONFIG_CONN_STRING
+}
+
+#[derive(Debug, EnvConfig)]
+#[env_cfg(prefix = "billing")]
+struct TemplateCustomConfig {
+    #[env_cfg(env = "{prefix}_CONN_STRING")]
+    url: String, // -> BILLING_CONN_STRING
+}
+
+#[derive(Debug, EnvConfig)]
+#[env_cfg(no_prefix)]
+struct TemplateNoPrefixConfig {
+    #[env_cfg(env = "{prefix}_CONN_STRING")]
+    url: String, // -> CONN_STRING
+}
+
+#[test]
+fn should_replace_prefix_placeholder_in_env_names() {
+    const ENV_KEYS_VALUES: &[(&str, &str)] = &[
+        ("TEMPLATE_DEFAULT_CONFIG_CONN_STRING", "default"),
+        ("BILLING_CONN_STRING", "custom"),
+        ("CONN_STRING", "none"),
+    ];
+    let (default, custom, none) = unsafe {
+        common::with_env_vars(ENV_KEYS_VALUES, || {
+            (
+                TemplateDefaultConfig::from_env().unwrap(),
+                TemplateCustomConfig::from_env().unwrap(),
+                TemplateNoPrefixConfig::from_env().unwrap(),
+            )
+        })
+    };
+
+    assert_eq!(default.url, "default");
+    assert_eq!(custom.url, "custom");
+    assert_eq!(none.url, "none");
+}
+
+// Test `{field}` placeholders in field-level env names
+#[derive(Debug, EnvConfig)]
+#[env_cfg(prefix = "APP")]
+struct TemplateFieldConfig {
+    #[env_cfg(env = "SVC_{field}_URL")]
+    database: String, // -> SVC_DATABASE_URL
+    #[env_cfg(env = "{prefix}_{field}_URL")]
+    cache: String, // -> APP_CACHE_URL
+    #[env_cfg(env = ["{field}_URL", "LEGACY_{field}"])]
+    queue: String, // -> QUEUE_URL, or LEGACY_QUEUE
+}
+
+#[test]
+fn should_replace_field_placeholder_in_env_names() {
+    const ENV_KEYS_VALUES: &[(&str, &str)] = &[
+        ("SVC_DATABASE_URL", "postgres://db"),
+        ("APP_CACHE_URL", "redis://cache"),
+        ("LEGACY_QUEUE", "amqp://queue"),
+    ];
+    let config = unsafe {
+        common::with_env_vars(ENV_KEYS_VALUES, || TemplateFieldConfig::from_env().unwrap())
+    };
+
+    assert_eq!(config.database, "postgres://db");
+    assert_eq!(config.cache, "redis://cache");
+    assert_eq!(config.queue, "amqp://queue");
+}
+
+// Test field-level prefix attribute (relocates a single field)
+#[derive(Debug, EnvConfig)]
+#[env_cfg(prefix = "APP")]
+struct FieldPrefixConfig {
+    name: String, // -> APP_NAME
+    #[env_cfg(prefix = "METRICS")]
+    endpoint: String, // -> METRICS_ENDPOINT
+    #[env_cfg(prefix = "metrics", default = "10")]
+    flush_interval: u64, // -> METRICS_FLUSH_INTERVAL (with default)
+}
+
+#[derive(Debug, EnvConfig)]
+struct FieldPrefixDefaultConfig {
+    #[env_cfg(prefix = "METRICS")]
+    endpoint: String, // -> METRICS_ENDPOINT (struct name prefix is not used)
+}
+
+#[derive(Debug, EnvConfig)]
+#[env_cfg(no_prefix)]
+struct FieldPrefixNoPrefixConfig {
+    #[env_cfg(prefix = "METRICS")]
+    endpoint: String, // -> METRICS_ENDPOINT
+    port: u16, // -> PORT
+}
+
+#[test]
+fn should_use_field_level_prefix() {
+    const ENV_KEYS_VALUES: &[(&str, &str)] = &[
+        ("APP_NAME", "app"),
+        ("APP_ENDPOINT", "wrong"),
+        ("METRICS_ENDPOINT", "http://metrics:9090"),
+        ("PORT", "8080"),
+    ];
+    let (custom, default, none) = unsafe {
+        common::with_env_vars(ENV_KEYS_VALUES, || {
+            (
+                FieldPrefixConfig::from_env().unwrap(),
+                FieldPrefixDefaultConfig::from_env().unwrap(),
+                FieldPrefixNoPrefixConfig::from_env().unwrap(),
+            )
+        })
+    };
+
+    assert_eq!(custom.name, "app");
+    assert_eq!(custom.endpoint, "http://metrics:9090");
+    assert_eq!(custom.flush_interval, 10);
+    assert_eq!(default.endpoint, "http://metrics:9090");
+    assert_eq!(none.endpoint, "http://metrics:9090");
+    assert_eq!(none.port, 8080);
+}
+
+// Test `rename`, an alias for `env`
+#[derive(Debug, EnvConfig)]
+#[env_cfg(prefix = "TEST")]
+struct RenameConfig {
+    #[env_cfg(rename = "CUSTOM_URL")]
+    database_url: String, // -> CUSTOM_URL
+    #[env_cfg(rename = "{prefix}_LISTEN_PORT")]
+    port: u16, // -> TEST_LISTEN_PORT
+}
+
+#[test]
+fn should_use_rename_like_env() {
+    const ENV_KEYS_VALUES: &[(&str, &str)] = &[
+        ("CUSTOM_URL", "postgres://custom"),
+        ("TEST_DATABASE_URL", "wrong"),
+        ("TEST_LISTEN_PORT", "9000"),
+    ];
+    let config =
+        unsafe { common::with_env_vars(ENV_KEYS_VALUES, || RenameConfig::from_env().unwrap()) };
+
+    assert_eq!(config.database_url, "postgres://custom");
+    assert_eq!(config.port, 9000);
+}
+
+// Test name conversion rules
+#[derive(Debug, EnvConfig)]
+struct OAuthConfig {
+    client_id: String, // -> O_AUTH_CONFIG_CLIENT_ID
+}
+
+#[derive(Debug, EnvConfig)]
+struct HTTP2ServerConfig {
+    port: u16, // -> HTTP2_SERVER_CONFIG_PORT
+}
+
+#[derive(Debug, EnvConfig)]
+#[env_cfg(rename_all = "ACRONYM_SNAKE_CASE")]
+struct OAuthAcronymConfig {
+    client_id: String, // -> OAUTH_ACRONYM_CONFIG_CLIENT_ID
+}
+
+#[derive(Debug, EnvConfig)]
+#[env_cfg(rename_all = "ACRONYM_SNAKE_CASE")]
+struct HTTPServerConfig {
+    port: u16, // -> HTTPSERVER_CONFIG_PORT
+}
+
+#[test]
+fn should_convert_names_with_rename_all_strategy() {
+    const ENV_KEYS_VALUES: &[(&str, &str)] = &[
+        ("O_AUTH_CONFIG_CLIENT_ID", "default"),
+        ("HTTP2_SERVER_CONFIG_PORT", "8080"),
+        ("OAUTH_ACRONYM_CONFIG_CLIENT_ID", "acronym"),
+        ("HTTPSERVER_CONFIG_PORT", "9090"),
+    ];
+    let (oauth, http2, oauth_acronym, http) = unsafe {
+        common::with_env_vars(ENV_KEYS_VALUES, || {
+            (
+                OAuthConfig::from_env().unwrap(),
+                HTTP2ServerConfig::from_env().unwrap(),
+                OAuthAcronymConfig::from_env().unwrap(),
+                HTTPServerConfig::from_env().unwrap(),
+            )
+        })
+    };
+
+    assert_eq!(oauth.client_id, "default");
+    assert_eq!(http2.port, 8080);
+    assert_eq!(oauth_acronym.client_id, "acronym");
+    assert_eq!(http.port, 9090);
+}
+
+const SERVICE_PREFIX: &str = "billing";
+
+mod prefixes {
+    // A trailing underscore is not doubled
+    pub const METRICS: &str = "METRICS_";
+}
+
+#[derive(Debug, EnvConfig)]
+#[env_cfg(prefix = SERVICE_PREFIX)]
+struct ConstPrefixConfig {
+    database_url: String, // -> BILLING_DATABASE_URL
+    #[env_cfg(env = "{prefix}_LISTEN_PORT")]
+    port: u16, // -> BILLING_LISTEN_PORT
+    #[env_cfg(prefix = prefixes::METRICS)]
+    endpoint: String, // -> METRICS_ENDPOINT
+    workers: Option<u8>,
+}
+
+#[test]
+fn should_read_prefix_from_const() {
+    const ENV_KEYS_VALUES: &[(&str, &str)] = &[
+        ("BILLING_DATABASE_URL", "postgres://localhost/billing"),
+        ("BILLING_LISTEN_PORT", "8080"),
+        ("METRICS_ENDPOINT", "http://localhost:9090"),
+    ];
+
+    let (config, missing) = unsafe {
+        common::with_env_vars(ENV_KEYS_VALUES, || {
+            (
+                ConstPrefixConfig::from_env().unwrap(),
+                ConstPrefixConfig::missing_env_vars(),
+            )
+        })
+    };
+
+    assert_eq!(config.database_url, "postgres://localhost/billing");
+    assert_eq!(config.port, 8080);
+    assert_eq!(config.endpoint, "http://localhost:9090");
+    assert_eq!(config.workers, None);
+    assert!(missing.is_empty());
+
+    let result = unsafe { common::with_env_vars(&[], ConstPrefixConfig::from_env) };
+    assert!(matches!(result, Err(EnvConfigError::Missing(var)) if var == "BILLING_DATABASE_URL"));
+}
+
+#[derive(Debug, EnvConfig)]
+#[env_cfg(prefix = "APP")]
+struct EnvListConfig {
+    #[env_cfg(env = ["DATABASE_URL", "DB_URL", "{prefix}_DB"])]
+    database_url: String,
+    #[env_cfg(rename = ["CACHE_TTL", "TTL"], default = "60")]
+    cache_ttl: u32,
+}
+
+#[test]
+fn should_try_env_names_in_order() {
+    const FIRST: &[(&str, &str)] = &[("DATABASE_URL", "first"), ("DB_URL", "second")];
+    const SECOND: &[(&str, &str)] = &[("DB_URL", "second"), ("TTL", "30")];
+    const THIRD: &[(&str, &str)] = &[("APP_DB", "third"), ("CACHE_TTL", "x")];
+
+    let first = unsafe { common::with_env_vars(FIRST, || EnvListConfig::from_env().unwrap()) };
+    let second = unsafe { common::with_env_vars(SECOND, || EnvListConfig::from_env().unwrap()) };
+    let third = unsafe { common::with_env_vars(THIRD, EnvListConfig::from_env) };
+    let (none, missing) = unsafe {
+        common::with_env_vars(&[], || {
+            (EnvListConfig::from_env(), EnvListConfig::missing_env_vars())
+        })
+    };
+
+    assert_eq!(first.database_url, "first");
+    assert_eq!(first.cache_ttl, 60);
+    assert_eq!(second.database_url, "second");
+    assert_eq!(second.cache_ttl, 30);
+    assert!(matches!(
+        third,
+        Err(EnvConfigError::Parse(var, _)) if var == "CACHE_TTL (or TTL)"
+    ));
+    assert!(matches!(
+        none,
+        Err(EnvConfigError::Missing(var)) if var == "DATABASE_URL (or DB_URL or APP_DB)"
+    ));
+    assert_eq!(missing, ["DATABASE_URL (or DB_URL or APP_DB)"]);
+}
+
+#[test]
+fn should_add_sub_prefix_after_struct_name() {
+    const ENV_KEYS_VALUES: &[(&str, &str)] = &[
+        ("DATABASE_CONFIG_DB_HOST", "db.internal"),
+        ("DB_PASSWORD", "hunter2"),
+        ("DATABASE_CONFIG_DB_USER", "admin"),
+    ];
+    let config =
+        unsafe { common::with_env_vars(ENV_KEYS_VALUES, || DatabaseConfig::from_env().unwrap()) };
+
+    assert_eq!(config.host, "db.internal");
+    assert_eq!(config.password, "hunter2");
+    assert_eq!(config.user, "admin");
+    assert_eq!(
+        DatabaseConfig::missing_vars(&std::collections::BTreeMap::new()),
+        [
+            "DATABASE_CONFIG_DB_HOST",
+            "DB_PASSWORD",
+            "DATABASE_CONFIG_DB_USER"
+        ]
+    );
+}
@@ -0,0 +1,73 @@
+// `profile_var` tests
+use env_cfg::{EnvConfig, EnvConfigError};
+
+mod common;
+
+#[derive(Debug, EnvConfig)]
+#[env_cfg(no_prefix, profile_var = "APP_PROFILE")]
+struct ProfiledConfig {
+    database_url: String, // -> PROD_DATABASE_URL (with APP_PROFILE=prod) or DATABASE_URL
+    #[env_cfg(default = "4")]
+    workers: u32, // -> PROD_WORKERS or WORKERS (with default)
+}
+
+#[test]
+fn should_prefer_profile_overrides() {
+    const ENV_KEYS_VALUES: &[(&str, &str)] = &[
+        ("APP_PROFILE", "prod"),
+        ("DATABASE_URL", "postgres://localhost/dev"),
+        ("PROD_DATABASE_URL", "postgres://db.internal/prod"),
+        ("PROD_WORKERS", "16"),
+    ];
+    let config =
+        unsafe { common::with_env_vars(ENV_KEYS_VALUES, || ProfiledConfig::from_env().unwrap()) };
+
+    assert_eq!(config.database_url, "postgres://db.internal/prod");
+    assert_eq!(config.workers, 16);
+}
+
+#[test]
+fn should_use_base_variables_without_profile() {
+    const ENV_KEYS_VALUES: &[(&str, &str)] = &[
+        ("DATABASE_URL", "postgres://localhost/dev"),
+        ("PROD_DATABASE_URL", "postgres://db.internal/prod"),
+    ];
+    let config =
+        unsafe { common::with_env_vars(ENV_KEYS_VALUES, || ProfiledConfig::from_env().unwrap()) };
+
+    assert_eq!(config.database_url, "postgres://localhost/dev");
+    assert_eq!(config.workers, 4);
+}
+
+#[test]
+fn should_fall_back_to_base_variables_without_override() {
+    const ENV_KEYS_VALUES: &[(&str, &str)] = &[
+        ("APP_PROFILE", "staging"),
+        ("DATABASE_URL", "postgres://localhost/dev"),
+        ("PROD_WORKERS", "16"),
+    ];
+    let config =
+        unsafe { common::with_env_vars(ENV_KEYS_VALUES, || ProfiledConfig::from_env().unwrap()) };
+
+    assert_eq!(config.database_url, "postgres://localhost/dev");
+    assert_eq!(config.workers, 4);
+}
+
+#[test]
+fn should_report_base_variables_as_missing() {
+    const ENV_KEYS_VALUES: &[(&str, &str)] = &[("APP_PROFILE", "prod"), ("PROD_WORKERS", "x")];
+    let (result, missing) = unsafe {
+        common::with_env_vars(ENV_KEYS_VALUES, || {
+            (
+                ProfiledConfig::from_env(),
+                ProfiledConfig::missing_env_vars(),
+            )
+        })
+    };
+
+    assert_eq!(
+        result.unwrap_err(),
+        EnvConfigError::Missing("DATABASE_URL".into())
+    );
+    assert_eq!(missing, ["DATABASE_URL"]);
+}
@@ -0,0 +1,64 @@
+// Integer radix parsing tests
+use env_cfg::{EnvConfig, EnvConfigError, parse_int_radix, parse_int_radix_auto};
+
+mod common;
+
+#[derive(Debug, EnvConfig)]
+#[env_cfg(no_prefix)]
+struct RadixConfig {
+    #[env_cfg(radix_auto)]
+    mode: u32, // -> MODE
+    #[env_cfg(radix_auto)]
+    offset: Option<i64>, // -> OFFSET (optional)
+    #[env_cfg(radix = 16)]
+    flags: u8, // -> FLAGS
+    #[env_cfg(radix = 2, default = "0b1010")]
+    mask: u16, // -> MASK (with default)
+}
+
+#[test]
+fn should_parse_integers_with_radix() {
+    const ENV_KEYS_VALUES: &[(&str, &str)] =
+        &[("MODE", "0o755"), ("OFFSET", "-0x20"), ("FLAGS", "FF")];
+    let config =
+        unsafe { common::with_env_vars(ENV_KEYS_VALUES, || RadixConfig::from_env().unwrap()) };
+
+    assert_eq!(config.mode, 0o755);
+    assert_eq!(config.offset, Some(-0x20));
+    assert_eq!(config.flags, 0xFF);
+    assert_eq!(config.mask, 0b1010);
+
+    const DECIMAL: &[(&str, &str)] = &[("MODE", "420"), ("FLAGS", "0x0f"), ("MASK", "11")];
+    let config = unsafe { common::with_env_vars(DECIMAL, || RadixConfig::from_env().unwrap()) };
+
+    assert_eq!(config.mode, 420);
+    assert_eq!(config.offset, None);
+    assert_eq!(config.flags, 0x0F);
+    assert_eq!(config.mask, 0b11);
+}
+
+#[test]
+fn should_err_on_invalid_digits_for_radix() {
+    const ENV_KEYS_VALUES: &[(&str, &str)] = &[("MODE", "0b102"), ("FLAGS", "FF")];
+    let result = unsafe { common::with_env_vars(ENV_KEYS_VALUES, RadixConfig::from_env) };
+
+    assert_eq!(
+        result.unwrap_err(),
+        EnvConfigError::Parse(
+            "MODE".into(),
+            "`0b102` is not a valid base 2 integer: invalid digit found in string".into()
+        )
+    );
+}
+
+#[test]
+fn should_parse_with_radix_helpers() {
+    assert_eq!(parse_int_radix::<u32>("777", 8), Ok(0o777));
+    assert_eq!(parse_int_radix::<i8>("-0B11", 2), Ok(-3));
+    assert_eq!(parse_int_radix::<u32>("z", 36), Ok(35));
+    assert!(parse_int_radix::<u8>("100", 16).is_err()); // overflow
+    assert!(parse_int_radix::<u8>("1", 37).is_err());
+    assert!(parse_int_radix_auto::<u8>("0x").is_err());
+    assert!(parse_int_radix_auto::<i32>("0x-1").is_err());
+    assert!(parse_int_radix_auto::<u8>("-0x1").is_err());
+}
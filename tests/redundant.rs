@@ -0,0 +1,106 @@
+// `redundant_vars` tests
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use env_cfg::{EnvConfig, EnvConfigError};
+
+mod common;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum LogLevel {
+    Info,
+    Debug,
+}
+
+impl std::str::FromStr for LogLevel {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "info" => Ok(LogLevel::Info),
+            "debug" => Ok(LogLevel::Debug),
+            other => Err(format!("unknown log level {other}")),
+        }
+    }
+}
+
+#[derive(Debug, EnvConfig)]
+#[env_cfg(prefix = "APP", redundant_vars)]
+struct AppConfig {
+    host: String, // -> APP_HOST (no default)
+    #[env_cfg(default = "8080")]
+    port: u16, // -> APP_PORT
+    #[env_cfg(default = LogLevel::Info)]
+    log_level: LogLevel, // -> APP_LOG_LEVEL
+    #[env_cfg(env = ["APP_WORKERS", "WORKERS"], default = "4")]
+    workers: Option<u32>, // -> APP_WORKERS, or WORKERS
+}
+
+#[test]
+fn should_list_variables_set_to_their_default() {
+    const ENV_KEYS_VALUES: &[(&str, &str)] = &[
+        ("APP_HOST", "localhost"),
+        ("APP_PORT", "08080"),
+        ("APP_LOG_LEVEL", "info"),
+        ("WORKERS", "4"),
+    ];
+    let redundant = unsafe {
+        common::with_env_vars(ENV_KEYS_VALUES, || AppConfig::redundant_env_vars().unwrap())
+    };
+
+    assert_eq!(redundant, vec!["APP_PORT", "APP_LOG_LEVEL", "WORKERS"]);
+}
+
+#[test]
+fn should_not_list_variables_set_to_other_values() {
+    let source = HashMap::from([
+        ("APP_HOST".to_string(), "localhost".to_string()),
+        ("APP_PORT".to_string(), "9090".to_string()),
+        ("APP_LOG_LEVEL".to_string(), "debug".to_string()),
+    ]);
+    let config = AppConfig::from_source(&source).unwrap();
+
+    assert_eq!(config.host, "localhost");
+    assert_eq!(config.log_level, LogLevel::Debug);
+    assert!(AppConfig::redundant_vars(&source).unwrap().is_empty());
+}
+
+#[test]
+fn should_fail_when_config_fails_to_load() {
+    // `APP_PORT` is set to its default, but `APP_HOST` is required
+    let source = HashMap::from([("APP_PORT".to_string(), "8080".to_string())]);
+
+    assert_eq!(
+        AppConfig::redundant_vars(&source),
+        Err(EnvConfigError::Missing("APP_HOST".to_string()))
+    );
+}
+
+static VALIDATIONS: AtomicUsize = AtomicUsize::new(0);
+
+fn count_validations(_: &ValidatedConfig) -> Result<(), String> {
+    VALIDATIONS.fetch_add(1, Ordering::SeqCst);
+    Ok(())
+}
+
+#[derive(Debug, EnvConfig)]
+#[env_cfg(prefix = "JOB", redundant_vars, validate = "count_validations")]
+struct ValidatedConfig {
+    #[env_cfg(default = "1")]
+    retries: u32, // -> JOB_RETRIES
+    #[env_cfg(default = "60")]
+    timeout_secs: u64, // -> JOB_TIMEOUT_SECS
+}
+
+#[test]
+fn should_validate_the_config_once() {
+    let source = HashMap::from([
+        ("JOB_RETRIES".to_string(), "1".to_string()),
+        ("JOB_TIMEOUT_SECS".to_string(), "60".to_string()),
+    ]);
+
+    let redundant = ValidatedConfig::redundant_vars(&source).unwrap();
+
+    assert_eq!(redundant, vec!["JOB_RETRIES", "JOB_TIMEOUT_SECS"]);
+    assert_eq!(VALIDATIONS.load(Ordering::SeqCst), 1);
+}
@@ -0,0 +1,63 @@
+// `from_source_with_report` tests
+use env_cfg::{EnvConfig, EnvConfigError, LoadReport, Lookup, LookupOutcome};
+
+mod common;
+
+#[derive(Debug, EnvConfig)]
+#[env_cfg(prefix = "DB")]
+#[allow(dead_code)]
+struct DatabaseConfig {
+    host: String, // -> DB_HOST
+    #[env_cfg(env = ["DB_PORT", "PGPORT"], default = 5432)]
+    port: u16,
+    #[cfg(feature = "zeroize")]
+    password: env_cfg::SecretString, // -> DB_PASSWORD
+}
+
+fn lookup(name: &str, outcome: LookupOutcome) -> Lookup {
+    Lookup {
+        name: name.to_string(),
+        outcome,
+    }
+}
+
+#[test]
+fn should_record_every_lookup_in_order() {
+    const ENV_KEYS_VALUES: &[(&str, &str)] = &[
+        ("DB_HOST", "localhost"),
+        ("PGPORT", "6432"),
+        ("DB_PASSWORD", "hunter2"),
+    ];
+    let (result, report) =
+        unsafe { common::with_env_vars(ENV_KEYS_VALUES, DatabaseConfig::from_env_with_report) };
+
+    assert_eq!(result.unwrap().port, 6432);
+    let mut expected = vec![
+        lookup("DB_HOST", LookupOutcome::Set("localhost".to_string())),
+        lookup("DB_PORT", LookupOutcome::Unset),
+        lookup("PGPORT", LookupOutcome::Set("6432".to_string())),
+    ];
+    if cfg!(feature = "zeroize") {
+        expected.push(lookup("DB_PASSWORD", LookupOutcome::Redacted));
+    }
+    assert_eq!(report.lookups, expected);
+    assert!(!report.to_string().contains("hunter2"));
+}
+
+#[test]
+fn should_return_report_when_loading_fails() {
+    let (result, report) =
+        unsafe { common::with_env_vars(&[], DatabaseConfig::from_env_with_report) };
+
+    assert_eq!(
+        result.unwrap_err(),
+        EnvConfigError::Missing("DB_HOST".to_string())
+    );
+    assert_eq!(
+        report,
+        LoadReport {
+            lookups: vec![lookup("DB_HOST", LookupOutcome::Unset)],
+        }
+    );
+    assert_eq!(report.to_string(), "DB_HOST (unset)\n");
+}
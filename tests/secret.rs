@@ -0,0 +1,120 @@
+// `SecretString` and `SecretBytes` loading tests
+#![cfg(feature = "zeroize")]
+use env_cfg::{EnvConfig, SecretBytes, SecretString};
+use zeroize::Zeroize;
+
+mod common;
+
+#[derive(Debug, EnvConfig)]
+#[env_cfg(prefix = "VAULT")]
+struct VaultConfig {
+    address: String,          // -> VAULT_ADDRESS
+    token: SecretString,      // -> VAULT_TOKEN
+    signing_key: SecretBytes, // -> VAULT_SIGNING_KEY
+    #[env_cfg(default = "fallback-token")]
+    fallback: SecretString, // -> VAULT_FALLBACK (with default)
+    unseal_key: Option<SecretString>, // -> VAULT_UNSEAL_KEY (optional)
+}
+
+#[test]
+fn should_load_secrets() {
+    const ENV_KEYS_VALUES: &[(&str, &str)] = &[
+        ("VAULT_ADDRESS", "https://vault.internal"),
+        ("VAULT_TOKEN", "s.hunter2"),
+        ("VAULT_SIGNING_KEY", "k3y"),
+    ];
+    let config =
+        unsafe { common::with_env_vars(ENV_KEYS_VALUES, || VaultConfig::from_env().unwrap()) };
+
+    assert_eq!(config.address, "https://vault.internal");
+    assert_eq!(config.token.expose_secret(), "s.hunter2");
+    assert_eq!(config.signing_key.expose_secret(), b"k3y");
+    assert_eq!(config.fallback.expose_secret(), "fallback-token");
+    assert!(config.unseal_key.is_none());
+}
+
+#[test]
+fn should_not_expose_secrets_in_debug() {
+    const ENV_KEYS_VALUES: &[(&str, &str)] = &[
+        ("VAULT_ADDRESS", "https://vault.internal"),
+        ("VAULT_TOKEN", "s.hunter2"),
+        ("VAULT_SIGNING_KEY", "k3y"),
+        ("VAULT_UNSEAL_KEY", "unseal-me"),
+    ];
+    let config =
+        unsafe { common::with_env_vars(ENV_KEYS_VALUES, || VaultConfig::from_env().unwrap()) };
+
+    let debug = format!("{config:?}");
+    assert!(debug.contains("https://vault.internal"), "{debug}");
+    assert!(debug.contains("SecretString([REDACTED])"), "{debug}");
+    assert!(debug.contains("SecretBytes([REDACTED])"), "{debug}");
+    for secret in ["s.hunter2", "k3y", "fallback-token", "unseal-me"] {
+        assert!(!debug.contains(secret), "{debug}");
+    }
+}
+
+#[test]
+fn should_zeroize_secrets() {
+    let mut secret = SecretString::from("s.hunter2".to_string());
+    secret.zeroize();
+    assert_eq!(secret.expose_secret(), "");
+
+    let mut key = SecretBytes::from(b"k3y".to_vec());
+    key.zeroize();
+    assert!(key.expose_secret().is_empty());
+}
+
+#[cfg(unix)]
+#[test]
+fn should_not_expose_non_unicode_secrets_in_errors() {
+    use std::os::unix::ffi::OsStrExt;
+
+    const ENV_KEYS_VALUES: &[(&str, &str)] = &[
+        ("VAULT_ADDRESS", "https://vault.internal"),
+        ("VAULT_SIGNING_KEY", "k3y"),
+    ];
+    let result = unsafe {
+        common::with_env_vars(ENV_KEYS_VALUES, || {
+            std::env::set_var("VAULT_TOKEN", std::ffi::OsStr::from_bytes(b"s.hunter\xFF"));
+            let loaded = VaultConfig::from_env();
+            std::env::remove_var("VAULT_TOKEN");
+            loaded
+        })
+    };
+
+    assert_eq!(
+        result.unwrap_err(),
+        env_cfg::EnvConfigError::Parse(
+            "VAULT_TOKEN".to_string(),
+            "Invalid Unicode (value redacted)".to_string()
+        )
+    );
+}
+
+#[derive(Debug, EnvConfig)]
+#[env_cfg(prefix = "VAULT", keys)]
+struct KeyedVaultConfig {
+    address: String,                  // -> VAULT_ADDRESS
+    token: SecretString,              // -> VAULT_TOKEN
+    unseal_key: Option<SecretString>, // -> VAULT_UNSEAL_KEY (optional)
+}
+
+#[test]
+fn should_redact_secrets_read_by_key() {
+    const ENV_KEYS_VALUES: &[(&str, &str)] = &[
+        ("VAULT_ADDRESS", "https://vault.internal"),
+        ("VAULT_TOKEN", "s.hunter2"),
+    ];
+    let config =
+        unsafe { common::with_env_vars(ENV_KEYS_VALUES, || KeyedVaultConfig::from_env().unwrap()) };
+
+    assert_eq!(
+        config.get(KeyedVaultConfigKey::Address).as_deref(),
+        Some("https://vault.internal")
+    );
+    assert_eq!(
+        config.get(KeyedVaultConfigKey::Token).as_deref(),
+        Some("[REDACTED]")
+    );
+    assert_eq!(config.get(KeyedVaultConfigKey::UnsealKey), None);
+}
@@ -0,0 +1,83 @@
+// Named section tests
+use std::collections::HashMap;
+
+use env_cfg::{EnvConfig, EnvConfigError, section_names};
+
+mod common;
+
+#[derive(Debug, EnvConfig, PartialEq)]
+#[env_cfg(no_prefix)]
+struct UpstreamConfig {
+    host: String, // -> <SECTION>_HOST
+    #[env_cfg(default = "80")]
+    port: u16, // -> <SECTION>_PORT
+}
+
+#[derive(Debug, EnvConfig)]
+#[env_cfg(prefix = "GATEWAY")]
+struct GatewayConfig {
+    #[env_cfg(sections = "UPSTREAM")]
+    upstreams: Vec<(String, UpstreamConfig)>, // -> UPSTREAM_<NAME>_HOST, UPSTREAM_<NAME>_PORT
+    #[env_cfg(sections = "{prefix}_ROUTE")]
+    routes: Vec<(String, UpstreamConfig)>, // -> GATEWAY_ROUTE_<NAME>_HOST, ...
+    listen: String, // -> GATEWAY_LISTEN
+}
+
+#[test]
+fn should_load_named_sections() {
+    const ENV_KEYS_VALUES: &[(&str, &str)] = &[
+        ("UPSTREAM_EU_WEST_HOST", "eu.example.com"),
+        ("UPSTREAM_EU_WEST_PORT", "443"),
+        ("UPSTREAM_API_HOST", "api.internal"),
+        ("UPSTREAM_TIMEOUT", "30"), // not a variable of a section
+        ("GATEWAY_LISTEN", "0.0.0.0:8080"),
+    ];
+
+    let config =
+        unsafe { common::with_env_vars(ENV_KEYS_VALUES, || GatewayConfig::from_env().unwrap()) };
+
+    assert_eq!(
+        config.upstreams,
+        [
+            (
+                "API".to_string(),
+                UpstreamConfig {
+                    host: "api.internal".to_string(),
+                    port: 80,
+                }
+            ),
+            (
+                "EU_WEST".to_string(),
+                UpstreamConfig {
+                    host: "eu.example.com".to_string(),
+                    port: 443,
+                }
+            ),
+        ]
+    );
+    assert!(config.routes.is_empty());
+    assert_eq!(config.listen, "0.0.0.0:8080");
+}
+
+#[test]
+fn should_err_on_partial_section() {
+    let vars = HashMap::from([
+        ("GATEWAY_ROUTE_ADMIN_PORT".to_string(), "9000".to_string()),
+        ("GATEWAY_LISTEN".to_string(), "0.0.0.0:8080".to_string()),
+    ]);
+
+    assert_eq!(
+        section_names::<UpstreamConfig>(&vars, "GATEWAY_ROUTE"),
+        ["ADMIN"]
+    );
+    assert_eq!(
+        GatewayConfig::missing_vars(&vars),
+        ["GATEWAY_ROUTE_ADMIN_HOST"]
+    );
+    assert_eq!(
+        GatewayConfig::try_from(&vars).unwrap_err(),
+        EnvConfigError::Missing("GATEWAY_ROUTE_ADMIN_HOST".to_string())
+            .context("section `ADMIN` of `GATEWAY_ROUTE`")
+            .context("nested UpstreamConfig")
+    );
+}
@@ -0,0 +1,103 @@
+// Loading from sources other than the process environment
+use std::collections::{BTreeMap, HashMap};
+
+use env_cfg::{EnvConfig, EnvConfigError};
+
+#[derive(Debug, EnvConfig, PartialEq)]
+#[env_cfg(prefix = "DB")]
+struct MapDatabaseConfig {
+    host: String, // -> DB_HOST
+    #[env_cfg(default = "5432")]
+    port: u16, // -> DB_PORT (with default)
+}
+
+#[derive(Debug, EnvConfig, PartialEq)]
+#[env_cfg(prefix = "APP")]
+struct MapAppConfig {
+    #[env_cfg(expand)]
+    name: String, // -> APP_NAME (expanded from the same map)
+    timeout: Option<u64>, // -> APP_TIMEOUT (optional)
+    #[env_cfg(nested)]
+    database: MapDatabaseConfig,
+}
+
+fn map(entries: &[(&str, &str)]) -> HashMap<String, String> {
+    entries
+        .iter()
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect()
+}
+
+// NOTE: these tests do not need to run with `common::with_env_vars` because they never read
+// ENV variables
+
+#[test]
+fn should_load_from_hash_map() {
+    let vars = map(&[
+        ("APP_NAME", "${DB_HOST}-app"),
+        ("APP_TIMEOUT", "30"),
+        ("DB_HOST", "db.internal"),
+    ]);
+    let config = MapAppConfig::try_from(&vars).unwrap();
+
+    assert_eq!(
+        config,
+        MapAppConfig {
+            name: "db.internal-app".to_string(),
+            timeout: Some(30),
+            database: MapDatabaseConfig {
+                host: "db.internal".to_string(),
+                port: 5432,
+            },
+        }
+    );
+}
+
+#[test]
+fn should_load_from_source() {
+    let vars = map(&[("DB_HOST", "localhost"), ("DB_PORT", "6543")]);
+    let config = MapDatabaseConfig::from_source(&vars).unwrap();
+
+    assert_eq!(config.host, "localhost");
+    assert_eq!(config.port, 6543);
+}
+
+#[test]
+fn should_err_if_map_is_missing_variables() {
+    let vars = map(&[("APP_NAME", "app")]);
+    let result = MapAppConfig::try_from(&vars);
+
+    assert!(matches!(
+        result,
+        Err(EnvConfigError::Nested { context, .. }) if context.contains("nested")
+    ));
+}
+
+#[test]
+fn should_load_from_btree_map() {
+    let vars: BTreeMap<String, String> = [("DB_HOST", "db.internal"), ("DB_PORT", "6543")]
+        .iter()
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect();
+    let config = MapDatabaseConfig::from_source(&vars).unwrap();
+
+    assert_eq!(
+        config,
+        MapDatabaseConfig {
+            host: "db.internal".to_string(),
+            port: 6543,
+        }
+    );
+}
+
+#[test]
+fn should_load_with_resolver_closure() {
+    let vars = map(&[("APP_NAME", "${DB_HOST}-app"), ("DB_HOST", "db.internal")]);
+    let config = MapAppConfig::from_env_with(|name| vars.get(name).cloned()).unwrap();
+    let missing = MapAppConfig::from_env_with(|_| None);
+
+    assert_eq!(config.name, "db.internal-app");
+    assert_eq!(config.timeout, None);
+    assert_eq!(config.database.host, "db.internal");
+    assert!(matches!(missing, Err(EnvConfigError::Missing(var)) if var == "APP_NAME"));
+}
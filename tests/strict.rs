@@ -0,0 +1,47 @@
+// `strict` tests
+use env_cfg::{EnvConfig, EnvConfigError};
+
+mod common;
+
+#[derive(Debug, EnvConfig)]
+#[env_cfg(prefix = "API", strict)]
+struct StrictConfig {
+    host: String, // -> API_HOST
+    #[env_cfg(default = 8080)]
+    port: u16, // -> API_PORT (default ignored)
+    #[env_cfg(default = "info")]
+    log_level: Option<String>, // -> API_LOG_LEVEL (default ignored)
+    timeout_secs: Option<u64>, // -> API_TIMEOUT_SECS (optional)
+}
+
+#[test]
+fn should_load_strict_config_when_every_variable_is_set() {
+    const ENV_KEYS_VALUES: &[(&str, &str)] = &[
+        ("API_HOST", "localhost"),
+        ("API_PORT", "9090"),
+        ("API_LOG_LEVEL", "debug"),
+    ];
+    let config =
+        unsafe { common::with_env_vars(ENV_KEYS_VALUES, || StrictConfig::from_env().unwrap()) };
+
+    assert_eq!(config.host, "localhost");
+    assert_eq!(config.port, 9090);
+    assert_eq!(config.log_level.as_deref(), Some("debug"));
+    assert_eq!(config.timeout_secs, None);
+}
+
+#[test]
+fn should_report_defaulted_fields_as_missing_in_strict_mode() {
+    const ENV_KEYS_VALUES: &[(&str, &str)] = &[("API_HOST", "localhost")];
+    let (result, missing) = unsafe {
+        common::with_env_vars(ENV_KEYS_VALUES, || {
+            (StrictConfig::from_env(), StrictConfig::missing_env_vars())
+        })
+    };
+
+    assert_eq!(
+        result.unwrap_err(),
+        EnvConfigError::Missing("API_PORT".to_string())
+    );
+    assert_eq!(missing, ["API_PORT", "API_LOG_LEVEL"]);
+}
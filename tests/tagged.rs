@@ -0,0 +1,106 @@
+// Enums with a variant-selecting env var
+use env_cfg::{EnvConfig, EnvConfigError};
+
+mod common;
+
+#[derive(Debug, PartialEq, EnvConfig)]
+#[env_cfg(prefix = "S3")]
+struct S3Config {
+    bucket: String, // -> S3_BUCKET
+    #[env_cfg(default = "us-east-1")]
+    region: String, // -> S3_REGION (with default)
+}
+
+#[derive(Debug, PartialEq, EnvConfig)]
+#[env_cfg(no_prefix)]
+struct LocalConfig {
+    local_path: String, // -> LOCAL_PATH
+}
+
+#[derive(Debug, PartialEq, EnvConfig)]
+#[env_cfg(tag = "STORAGE_KIND")]
+enum StorageConfig {
+    S3(S3Config),       // STORAGE_KIND=s3
+    Local(LocalConfig), // STORAGE_KIND=local
+    #[env_cfg(rename = "mem")]
+    InMemory, // STORAGE_KIND=mem
+}
+
+#[derive(Debug, EnvConfig)]
+#[env_cfg(prefix = "APP")]
+struct AppConfig {
+    name: String, // -> APP_NAME
+    #[env_cfg(nested)]
+    storage: StorageConfig,
+}
+
+#[test]
+fn should_load_selected_variant() {
+    const ENV_KEYS_VALUES: &[(&str, &str)] = &[
+        ("STORAGE_KIND", "s3"),
+        ("S3_BUCKET", "assets"),
+        ("LOCAL_PATH", "/var/data"),
+    ];
+    let config =
+        unsafe { common::with_env_vars(ENV_KEYS_VALUES, || StorageConfig::from_env().unwrap()) };
+
+    assert_eq!(
+        config,
+        StorageConfig::S3(S3Config {
+            bucket: "assets".to_string(),
+            region: "us-east-1".to_string(),
+        })
+    );
+}
+
+#[test]
+fn should_load_nested_and_unit_variants() {
+    const ENV_KEYS_VALUES: &[(&str, &str)] = &[
+        ("APP_NAME", "app"),
+        ("STORAGE_KIND", "local"),
+        ("LOCAL_PATH", "/var/data"),
+    ];
+    let config =
+        unsafe { common::with_env_vars(ENV_KEYS_VALUES, || AppConfig::from_env().unwrap()) };
+    let in_memory = unsafe {
+        common::with_env_vars(&[("STORAGE_KIND", "mem")], || {
+            StorageConfig::from_env().unwrap()
+        })
+    };
+
+    assert_eq!(config.name, "app");
+    assert_eq!(
+        config.storage,
+        StorageConfig::Local(LocalConfig {
+            local_path: "/var/data".to_string(),
+        })
+    );
+    assert_eq!(in_memory, StorageConfig::InMemory);
+}
+
+#[test]
+fn should_err_on_missing_or_unknown_tag() {
+    let missing = unsafe { common::with_env_vars(&[], StorageConfig::from_env) };
+    let unknown =
+        unsafe { common::with_env_vars(&[("STORAGE_KIND", "gcs")], StorageConfig::from_env) };
+
+    assert!(matches!(missing, Err(EnvConfigError::Missing(var)) if var == "STORAGE_KIND"));
+    assert!(
+        matches!(unknown, Err(EnvConfigError::Parse(var, msg)) if var == "STORAGE_KIND" && msg == "Unknown variant `gcs`, expected one of: s3, local, mem")
+    );
+}
+
+#[test]
+fn should_report_missing_variables_of_selected_variant() {
+    let (no_tag, s3, in_memory) = unsafe {
+        (
+            common::with_env_vars(&[], StorageConfig::missing_env_vars),
+            common::with_env_vars(&[("STORAGE_KIND", "s3")], StorageConfig::missing_env_vars),
+            common::with_env_vars(&[("STORAGE_KIND", "mem")], StorageConfig::missing_env_vars),
+        )
+    };
+
+    assert_eq!(no_tag, ["STORAGE_KIND"]);
+    assert_eq!(s3, ["S3_BUCKET"]);
+    assert!(in_memory.is_empty());
+}
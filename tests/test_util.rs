@@ -0,0 +1,68 @@
+// `ScopedEnv` tests. These run in their own binary, since `ScopedEnv` and the
+// `common::with_env_vars` helper of the other tests don't share a lock
+#![cfg(feature = "test-util")]
+use env_cfg::EnvConfig;
+use env_cfg::test_util::ScopedEnv;
+
+#[derive(Debug, EnvConfig)]
+#[env_cfg(prefix = "SCOPED")]
+struct ScopedConfig {
+    host: String,
+    port: u16,
+}
+
+#[test]
+fn should_set_variables_while_alive() {
+    let env = unsafe { ScopedEnv::new(&[("SCOPED_HOST", "localhost"), ("SCOPED_PORT", "8080")]) };
+
+    let config = ScopedConfig::from_env().unwrap();
+    assert_eq!(config.host, "localhost");
+    assert_eq!(config.port, 8080);
+
+    drop(env);
+    assert!(std::env::var_os("SCOPED_HOST").is_none());
+    assert!(std::env::var_os("SCOPED_PORT").is_none());
+}
+
+#[test]
+fn should_restore_pre_existing_values() {
+    {
+        let mut outer = unsafe { ScopedEnv::new(&[]) };
+        // Simulate variables set before the guard, e.g. by the CI environment
+        unsafe {
+            std::env::set_var("SCOPED_RESTORED_HOST", "original");
+            std::env::set_var("SCOPED_RESTORED_PORT", "1");
+        }
+
+        unsafe {
+            outer.set("SCOPED_RESTORED_HOST", "first");
+            outer.set("SCOPED_RESTORED_HOST", "second");
+            outer.remove("SCOPED_RESTORED_PORT");
+        }
+        assert_eq!(std::env::var("SCOPED_RESTORED_HOST").unwrap(), "second");
+        assert!(std::env::var_os("SCOPED_RESTORED_PORT").is_none());
+
+        drop(outer);
+        assert_eq!(std::env::var("SCOPED_RESTORED_HOST").unwrap(), "original");
+        assert_eq!(std::env::var("SCOPED_RESTORED_PORT").unwrap(), "1");
+    }
+
+    let env = unsafe { ScopedEnv::new(&[]) };
+    unsafe {
+        std::env::remove_var("SCOPED_RESTORED_HOST");
+        std::env::remove_var("SCOPED_RESTORED_PORT");
+    }
+    drop(env);
+}
+
+#[test]
+fn should_restore_values_after_a_panic() {
+    let result = std::panic::catch_unwind(|| {
+        let _env = unsafe { ScopedEnv::new(&[("SCOPED_PANIC", "set")]) };
+        panic!("test failed while the guard was alive");
+    });
+
+    assert!(result.is_err());
+    let _env = unsafe { ScopedEnv::new(&[]) };
+    assert!(std::env::var_os("SCOPED_PANIC").is_none());
+}
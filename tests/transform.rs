@@ -0,0 +1,84 @@
+// `transform` tests
+use env_cfg::{EnvConfig, EnvConfigError};
+
+mod common;
+
+fn double(value: u32) -> u32 {
+    value * 2
+}
+
+fn add_one(value: u32) -> u32 {
+    value + 1
+}
+
+fn require_even(value: u32) -> Result<u32, String> {
+    if !value.is_multiple_of(2) {
+        return Err(format!("{value} is not even"));
+    }
+    Ok(value)
+}
+
+fn trim(value: String) -> String {
+    value.trim().to_string()
+}
+
+fn lowercase(value: String) -> String {
+    value.to_lowercase()
+}
+
+#[derive(Debug, EnvConfig)]
+#[env_cfg(prefix = "POOL")]
+struct PoolConfig {
+    #[env_cfg(transform = "double", transform = "add_one")]
+    min_size: u32, // -> POOL_MIN_SIZE, doubled then incremented
+    #[env_cfg(transform = "add_one", transform = "double")]
+    max_size: u32, // -> POOL_MAX_SIZE, incremented then doubled
+    #[env_cfg(transform = "double", validate_map = "require_even", default = "3")]
+    batch_size: u32, // -> POOL_BATCH_SIZE, validated after doubling
+    #[env_cfg(transform = "trim")]
+    #[env_cfg(transform = "lowercase")]
+    name: Option<String>, // -> POOL_NAME
+}
+
+#[test]
+fn should_apply_transforms_in_declaration_order() {
+    const ENV_KEYS_VALUES: &[(&str, &str)] = &[
+        ("POOL_MIN_SIZE", "5"),
+        ("POOL_MAX_SIZE", "5"),
+        ("POOL_NAME", "  Primary "),
+    ];
+    let config =
+        unsafe { common::with_env_vars(ENV_KEYS_VALUES, || PoolConfig::from_env().unwrap()) };
+
+    assert_eq!(config.min_size, 11);
+    assert_eq!(config.max_size, 12);
+    assert_eq!(config.name.as_deref(), Some("primary"));
+}
+
+#[test]
+fn should_transform_before_validating() {
+    const ENV_KEYS_VALUES: &[(&str, &str)] = &[
+        ("POOL_MIN_SIZE", "1"),
+        ("POOL_MAX_SIZE", "1"),
+        ("POOL_BATCH_SIZE", "7"),
+    ];
+    let config =
+        unsafe { common::with_env_vars(ENV_KEYS_VALUES, || PoolConfig::from_env().unwrap()) };
+
+    // 7 and the default 3 are odd, but validated once doubled
+    assert_eq!(config.batch_size, 14);
+    let config =
+        unsafe { common::with_env_vars(&ENV_KEYS_VALUES[..2], || PoolConfig::from_env().unwrap()) };
+    assert_eq!(config.batch_size, 6);
+}
+
+#[test]
+fn should_report_parse_errors_before_transforming() {
+    const ENV_KEYS_VALUES: &[(&str, &str)] = &[("POOL_MIN_SIZE", "-5"), ("POOL_MAX_SIZE", "1")];
+    let result = unsafe { common::with_env_vars(ENV_KEYS_VALUES, PoolConfig::from_env) };
+
+    assert!(matches!(
+        result,
+        Err(EnvConfigError::Parse(var, _)) if var == "POOL_MIN_SIZE"
+    ));
+}
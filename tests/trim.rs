@@ -0,0 +1,103 @@
+// Value trimming tests
+use env_cfg::{EnvConfig, trim_matching_pair};
+
+mod common;
+
+#[derive(Debug, EnvConfig)]
+#[env_cfg(no_prefix)]
+struct QuotedConfig {
+    #[env_cfg(unquote)]
+    api_secret: String, // -> API_SECRET
+    #[env_cfg(unquote)]
+    api_port: u16, // -> API_PORT
+    #[env_cfg(trim_matches = "|")]
+    api_banner: Option<String>, // -> API_BANNER (optional)
+    api_raw: String, // -> API_RAW (not trimmed)
+}
+
+#[test]
+fn should_strip_matching_quotes() {
+    const ENV_KEYS_VALUES: &[(&str, &str)] = &[
+        ("API_SECRET", "\"secret\""),
+        ("API_PORT", "'8080'"),
+        ("API_BANNER", "|hello|"),
+        ("API_RAW", "\"raw\""),
+    ];
+    let config =
+        unsafe { common::with_env_vars(ENV_KEYS_VALUES, || QuotedConfig::from_env().unwrap()) };
+
+    assert_eq!(config.api_secret, "secret");
+    assert_eq!(config.api_port, 8080);
+    assert_eq!(config.api_banner, Some("hello".to_string()));
+    assert_eq!(config.api_raw, "\"raw\"");
+}
+
+#[test]
+fn should_keep_unmatched_quotes() {
+    const ENV_KEYS_VALUES: &[(&str, &str)] = &[
+        ("API_SECRET", "\"secret'"),
+        ("API_PORT", "8080"),
+        ("API_RAW", "raw"),
+    ];
+    let config =
+        unsafe { common::with_env_vars(ENV_KEYS_VALUES, || QuotedConfig::from_env().unwrap()) };
+
+    assert_eq!(config.api_secret, "\"secret'");
+    assert_eq!(config.api_port, 8080);
+    assert_eq!(config.api_banner, None);
+}
+
+#[test]
+fn should_only_trim_matching_pairs() {
+    assert_eq!(trim_matching_pair("\"value\"", "\""), "value");
+    assert_eq!(trim_matching_pair("\"\"value\"\"", "\""), "\"value\"");
+    assert_eq!(trim_matching_pair("\"value", "\""), "\"value");
+    assert_eq!(trim_matching_pair("value\"", "\""), "value\"");
+    assert_eq!(trim_matching_pair("\"", "\""), "\"");
+    assert_eq!(trim_matching_pair("\"\"", "\""), "");
+    assert_eq!(trim_matching_pair("'value'", "\""), "'value'");
+}
+
+#[derive(Debug, EnvConfig)]
+#[env_cfg(prefix = "AUTH")]
+struct TokenConfig {
+    #[env_cfg(trim_prefix = "Bearer ")]
+    token: String, // -> AUTH_TOKEN
+    #[env_cfg(trim_prefix = "Bearer ", empty_as_none)]
+    refresh_token: Option<String>, // -> AUTH_REFRESH_TOKEN (optional)
+    #[env_cfg(trim_prefix = "v", default = "1")]
+    version: u8, // -> AUTH_VERSION (with default, used as written)
+}
+
+#[test]
+fn should_strip_value_prefix() {
+    const PRESENT: &[(&str, &str)] = &[
+        ("AUTH_TOKEN", "Bearer xyz"),
+        ("AUTH_REFRESH_TOKEN", "Bearer abc"),
+        ("AUTH_VERSION", "v2"),
+    ];
+    let config = unsafe { common::with_env_vars(PRESENT, || TokenConfig::from_env().unwrap()) };
+
+    assert_eq!(config.token, "xyz");
+    assert_eq!(config.refresh_token.as_deref(), Some("abc"));
+    assert_eq!(config.version, 2);
+
+    const ABSENT: &[(&str, &str)] = &[("AUTH_TOKEN", "xyz"), ("AUTH_VERSION", "3")];
+    let config = unsafe { common::with_env_vars(ABSENT, || TokenConfig::from_env().unwrap()) };
+
+    assert_eq!(config.token, "xyz");
+    assert_eq!(config.refresh_token, None);
+    assert_eq!(config.version, 3);
+}
+
+#[test]
+fn should_strip_whole_value_prefix() {
+    const ENV_KEYS_VALUES: &[(&str, &str)] =
+        &[("AUTH_TOKEN", "Bearer "), ("AUTH_REFRESH_TOKEN", "Bearer ")];
+    let config =
+        unsafe { common::with_env_vars(ENV_KEYS_VALUES, || TokenConfig::from_env().unwrap()) };
+
+    assert_eq!(config.token, "");
+    assert_eq!(config.refresh_token, None); // empty once stripped
+    assert_eq!(config.version, 1);
+}
@@ -0,0 +1,89 @@
+// `try_from` and `env_var_try_from` tests
+use env_cfg::{EnvConfig, EnvConfigError, env_var_optional_try_from, env_var_try_from};
+
+mod common;
+
+/// Implements `TryFrom<String>` but not `FromStr`
+#[derive(Debug, PartialEq)]
+struct Hostname(String);
+
+impl TryFrom<String> for Hostname {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        if value.is_empty() || value.contains(char::is_whitespace) {
+            return Err(format!("`{value}` is not a valid hostname"));
+        }
+        Ok(Hostname(value))
+    }
+}
+
+#[derive(Debug, EnvConfig)]
+#[env_cfg(prefix = "PROXY")]
+struct ProxyConfig {
+    #[env_cfg(try_from)]
+    host: Hostname, // -> PROXY_HOST
+    #[env_cfg(try_from, default = "localhost")]
+    fallback_host: Hostname, // -> PROXY_FALLBACK_HOST (with default)
+    #[env_cfg(try_from)]
+    upstream_host: Option<Hostname>, // -> PROXY_UPSTREAM_HOST (optional)
+}
+
+#[test]
+fn should_convert_with_try_from() {
+    const ENV_KEYS_VALUES: &[(&str, &str)] = &[
+        ("PROXY_HOST", "proxy.internal"),
+        ("PROXY_UPSTREAM_HOST", "api.internal"),
+    ];
+    let config =
+        unsafe { common::with_env_vars(ENV_KEYS_VALUES, || ProxyConfig::from_env().unwrap()) };
+
+    assert_eq!(config.host, Hostname("proxy.internal".to_string()));
+    assert_eq!(config.fallback_host, Hostname("localhost".to_string()));
+    assert_eq!(
+        config.upstream_host,
+        Some(Hostname("api.internal".to_string()))
+    );
+}
+
+#[test]
+fn should_report_try_from_errors_as_parse_errors() {
+    const ENV_KEYS_VALUES: &[(&str, &str)] = &[("PROXY_HOST", "not a host")];
+    let result = unsafe { common::with_env_vars(ENV_KEYS_VALUES, ProxyConfig::from_env) };
+    assert!(matches!(
+        result,
+        Err(EnvConfigError::Parse(var, message))
+            if var == "PROXY_HOST" && message == "`not a host` is not a valid hostname"
+    ));
+}
+
+#[test]
+fn should_load_with_try_from_helpers() {
+    const ENV_KEYS_VALUES: &[(&str, &str)] = &[("HOST", "example.com"), ("BAD_HOST", "a b")];
+    let results = unsafe {
+        common::with_env_vars(ENV_KEYS_VALUES, || {
+            (
+                env_var_try_from::<Hostname>("HOST"),
+                env_var_try_from::<Hostname>("BAD_HOST"),
+                env_var_try_from::<Hostname>("MISSING_HOST"),
+                env_var_optional_try_from::<Hostname>("HOST"),
+                env_var_optional_try_from::<Hostname>("MISSING_HOST"),
+            )
+        })
+    };
+
+    assert_eq!(results.0, Ok(Hostname("example.com".to_string())));
+    assert_eq!(
+        results.1,
+        Err(EnvConfigError::Parse(
+            "BAD_HOST".into(),
+            "`a b` is not a valid hostname".into()
+        ))
+    );
+    assert_eq!(
+        results.2,
+        Err(EnvConfigError::Missing("MISSING_HOST".into()))
+    );
+    assert_eq!(results.3, Ok(Some(Hostname("example.com".to_string()))));
+    assert_eq!(results.4, Ok(None));
+}
@@ -0,0 +1,10 @@
+use env_cfg::EnvConfig;
+
+#[derive(EnvConfig)]
+#[env_cfg(prefix = "CANVAS")]
+struct InvalidConfig {
+    #[env_cfg(alias(parse_with = "str::to_uppercase"))]
+    origin: String,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use env_cfg::EnvConfig;
+
+#[derive(EnvConfig)]
+struct InvalidConfig {
+    #[env_cfg(clamp(256, 1))]
+    workers: u32,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use env_cfg::EnvConfig;
+
+#[derive(EnvConfig)]
+struct InvalidConfig {
+    #[env_cfg(default_from = "bind_addr")]
+    public_url: String,
+    bind_addr: String,
+}
+
+fn main() {}
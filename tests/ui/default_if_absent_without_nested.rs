@@ -0,0 +1,9 @@
+use env_cfg::EnvConfig;
+
+#[derive(EnvConfig)]
+struct InvalidConfig {
+    #[env_cfg(default_if_absent)]
+    host: String,
+}
+
+fn main() {}
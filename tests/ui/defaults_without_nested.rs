@@ -0,0 +1,10 @@
+use env_cfg::EnvConfig;
+
+#[derive(EnvConfig)]
+#[env_cfg(prefix = "APP")]
+struct InvalidConfig {
+    #[env_cfg(defaults(DB_PORT = "5432"))]
+    port: u16,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use env_cfg::EnvConfig;
+
+#[derive(EnvConfig)]
+#[env_cfg(derive_from = "DATABASE_URL")]
+struct InvalidConfig {
+    host: String,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use env_cfg::EnvConfig;
+
+#[derive(EnvConfig)]
+#[env_cfg(dotted, rename_all = "ACRONYM_SNAKE_CASE")]
+struct InvalidConfig {
+    name: String,
+}
+
+fn main() {}
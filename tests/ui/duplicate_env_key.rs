@@ -0,0 +1,11 @@
+use env_cfg::EnvConfig;
+
+#[derive(EnvConfig)]
+#[env_cfg(prefix = "DB")]
+struct InvalidConfig {
+    url: String,
+    #[env_cfg(env = "DB_URL")]
+    replica_url: String,
+}
+
+fn main() {}
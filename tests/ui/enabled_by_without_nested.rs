@@ -0,0 +1,9 @@
+use env_cfg::EnvConfig;
+
+#[derive(EnvConfig)]
+struct InvalidConfig {
+    #[env_cfg(enabled_by = "TRACING_ENABLED")]
+    endpoint: String,
+}
+
+fn main() {}
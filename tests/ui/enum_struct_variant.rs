@@ -0,0 +1,9 @@
+use env_cfg::EnvConfig;
+
+#[derive(EnvConfig)]
+#[env_cfg(tag = "STORAGE_KIND")]
+enum InvalidConfig {
+    Local { path: String },
+}
+
+fn main() {}
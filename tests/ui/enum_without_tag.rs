@@ -0,0 +1,8 @@
+use env_cfg::EnvConfig;
+
+#[derive(EnvConfig)]
+enum InvalidConfig {
+    Memory,
+}
+
+fn main() {}
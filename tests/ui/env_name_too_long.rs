@@ -0,0 +1,9 @@
+use env_cfg::EnvConfig;
+
+#[derive(EnvConfig)]
+#[env_cfg(prefix = "ACCOUNTS_RECEIVABLE_RECONCILIATION_ACCOUNTS_RECEIVABLE_RECONCILIATION_ACCOUNTS_RECEIVABLE_RECONCILIATION_ACCOUNTS_RECEIVABLE_RECONCILIATION_ACCOUNTS_RECEIVABLE_RECONCILIATION_ACCOUNTS_RECEIVABLE_RECONCILIATION_ACCOUNTS_RECEIVABLE_RECONCILIATION_WORKER")]
+struct InvalidConfig {
+    upstream_settlement_batch_timeout: u64,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use env_cfg::EnvConfig;
+
+#[derive(EnvConfig)]
+struct InvalidConfig {
+    #[env_cfg(error_if_set)]
+    url: String,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use env_cfg::EnvConfig;
+
+#[derive(EnvConfig)]
+struct InvalidConfig {
+    #[env_cfg(flag, empty_as_none)]
+    verbose: bool,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use env_cfg::EnvConfig;
+
+#[derive(EnvConfig)]
+#[env_cfg(prefix = "CACHE", global)]
+struct InvalidConfig<T: std::str::FromStr> {
+    ttl: T,
+}
+
+fn main() {}
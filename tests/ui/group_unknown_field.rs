@@ -0,0 +1,10 @@
+use env_cfg::EnvConfig;
+
+#[derive(EnvConfig)]
+#[env_cfg(group("db_host", "db_port"))]
+struct InvalidConfig {
+    db_host: String,
+    port: u16,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use env_cfg::EnvConfig;
+
+#[derive(EnvConfig)]
+struct InvalidConfig {
+    #[env_cfg(env = ["DB_NAME", "DB NAME"])]
+    name: String,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use env_cfg::EnvConfig;
+
+#[derive(EnvConfig)]
+#[env_cfg(prefix = "my-app")]
+struct InvalidConfig {
+    name: String,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use env_cfg::EnvConfig;
+
+#[derive(EnvConfig)]
+#[env_cfg(tag = "BACKEND", keys)]
+enum InvalidConfig {
+    Memory,
+}
+
+fn main() {}
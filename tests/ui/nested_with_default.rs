@@ -0,0 +1,14 @@
+use env_cfg::EnvConfig;
+
+#[derive(EnvConfig)]
+struct InnerConfig {
+    url: String,
+}
+
+#[derive(EnvConfig)]
+struct InvalidConfig {
+    #[env_cfg(nested, default = "something")]
+    inner: InnerConfig,
+}
+
+fn main() {}
@@ -0,0 +1,18 @@
+use env_cfg::EnvConfig;
+
+#[derive(EnvConfig)]
+struct InnerConfig {
+    url: String,
+}
+
+fn some_parser(_: String) -> InnerConfig {
+    unimplemented!()
+}
+
+#[derive(EnvConfig)]
+struct InvalidConfig {
+    #[env_cfg(nested, parse_with = "some_parser")]
+    inner: InnerConfig,
+}
+
+fn main() {}
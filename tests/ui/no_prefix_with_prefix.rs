@@ -0,0 +1,9 @@
+use env_cfg::EnvConfig;
+
+#[derive(EnvConfig)]
+#[env_cfg(no_prefix, prefix = "APP")]
+struct InvalidConfig {
+    retries: u8,
+}
+
+fn main() {}
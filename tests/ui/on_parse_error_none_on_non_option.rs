@@ -0,0 +1,10 @@
+use env_cfg::EnvConfig;
+
+#[derive(EnvConfig)]
+#[env_cfg(prefix = "WORKER")]
+struct InvalidConfig {
+    #[env_cfg(on_parse_error = "none")]
+    threads: u8,
+}
+
+fn main() {}
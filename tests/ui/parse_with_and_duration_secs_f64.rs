@@ -0,0 +1,15 @@
+use std::time::Duration;
+
+use env_cfg::EnvConfig;
+
+fn parse_interval(value: String) -> Duration {
+    Duration::from_secs(value.parse().unwrap())
+}
+
+#[derive(EnvConfig)]
+struct InvalidConfig {
+    #[env_cfg(duration_secs_f64, parse_with = "parse_interval")]
+    interval: Duration,
+}
+
+fn main() {}
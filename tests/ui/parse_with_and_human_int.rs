@@ -0,0 +1,13 @@
+use env_cfg::EnvConfig;
+
+fn parse_retries(value: String) -> u8 {
+    value.parse().unwrap()
+}
+
+#[derive(EnvConfig)]
+struct InvalidConfig {
+    #[env_cfg(parse_with = "parse_retries", human_int)]
+    retries: u8,
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+use env_cfg::EnvConfig;
+
+fn parse_csv<T: std::str::FromStr>(s: String) -> Vec<T> {
+    s.split(',').filter_map(|item| item.parse().ok()).collect()
+}
+
+#[derive(EnvConfig)]
+struct InvalidConfig {
+    #[env_cfg(parse_with = "parse_csv<u16>")]
+    ports: Vec<u16>,
+}
+
+fn main() {}
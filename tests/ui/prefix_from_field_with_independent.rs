@@ -0,0 +1,16 @@
+use env_cfg::EnvConfig;
+
+#[derive(EnvConfig)]
+#[env_cfg(no_prefix)]
+struct DatabaseConfig {
+    host: String,
+}
+
+#[derive(EnvConfig)]
+#[env_cfg(prefix = "APP")]
+struct InvalidConfig {
+    #[env_cfg(nested, independent, prefix_from_field)]
+    primary_db: DatabaseConfig,
+}
+
+fn main() {}
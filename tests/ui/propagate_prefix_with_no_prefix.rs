@@ -0,0 +1,16 @@
+use env_cfg::EnvConfig;
+
+#[derive(EnvConfig)]
+#[env_cfg(no_prefix)]
+struct DatabaseConfig {
+    host: String,
+}
+
+#[derive(EnvConfig)]
+#[env_cfg(no_prefix, propagate_prefix)]
+struct InvalidConfig {
+    #[env_cfg(nested)]
+    database: DatabaseConfig,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use env_cfg::EnvConfig;
+
+#[derive(EnvConfig)]
+#[env_cfg(redundant_vars)]
+struct InvalidConfig {
+    host: String,
+    port: u16,
+}
+
+fn main() {}
@@ -0,0 +1,15 @@
+use env_cfg::EnvConfig;
+
+#[derive(EnvConfig)]
+#[env_cfg(no_prefix)]
+struct UpstreamConfig {
+    host: String,
+}
+
+#[derive(EnvConfig)]
+struct InvalidConfig {
+    #[env_cfg(sections = "UPSTREAM")]
+    upstreams: Vec<UpstreamConfig>,
+}
+
+fn main() {}
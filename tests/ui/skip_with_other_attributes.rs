@@ -0,0 +1,9 @@
+use env_cfg::EnvConfig;
+
+#[derive(EnvConfig)]
+struct InvalidConfig {
+    #[env_cfg(skip, default = "5")]
+    retries: u8,
+}
+
+fn main() {}
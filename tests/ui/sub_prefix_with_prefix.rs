@@ -0,0 +1,9 @@
+use env_cfg::EnvConfig;
+
+#[derive(EnvConfig)]
+#[env_cfg(prefix = "APP", sub_prefix = "DB")]
+struct InvalidConfig {
+    host: String,
+}
+
+fn main() {}
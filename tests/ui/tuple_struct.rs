@@ -0,0 +1,6 @@
+use env_cfg::EnvConfig;
+
+#[derive(EnvConfig)]
+struct InvalidConfig(String);
+
+fn main() {}
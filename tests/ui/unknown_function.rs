@@ -0,0 +1,18 @@
+use env_cfg::EnvConfig;
+
+mod parsers {}
+
+#[derive(EnvConfig)]
+#[env_cfg(validate = "chek_config")]
+struct InvalidConfig {
+    #[env_cfg(parse_with = "prase_point")]
+    point: (i32, i32),
+    #[env_cfg(parse_with = "parsers::parse_point")]
+    origin: (i32, i32),
+    #[env_cfg(transform = "normalise")]
+    scale: u32,
+    #[env_cfg(parse_with_env = "load_origins")]
+    origins: Vec<(i32, i32)>,
+}
+
+fn main() {}
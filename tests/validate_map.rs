@@ -0,0 +1,96 @@
+// `validate_map` tests
+use env_cfg::{EnvConfig, EnvConfigError};
+
+mod common;
+
+fn canonicalize_url(url: String) -> Result<String, String> {
+    let Some((scheme, rest)) = url.split_once("://") else {
+        return Err(format!("`{url}` is not a URL"));
+    };
+    Ok(format!(
+        "{}://{}",
+        scheme.to_ascii_lowercase(),
+        rest.trim_end_matches('/')
+    ))
+}
+
+fn nonzero_workers(workers: u32) -> Result<u32, String> {
+    if workers == 0 {
+        return Err("at least one worker is required".to_string());
+    }
+    Ok(workers)
+}
+
+#[derive(Debug, EnvConfig)]
+#[env_cfg(prefix = "UPSTREAM")]
+struct UpstreamConfig {
+    #[env_cfg(validate_map = "canonicalize_url")]
+    url: String, // -> UPSTREAM_URL
+    #[env_cfg(validate_map = "canonicalize_url", default = "HTTP://localhost/")]
+    fallback_url: String, // -> UPSTREAM_FALLBACK_URL (default is canonicalized too)
+    #[env_cfg(validate_map = "canonicalize_url")]
+    mirror_url: Option<String>, // -> UPSTREAM_MIRROR_URL (optional)
+    #[env_cfg(validate_map = "nonzero_workers", default = 4)]
+    workers: u32, // -> UPSTREAM_WORKERS (typed default)
+}
+
+#[allow(dead_code)]
+#[derive(Debug, EnvConfig)]
+#[env_cfg(prefix = "BROKEN")]
+struct InvalidDefaultConfig {
+    #[env_cfg(validate_map = "nonzero_workers", default = "0")]
+    workers: u32, // -> BROKEN_WORKERS (default fails validation)
+}
+
+#[test]
+fn should_replace_values_with_validated_values() {
+    const ENV_KEYS_VALUES: &[(&str, &str)] = &[
+        ("UPSTREAM_URL", "HTTPS://api.example.com/"),
+        ("UPSTREAM_MIRROR_URL", "Https://mirror.example.com//"),
+    ];
+    let config =
+        unsafe { common::with_env_vars(ENV_KEYS_VALUES, || UpstreamConfig::from_env().unwrap()) };
+
+    assert_eq!(config.url, "https://api.example.com");
+    assert_eq!(config.fallback_url, "http://localhost");
+    assert_eq!(
+        config.mirror_url.as_deref(),
+        Some("https://mirror.example.com")
+    );
+    assert_eq!(config.workers, 4);
+}
+
+#[test]
+fn should_report_validation_errors_as_parse_errors() {
+    const ENV_KEYS_VALUES: &[(&str, &str)] = &[("UPSTREAM_URL", "api.example.com")];
+    let result = unsafe { common::with_env_vars(ENV_KEYS_VALUES, UpstreamConfig::from_env) };
+    assert_eq!(
+        result.unwrap_err(),
+        EnvConfigError::Parse(
+            "UPSTREAM_URL".to_string(),
+            "`api.example.com` is not a URL".to_string()
+        )
+    );
+
+    const ZERO_WORKERS: &[(&str, &str)] = &[
+        ("UPSTREAM_URL", "https://api.example.com"),
+        ("UPSTREAM_WORKERS", "0"),
+    ];
+    let result = unsafe { common::with_env_vars(ZERO_WORKERS, UpstreamConfig::from_env) };
+    assert!(matches!(
+        result,
+        Err(EnvConfigError::Parse(var, _)) if var == "UPSTREAM_WORKERS"
+    ));
+}
+
+#[test]
+fn should_validate_defaults() {
+    let result = unsafe { common::with_env_vars(&[], InvalidDefaultConfig::from_env) };
+    assert_eq!(
+        result.unwrap_err(),
+        EnvConfigError::Parse(
+            "default for BROKEN_WORKERS".to_string(),
+            "at least one worker is required".to_string()
+        )
+    );
+}
@@ -0,0 +1,72 @@
+// Value case normalization tests
+use std::str::FromStr;
+
+use env_cfg::EnvConfig;
+
+mod common;
+
+#[derive(Debug, PartialEq)]
+enum LogLevel {
+    Debug,
+    Info,
+}
+
+impl FromStr for LogLevel {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "debug" => Ok(LogLevel::Debug),
+            "info" => Ok(LogLevel::Info),
+            other => Err(format!("unknown log level '{other}'")),
+        }
+    }
+}
+
+#[derive(Debug, EnvConfig)]
+#[env_cfg(no_prefix, rename_all_values = "lowercase")]
+struct LoggingConfig {
+    log_level: LogLevel, // -> LOG_LEVEL
+    #[env_cfg(default = "info")]
+    audit_level: LogLevel, // -> AUDIT_LEVEL
+    #[env_cfg(default = "INFO")]
+    trace_level: LogLevel, // -> TRACE_LEVEL (default is lowercased too)
+    log_format: Option<String>, // -> LOG_FORMAT
+    #[env_cfg(preserve_case)]
+    log_token: String, // -> LOG_TOKEN (case is significant)
+}
+
+#[test]
+fn should_lowercase_values_unless_preserved() {
+    const ENV_KEYS_VALUES: &[(&str, &str)] = &[
+        ("LOG_LEVEL", "DEBUG"),
+        ("AUDIT_LEVEL", "Info"),
+        ("LOG_FORMAT", "JSON"),
+        ("LOG_TOKEN", "AbC123"),
+    ];
+
+    let config =
+        unsafe { common::with_env_vars(ENV_KEYS_VALUES, || LoggingConfig::from_env().unwrap()) };
+
+    assert_eq!(config.log_level, LogLevel::Debug);
+    assert_eq!(config.audit_level, LogLevel::Info);
+    assert_eq!(config.trace_level, LogLevel::Info);
+    assert_eq!(config.log_format.as_deref(), Some("json"));
+    assert_eq!(config.log_token, "AbC123");
+}
+
+#[derive(Debug, EnvConfig)]
+#[env_cfg(prefix = "REGION", rename_all_values = "UPPERCASE")]
+struct RegionConfig {
+    code: String, // -> REGION_CODE
+}
+
+#[test]
+fn should_uppercase_values() {
+    const ENV_KEYS_VALUES: &[(&str, &str)] = &[("REGION_CODE", "eu-West-1")];
+
+    let config =
+        unsafe { common::with_env_vars(ENV_KEYS_VALUES, || RegionConfig::from_env().unwrap()) };
+
+    assert_eq!(config.code, "EU-WEST-1");
+}